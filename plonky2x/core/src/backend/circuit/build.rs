@@ -1,8 +1,9 @@
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use anyhow::Result;
 use log::{debug, trace};
 use plonky2::field::types::PrimeField64;
 use plonky2::iop::witness::PartialWitness;
@@ -10,16 +11,17 @@ use plonky2::plonk::circuit_data::CircuitData;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig, GenericHashOut};
 use plonky2::plonk::proof::ProofWithPublicInputs;
 use plonky2::plonk::prover::prove_with_partition_witness;
-use plonky2::util::serialization::{Buffer, GateSerializer, IoResult, Read, Write};
+use plonky2::util::serialization::{Buffer, GateSerializer, IoError, IoResult, Read, Write};
 use plonky2::util::timing::TimingTree;
 
 use super::config::PlonkParameters;
 use super::input::PublicInput;
-use super::output::PublicOutput;
+use super::output::{CircuitOutput, PublicOutput};
 use super::serialization::hints::HintSerializer;
 use super::serialization::{GateRegistry, HintRegistry};
 use super::witness::{generate_witness, generate_witness_async};
-use crate::frontend::builder::CircuitIO;
+use crate::backend::prover::{ProveMetrics, ProvingBackend};
+use crate::frontend::builder::{CircuitIO, IOLabel};
 use crate::frontend::hint::asynchronous::generator::AsyncHintDataRef;
 use crate::utils::hex;
 use crate::utils::serde::{BufferRead, BufferWrite};
@@ -32,6 +34,32 @@ pub struct CircuitBuild<L: PlonkParameters<D>, const D: usize> {
     pub data: CircuitData<L::Field, L::Config, D>,
     pub io: CircuitIO<D>,
     pub async_hints: BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    /// The number of gates in the circuit, before padding to a power of two.
+    pub num_gates: usize,
+    /// The names of the inputs read with `read_named`.
+    pub input_labels: Vec<IOLabel>,
+    /// The names of the outputs registered with `register_public_output`.
+    pub output_labels: Vec<IOLabel>,
+    /// The ids of the generators that fetch their values from the network.
+    pub network_generator_ids: BTreeSet<String>,
+}
+
+/// Size statistics of a compiled circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// The number of gates in the circuit, before padding to a power of two.
+    ///
+    /// This does not include the gates that plonky2 adds while building, such as the ones for
+    /// constants, lookup tables and the hashing of the public inputs.
+    pub num_gates: usize,
+    /// The log2 of the number of rows after padding.
+    pub degree_bits: usize,
+    /// The number of distinct gate types.
+    pub num_gate_types: usize,
+    /// The number of public inputs.
+    pub num_public_inputs: usize,
+    /// The number of witness generators.
+    pub num_generators: usize,
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
@@ -49,6 +77,26 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         ProofWithPublicInputs<L::Field, L::Config, D>,
         PublicOutput<L, D>,
     )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let (proof_with_pis, output, _, _) = self.prove_with_partial_witness_timed(pw);
+        (proof_with_pis, output)
+    }
+
+    /// Like `prove_with_partial_witness`, but also returns the time spent generating the witness
+    /// and the time spent generating the proof from it.
+    #[allow(clippy::type_complexity)]
+    fn prove_with_partial_witness_timed(
+        &self,
+        pw: PartialWitness<L::Field>,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        PublicOutput<L, D>,
+        Duration,
+        Duration,
+    )
     where
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
             AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
@@ -62,8 +110,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
             &self.async_hints,
         )
         .unwrap();
-        let elapsed_time = start_time.elapsed();
-        debug!("Witness generation took {:?}", elapsed_time);
+        let witness_time = start_time.elapsed();
+        debug!("Witness generation took {:?}", witness_time);
         trace!("finished generating witness");
         trace!("generating proof...");
         let proof_with_pis = prove_with_partition_witness::<L::Field, L::Config, D>(
@@ -77,7 +125,12 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         let output = PublicOutput::from_proof_with_pis(&self.io, &proof_with_pis);
         let elapsed_time = start_time.elapsed();
         debug!("proving took: {:?}", elapsed_time);
-        (proof_with_pis, output)
+        (
+            proof_with_pis,
+            output,
+            witness_time,
+            elapsed_time - witness_time,
+        )
     }
 
     /// Generates a proof for the circuit. The proof can be verified using `verify`.
@@ -86,7 +139,28 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         input: &PublicInput<L, D>,
     ) -> (
         ProofWithPublicInputs<L::Field, L::Config, D>,
-        PublicOutput<L, D>,
+        CircuitOutput<L, D>,
+    )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let mut pw = PartialWitness::new();
+        self.io.set_witness(&mut pw, input);
+        let (proof, output) = self.prove_with_partial_witness(pw);
+        (proof, CircuitOutput::new(output, &self.output_labels))
+    }
+
+    /// Generates a proof for the circuit, along with its timing and size metrics. The proof can be
+    /// verified using `verify`.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_with_metrics(
+        &self,
+        input: &PublicInput<L, D>,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+        ProveMetrics,
     )
     where
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
@@ -94,7 +168,31 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
     {
         let mut pw = PartialWitness::new();
         self.io.set_witness(&mut pw, input);
-        self.prove_with_partial_witness(pw)
+        let (proof, output, witness_time, prove_time) = self.prove_with_partial_witness_timed(pw);
+        let metrics = ProveMetrics::new(witness_time, prove_time, proof.to_bytes().len());
+        (
+            proof,
+            CircuitOutput::new(output, &self.output_labels),
+            metrics,
+        )
+    }
+
+    /// Generates a proof for the circuit with the given backend. The proof can be verified using
+    /// `verify`.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_with<B: ProvingBackend<L, D>>(
+        &self,
+        backend: &B,
+        input: &PublicInput<L, D>,
+    ) -> Result<(
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+    )>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        backend.prove(self, input)
     }
 
     /// Generates a proof for the circuit using a plonky2 partial witness. The proof can be verified
@@ -145,7 +243,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         input: &PublicInput<L, D>,
     ) -> (
         ProofWithPublicInputs<L::Field, L::Config, D>,
-        PublicOutput<L, D>,
+        CircuitOutput<L, D>,
     )
     where
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
@@ -153,7 +251,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
     {
         let mut pw = PartialWitness::new();
         self.io.set_witness(&mut pw, input);
-        self.prove_with_partial_witness_async(pw).await
+        let (proof, output) = self.prove_with_partial_witness_async(pw).await;
+        (proof, CircuitOutput::new(output, &self.output_labels))
     }
 
     /// Verifies a proof for the circuit.
@@ -183,6 +282,30 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         circuit_digest[0..22].to_string()
     }
 
+    /// Splits the field elements of `output` by the names registered with
+    /// `register_public_output`.
+    pub fn labeled_output(&self, output: &PublicOutput<L, D>) -> BTreeMap<String, Vec<L::Field>> {
+        let elements = match output {
+            PublicOutput::Elements(elements) => elements,
+            _ => panic!("labeled outputs require field io"),
+        };
+        self.output_labels
+            .iter()
+            .map(|label| (label.name.clone(), elements[label.range.clone()].to_vec()))
+            .collect()
+    }
+
+    /// Returns size statistics of the circuit.
+    pub fn stats(&self) -> CircuitStats {
+        CircuitStats {
+            num_gates: self.num_gates,
+            degree_bits: self.data.common.degree_bits(),
+            num_gate_types: self.data.common.gates.len(),
+            num_public_inputs: self.data.common.num_public_inputs,
+            num_generators: self.data.prover_only.generators.len(),
+        }
+    }
+
     /// Serializes the circuit to bytes.
     pub fn serialize(
         &self,
@@ -194,7 +317,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         let data = self.data.to_bytes(gate_serializer, hint_serializer)?;
         buffer.write_bytes(&data)?;
 
-        let io = bincode::serialize(&self.io).unwrap();
+        let io = bincode::serialize(&self.io).map_err(|_| IoError)?;
         buffer.write_bytes(&io)?;
 
         // serialize the async generator map
@@ -205,6 +328,20 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
             hint_serializer.write_async_hint(&mut buffer, hint_data, &self.data.common)?;
         }
 
+        buffer.write_usize(self.num_gates)?;
+
+        // The output labels keep the slot they were first serialized in, and the input labels
+        // follow in their own slot.
+        let output_labels = bincode::serialize(&self.output_labels).map_err(|_| IoError)?;
+        buffer.write_bytes(&output_labels)?;
+
+        let input_labels = bincode::serialize(&self.input_labels).map_err(|_| IoError)?;
+        buffer.write_bytes(&input_labels)?;
+
+        let network_generator_ids =
+            bincode::serialize(&self.network_generator_ids).map_err(|_| IoError)?;
+        buffer.write_bytes(&network_generator_ids)?;
+
         Ok(buffer)
     }
 
@@ -224,7 +361,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         )?;
 
         let io = buffer.read_bytes()?;
-        let io: CircuitIO<D> = bincode::deserialize(&io).unwrap();
+        let io: CircuitIO<D> = bincode::deserialize(&io).map_err(|_| IoError)?;
 
         let mut async_hints = BTreeMap::new();
         let map_size = buffer.read_usize()?;
@@ -234,10 +371,42 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
             async_hints.insert(key, hint_data);
         }
 
+        // Circuits serialized before the gate count was recorded fall back to the padded degree.
+        let num_gates = if buffer.unread_bytes().is_empty() {
+            data.common.degree()
+        } else {
+            buffer.read_usize()?
+        };
+
+        let output_labels = if buffer.unread_bytes().is_empty() {
+            Vec::new()
+        } else {
+            let output_labels = buffer.read_bytes()?;
+            bincode::deserialize(&output_labels).map_err(|_| IoError)?
+        };
+
+        let input_labels = if buffer.unread_bytes().is_empty() {
+            Vec::new()
+        } else {
+            let input_labels = buffer.read_bytes()?;
+            bincode::deserialize(&input_labels).map_err(|_| IoError)?
+        };
+
+        let network_generator_ids = if buffer.unread_bytes().is_empty() {
+            BTreeSet::new()
+        } else {
+            let ids = buffer.read_bytes()?;
+            bincode::deserialize(&ids).map_err(|_| IoError)?
+        };
+
         Ok(CircuitBuild {
             data,
             io,
             async_hints,
+            num_gates,
+            input_labels,
+            output_labels,
+            network_generator_ids,
         })
     }
 
@@ -298,12 +467,43 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
 #[cfg(test)]
 pub(crate) mod tests {
 
+    use ethers::types::{H256, U256};
+
     use crate::backend::circuit::CircuitBuild;
+    use crate::frontend::builder::{IOLabel, ValueEncoding};
     use crate::prelude::*;
+    use crate::utils::assert_circuit_size;
 
     type L = DefaultParameters;
     const D: usize = 2;
 
+    #[test]
+    fn test_assert_circuit_size() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Variable>();
+        let b = builder.read::<Variable>();
+        let c = builder.add(a, b);
+        builder.write(c);
+
+        let circuit = assert_circuit_size!(builder, 1 << 10);
+        let stats = circuit.stats();
+        assert!(stats.num_gates <= 1 << 10);
+        assert!(stats.num_gates <= 1 << stats.degree_bits);
+        assert_eq!(stats.num_public_inputs, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the bound of 1 gates")]
+    fn test_assert_circuit_size_fails() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<U64Variable>();
+        let b = builder.read::<U64Variable>();
+        let c = builder.mul(a, b);
+        builder.write(c);
+
+        assert_circuit_size!(builder, 1);
+    }
+
     #[test]
     fn test_serialize_with_field_io() {
         // Define your circuit.
@@ -421,4 +621,85 @@ pub(crate) mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_serialize_output_labels() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read_named::<U64Variable>("a");
+        let b = builder.read_named::<Bytes32Variable>("b");
+        let c = builder.add(a, a);
+        builder.register_public_output("sum", &c);
+        builder.register_public_output("root", &b);
+        let circuit = builder.build();
+        let input_labels = circuit.input_labels.clone();
+        assert_eq!(input_labels.len(), 2);
+
+        let gate_serializer = GateRegistry::<L, D>::new();
+        let hint_serializer = HintRegistry::<L, D>::new();
+        let bytes = circuit
+            .serialize(&gate_serializer, &hint_serializer)
+            .unwrap();
+        let circuit =
+            CircuitBuild::<L, D>::deserialize(&bytes, &gate_serializer, &hint_serializer).unwrap();
+
+        assert_eq!(circuit.input_labels, input_labels);
+
+        let sum_len = U64Variable::nb_elements();
+        let root_len = Bytes32Variable::nb_elements();
+        assert_eq!(
+            circuit.output_labels,
+            vec![
+                IOLabel {
+                    name: "sum".to_string(),
+                    variable_type: "U64Variable".to_string(),
+                    byte_length: 8,
+                    encoding: ValueEncoding::Decimal,
+                    range: 0..sum_len,
+                },
+                IOLabel {
+                    name: "root".to_string(),
+                    variable_type: "Bytes32Variable".to_string(),
+                    byte_length: 32,
+                    encoding: ValueEncoding::Hex,
+                    range: sum_len..sum_len + root_len,
+                },
+            ]
+        );
+
+        let mut input = circuit.input();
+        input.write::<U64Variable>(21);
+        input.write::<Bytes32Variable>(H256::repeat_byte(7));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let labeled = circuit.labeled_output(&output);
+        assert_eq!(
+            U64Variable::from_elements::<GoldilocksField>(&labeled["sum"]),
+            42
+        );
+        assert_eq!(
+            Bytes32Variable::from_elements::<GoldilocksField>(&labeled["root"]),
+            H256::repeat_byte(7)
+        );
+    }
+
+    #[test]
+    fn test_circuit_output_read_and_get() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<U256Variable>();
+        let b = builder.read::<Bytes32Variable>();
+        let c = builder.add(a, a);
+        builder.write(c);
+        builder.register_public_output("root", &b);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<U256Variable>(U256::from(21));
+        input.write::<Bytes32Variable>(H256::repeat_byte(7));
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U256Variable>(), U256::from(42));
+        assert_eq!(output.get::<Bytes32Variable>("root"), H256::repeat_byte(7));
+    }
 }
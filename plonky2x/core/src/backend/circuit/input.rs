@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use super::PlonkParameters;
 use crate::backend::prover::ProofId;
 use crate::frontend::builder::CircuitIO;
-use crate::frontend::vars::{EvmVariable, ValueStream};
+use crate::frontend::vars::{EvmVariable, ValueSerializable, ValueStream};
 use crate::prelude::{ByteVariable, CircuitVariable};
 
 /// Public inputs to the circuit. In the form of bytes, field elements, or recursive proofs.
@@ -105,7 +105,7 @@ impl<L: PlonkParameters<D>, const D: usize> PublicInput<L, D> {
     pub fn evm_write<V: EvmVariable>(&mut self, value: V::ValueType<L::Field>) {
         match self {
             PublicInput::Bytes(input) => {
-                let bytes = V::encode_value(value);
+                let bytes = V::to_bytes(value).unwrap_or_else(|e| panic!("{}", e));
                 input.extend(bytes);
             }
             _ => panic!("evm io is not enabled"),
@@ -3,22 +3,30 @@ pub mod config;
 mod input;
 mod mock;
 mod output;
+mod prepared;
+mod schema;
 mod serialization;
+mod verifier;
 mod witness;
 
 use core::fmt::Debug;
 
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 
-pub use self::build::CircuitBuild;
+pub use self::build::{CircuitBuild, CircuitStats};
 pub use self::config::{DefaultParameters, Groth16WrapperParameters, PlonkParameters};
 pub use self::input::PublicInput;
 pub use self::mock::MockCircuitBuild;
-pub use self::output::PublicOutput;
+pub use self::output::{CircuitOutput, DecodedOutputs, PublicOutput};
+pub use self::prepared::PreparedInputs;
 pub use self::serialization::{
     CircuitSerializer, DefaultSerializer, GateRegistry, HintRegistry, Serializer,
 };
-pub use self::witness::{generate_witness, generate_witness_async};
+pub use self::verifier::VerifierCircuit;
+pub use self::witness::{
+    generate_witness, generate_witness_async, generate_witness_with_prepared,
+    generate_witness_with_scratch, NetworkValues, WitnessScratch,
+};
 use crate::prelude::CircuitBuilder;
 
 pub trait Circuit: Debug + Clone + Send + Sync + 'static {
@@ -1,11 +1,14 @@
+use alloc::collections::BTreeMap;
+use core::ops::{Deref, DerefMut};
+
 use itertools::Itertools;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::proof::ProofWithPublicInputs;
 use serde::{Deserialize, Serialize};
 
 use super::PlonkParameters;
-use crate::frontend::builder::CircuitIO;
-use crate::frontend::vars::{EvmVariable, ValueStream};
+use crate::frontend::builder::{CircuitIO, IOLabel, SchemaVariable};
+use crate::frontend::vars::{EvmVariable, ValueSerializable, ValueStream};
 use crate::prelude::{ByteVariable, CircuitVariable};
 
 /// An output from the circuit. Can either be in the form of bytes, field elements, or proofs.
@@ -108,9 +111,8 @@ impl<L: PlonkParameters<D>, const D: usize> PublicOutput<L, D> {
     pub fn evm_read<V: EvmVariable>(&mut self) -> V::ValueType<L::Field> {
         match self {
             PublicOutput::Bytes(output) => {
-                let nb_bytes = V::nb_bytes::<L, D>();
-                let bytes = output.drain(0..nb_bytes).collect_vec();
-                V::decode_value(bytes.as_slice())
+                let bytes = output.drain(0..V::nb_value_bytes()).collect_vec();
+                V::from_bytes(bytes.as_slice()).unwrap()
             }
             _ => panic!("evm io is not enabled"),
         }
@@ -130,3 +132,99 @@ impl<L: PlonkParameters<D>, const D: usize> PublicOutput<L, D> {
         todo!()
     }
 }
+
+/// The outputs registered with `register_public_output`, decoded by name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedOutputs<L: PlonkParameters<D>, const D: usize> {
+    named: BTreeMap<String, (String, Vec<L::Field>)>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> DecodedOutputs<L, D> {
+    /// Splits the field elements of the circuit output by the given output labels.
+    pub fn new(elements: &[L::Field], labels: &[IOLabel]) -> Self {
+        let named = labels
+            .iter()
+            .map(|label| {
+                assert!(
+                    label.range.end <= elements.len(),
+                    "public output {} is out of range of the {} output elements",
+                    label.name,
+                    elements.len()
+                );
+                let value = (
+                    label.variable_type.clone(),
+                    elements[label.range.clone()].to_vec(),
+                );
+                (label.name.clone(), value)
+            })
+            .collect();
+        Self { named }
+    }
+
+    /// Reads the output registered under `name`.
+    pub fn get<V: SchemaVariable>(&self, name: &str) -> V::ValueType<L::Field> {
+        let (variable_type, elements) = self
+            .named
+            .get(name)
+            .unwrap_or_else(|| panic!("no public output is registered as {}", name));
+        assert_eq!(
+            variable_type,
+            &V::schema_type(),
+            "public output {} is a {}",
+            name,
+            variable_type
+        );
+        V::from_elements::<L::Field>(elements)
+    }
+}
+
+/// The output of a proven circuit.
+///
+/// Dereferences to the [`PublicOutput`], so values can be read in the order they were written with
+/// `read` and `evm_read`. Outputs registered with `register_public_output` can also be looked up by
+/// name with [`CircuitOutput::get`], independently of the values read so far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitOutput<L: PlonkParameters<D>, const D: usize> {
+    output: PublicOutput<L, D>,
+    named: DecodedOutputs<L, D>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitOutput<L, D> {
+    /// Splits the field elements of `output` by the given output labels.
+    pub fn new(output: PublicOutput<L, D>, labels: &[IOLabel]) -> Self {
+        let named = match output {
+            PublicOutput::Elements(ref elements) => DecodedOutputs::new(elements, labels),
+            _ => DecodedOutputs::new(&[], &[]),
+        };
+        Self { output, named }
+    }
+
+    /// Reads the output registered under `name`.
+    pub fn get<V: SchemaVariable>(&self, name: &str) -> V::ValueType<L::Field> {
+        self.named.get::<V>(name)
+    }
+
+    pub fn into_public_output(self) -> PublicOutput<L, D> {
+        self.output
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Deref for CircuitOutput<L, D> {
+    type Target = PublicOutput<L, D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.output
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> DerefMut for CircuitOutput<L, D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.output
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> From<CircuitOutput<L, D>> for PublicOutput<L, D> {
+    fn from(output: CircuitOutput<L, D>) -> Self {
+        output.output
+    }
+}
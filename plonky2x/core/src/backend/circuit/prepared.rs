@@ -0,0 +1,145 @@
+use alloc::collections::BTreeMap;
+use std::fs;
+
+use anyhow::{anyhow, ensure, Result};
+use plonky2::field::types::{Field, PrimeField64};
+use plonky2::iop::target::Target;
+use plonky2::iop::wire::Wire;
+use serde::{Deserialize, Serialize};
+
+/// The values written by the network-backed generators of a circuit, captured ahead of proving.
+///
+/// Preparing the inputs runs witness generation on a machine with network access and records the
+/// values set by the generators that opt in, either as hints with [`Hint::NETWORK`] set or as
+/// generators added with [`CircuitBuilder::add_network_simple_generator`]. Proving with the
+/// prepared inputs sets those values instead of running the generators, so that the proof can be
+/// generated without network access. All other generators still run when proving.
+///
+/// [`Hint::NETWORK`]: crate::frontend::hint::simple::hint::Hint::NETWORK
+/// [`CircuitBuilder::add_network_simple_generator`]: crate::frontend::builder::CircuitBuilder::add_network_simple_generator
+///
+/// Prepared inputs are only valid for the circuit and the input they were prepared with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreparedInputs {
+    circuit_id: String,
+    generators: BTreeMap<usize, PreparedGenerator>,
+}
+
+/// The values set by a network-backed generator, stored as canonical `u64`s.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PreparedGenerator {
+    id: String,
+    values: Vec<(PreparedTarget, u64)>,
+}
+
+/// A serializable [`Target`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum PreparedTarget {
+    Wire { row: usize, column: usize },
+    Virtual { index: usize },
+}
+
+impl From<Target> for PreparedTarget {
+    fn from(target: Target) -> Self {
+        match target {
+            Target::Wire(Wire { row, column }) => Self::Wire { row, column },
+            Target::VirtualTarget { index } => Self::Virtual { index },
+        }
+    }
+}
+
+impl From<PreparedTarget> for Target {
+    fn from(target: PreparedTarget) -> Self {
+        match target {
+            PreparedTarget::Wire { row, column } => Target::Wire(Wire { row, column }),
+            PreparedTarget::Virtual { index } => Target::VirtualTarget { index },
+        }
+    }
+}
+
+impl PreparedInputs {
+    pub(crate) fn new(circuit_id: String) -> Self {
+        Self {
+            circuit_id,
+            generators: BTreeMap::new(),
+        }
+    }
+
+    /// The id of the circuit the inputs were prepared for.
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// The number of network-backed generators whose values were captured.
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    /// Fails if the inputs were not prepared for the circuit with the given id.
+    pub(crate) fn check_circuit(&self, circuit_id: &str) -> Result<()> {
+        ensure!(
+            self.circuit_id == circuit_id,
+            "prepared inputs are for circuit {}, not {}",
+            self.circuit_id,
+            circuit_id
+        );
+        Ok(())
+    }
+
+    /// Records the values set by the generator at `index` with the given id.
+    pub(crate) fn record<F: PrimeField64>(
+        &mut self,
+        index: usize,
+        id: &str,
+        values: &[(Target, F)],
+    ) {
+        let generator = self
+            .generators
+            .entry(index)
+            .or_insert_with(|| PreparedGenerator {
+                id: id.to_string(),
+                values: Vec::new(),
+            });
+        generator.values.extend(
+            values
+                .iter()
+                .map(|(t, v)| ((*t).into(), v.to_canonical_u64())),
+        );
+    }
+
+    /// Returns the values recorded for the generator at `index` with the given id.
+    pub(crate) fn replay<F: Field>(&self, index: usize, id: &str) -> Result<Vec<(Target, F)>> {
+        let generator = self.generators.get(&index).ok_or_else(|| {
+            anyhow!(
+                "prepared inputs are missing the values of generator {} ({})",
+                index,
+                id
+            )
+        })?;
+        ensure!(
+            generator.id == id,
+            "prepared values of generator {} are for {}, not {}",
+            index,
+            generator.id,
+            id
+        );
+        Ok(generator
+            .values
+            .iter()
+            .map(|(t, v)| ((*t).into(), F::from_canonical_u64(*v)))
+            .collect())
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
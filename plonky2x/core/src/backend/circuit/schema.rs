@@ -0,0 +1,264 @@
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use ethers::types::U256;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use serde_json::{json, Value};
+
+use super::{CircuitBuild, PlonkParameters, PublicInput};
+use crate::frontend::builder::{CircuitIO, IOLabel, SchemaVariable, ValueEncoding};
+use crate::frontend::eth::vars::{AddressVariable, BLSPubkeyVariable};
+use crate::prelude::{
+    BoolVariable, ByteVariable, Bytes32Variable, CircuitVariable, U128Variable, U256Variable,
+    U32Variable, U64Variable, ValueSerializable, Variable,
+};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
+    /// Describes the labeled inputs and outputs of the circuit.
+    ///
+    /// Each entry has the name, variable type, byte length and JSON encoding of a value, along
+    /// with the range of field elements it occupies.
+    pub fn io_schema(&self) -> Value {
+        json!({
+            "inputs": self.input_labels,
+            "outputs": self.output_labels,
+        })
+    }
+
+    /// Parses a labeled JSON object into the public input of the circuit, validating it against
+    /// the schema returned by `io_schema`.
+    pub fn input_from_json(&self, json: &Value) -> Result<PublicInput<L, D>> {
+        let fields = json
+            .as_object()
+            .ok_or_else(|| anyhow!("expected the input to be a JSON object"))?;
+        let num_inputs = match &self.io {
+            CircuitIO::Elements(io) => io.input.len(),
+            _ => bail!("labeled inputs require field io"),
+        };
+        let num_labeled_inputs = self
+            .input_labels
+            .iter()
+            .map(|label| label.range.len())
+            .sum::<usize>();
+        ensure!(
+            num_labeled_inputs == num_inputs,
+            "the circuit has inputs that are not labeled"
+        );
+
+        for name in fields.keys() {
+            ensure!(
+                self.input_labels.iter().any(|label| &label.name == name),
+                "unknown input field `{}`",
+                name
+            );
+        }
+
+        let mut elements = vec![L::Field::ZERO; num_inputs];
+        for label in self.input_labels.iter() {
+            let value = fields
+                .get(&label.name)
+                .ok_or_else(|| anyhow!("missing input field `{}`", label.name))?;
+            let value_elements = parse_value::<L::Field>(label, value)
+                .with_context(|| format!("invalid value for input field `{}`", label.name))?;
+            elements[label.range.clone()].copy_from_slice(&value_elements);
+        }
+        Ok(PublicInput::Elements(elements))
+    }
+}
+
+/// Converts a JSON value into the field elements of the labeled variable.
+fn parse_value<F: RichField>(label: &IOLabel, value: &Value) -> Result<Vec<F>> {
+    let bytes = value_to_bytes(label, value)?;
+    let elements = bytes_to_elements::<F>(label, &bytes)?;
+    ensure!(
+        elements.len() == label.range.len(),
+        "expected {} field elements, got {}",
+        label.range.len(),
+        elements.len()
+    );
+    Ok(elements)
+}
+
+/// Converts a JSON value into the `ValueSerializable` encoding of the labeled variable.
+fn value_to_bytes(label: &IOLabel, value: &Value) -> Result<Vec<u8>> {
+    let bytes = match label.encoding {
+        ValueEncoding::Hex => {
+            let value = value
+                .as_str()
+                .ok_or_else(|| anyhow!("expected a hex string"))?;
+            hex::decode(value.strip_prefix("0x").unwrap_or(value))?
+        }
+        ValueEncoding::Bool => {
+            let value = value.as_bool().ok_or_else(|| anyhow!("expected a bool"))?;
+            vec![value as u8]
+        }
+        ValueEncoding::Decimal => {
+            let value = match value {
+                Value::Number(n) => U256::from(
+                    n.as_u64()
+                        .ok_or_else(|| anyhow!("expected a non-negative integer"))?,
+                ),
+                Value::String(s) => U256::from_dec_str(s)
+                    .map_err(|_| anyhow!("expected a decimal string, got {}", s))?,
+                _ => bail!("expected a decimal number or string"),
+            };
+            ensure!(
+                value.bits() <= label.byte_length * 8,
+                "value does not fit in {} bytes",
+                label.byte_length
+            );
+            let mut bytes = [0u8; 32];
+            value.to_big_endian(&mut bytes);
+            bytes[32 - label.byte_length..].to_vec()
+        }
+    };
+    ensure!(
+        bytes.len() == label.byte_length,
+        "expected {} bytes, got {}",
+        label.byte_length,
+        bytes.len()
+    );
+    Ok(bytes)
+}
+
+/// Decodes the `ValueSerializable` encoding of the labeled variable into its field elements.
+fn bytes_to_elements<F: RichField>(label: &IOLabel, bytes: &[u8]) -> Result<Vec<F>> {
+    macro_rules! decode {
+        ($($type:ty),*) => {
+            $(
+                if label.variable_type == <$type>::schema_type() {
+                    return Ok(<$type>::elements(<$type>::from_bytes::<F>(bytes)?));
+                }
+            )*
+        };
+    }
+    decode!(
+        Variable,
+        BoolVariable,
+        U32Variable,
+        U64Variable,
+        U128Variable,
+        U256Variable,
+        ByteVariable,
+        Bytes32Variable,
+        AddressVariable,
+        BLSPubkeyVariable
+    );
+    if label.variable_type.starts_with("BytesVariable<") {
+        return Ok(bytes
+            .iter()
+            .flat_map(|b| ByteVariable::elements(*b))
+            .collect());
+    }
+    bail!("unsupported variable type {}", label.variable_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+    use serde_json::json;
+
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    fn circuit() -> crate::backend::circuit::CircuitBuild<L, D> {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let root = builder.read_named::<Bytes32Variable>("root");
+        let amount = builder.read_named::<U64Variable>("amount");
+        let double = builder.read_named::<BoolVariable>("double");
+        let sum = builder.add(amount, amount);
+        let doubled = builder.select(double, sum, amount);
+        builder.register_public_output("doubled", &doubled);
+        builder.register_public_output("root", &root);
+        builder.build()
+    }
+
+    #[test]
+    fn test_io_schema() {
+        let circuit = circuit();
+        let expected = json!({
+            "inputs": [
+                {
+                    "name": "root",
+                    "type": "Bytes32Variable",
+                    "byteLength": 32,
+                    "encoding": "hex",
+                    "range": { "start": 0, "end": 256 },
+                },
+                {
+                    "name": "amount",
+                    "type": "U64Variable",
+                    "byteLength": 8,
+                    "encoding": "decimal",
+                    "range": { "start": 256, "end": 258 },
+                },
+                {
+                    "name": "double",
+                    "type": "BoolVariable",
+                    "byteLength": 1,
+                    "encoding": "bool",
+                    "range": { "start": 258, "end": 259 },
+                },
+            ],
+            "outputs": [
+                {
+                    "name": "doubled",
+                    "type": "U64Variable",
+                    "byteLength": 8,
+                    "encoding": "decimal",
+                    "range": { "start": 0, "end": 2 },
+                },
+                {
+                    "name": "root",
+                    "type": "Bytes32Variable",
+                    "byteLength": 32,
+                    "encoding": "hex",
+                    "range": { "start": 2, "end": 258 },
+                },
+            ],
+        });
+        assert_eq!(circuit.io_schema(), expected);
+    }
+
+    #[test]
+    fn test_input_from_json() {
+        let circuit = circuit();
+        let input = circuit
+            .input_from_json(&json!({
+                "root": format!("{:?}", H256::repeat_byte(3)),
+                "amount": "21",
+                "double": true,
+            }))
+            .unwrap();
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<U64Variable>(), 42);
+        assert_eq!(output.read::<Bytes32Variable>(), H256::repeat_byte(3));
+    }
+
+    #[test]
+    fn test_input_from_json_malformed() {
+        let circuit = circuit();
+        let file = r#"{ "root": "0x1234", "amount": 21, "double": true }"#;
+        let json = serde_json::from_str(file).unwrap();
+        let err = circuit.input_from_json(&json).unwrap_err();
+        assert!(format!("{:#}", err).contains("`root`"), "{:#}", err);
+
+        let file =
+            r#"{ "root": "0x0000000000000000000000000000000000000000000000000000000000000000" }"#;
+        let json = serde_json::from_str(file).unwrap();
+        let err = circuit.input_from_json(&json).unwrap_err();
+        assert!(format!("{:#}", err).contains("`amount`"), "{:#}", err);
+
+        let file = r#"{
+            "root": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "amount": 21,
+            "double": 1
+        }"#;
+        let json = serde_json::from_str(file).unwrap();
+        let err = circuit.input_from_json(&json).unwrap_err();
+        assert!(format!("{:#}", err).contains("`double`"), "{:#}", err);
+    }
+}
@@ -38,25 +38,34 @@ use starkyx::plonky2::cubic::mul_gate::MulCubicGenerator;
 use super::registry::{SerializationRegistry, Serializer};
 use super::PlonkParameters;
 use crate as plonky2x;
+#[cfg(feature = "ecc")]
 use crate::frontend::ecc::curve25519::curta::proof_hint::EcOpProofHint;
+#[cfg(feature = "ecc")]
 use crate::frontend::ecc::curve25519::curta::result_hint::EcOpResultHint;
+#[cfg(feature = "beacon")]
 use crate::frontend::eth::beacon::generators::{
     BeaconAllWithdrawalsHint, BeaconBalanceBatchWitnessHint, BeaconBalanceGenerator,
-    BeaconBalanceWitnessHint, BeaconBalancesGenerator, BeaconBlockRootsHint, BeaconGraffitiHint,
+    BeaconBalanceWitnessHint, BeaconBalancesGenerator, BeaconBlockRootHint, BeaconBlockRootsHint,
+    BeaconExecutionPayloadHeaderHint, BeaconGenesisValidatorsRootHint, BeaconGraffitiHint,
     BeaconHeaderHint, BeaconHeadersFromOffsetRangeHint, BeaconHistoricalBlockHint,
-    BeaconPartialBalancesHint, BeaconPartialValidatorsHint, BeaconValidatorBatchHint,
-    BeaconValidatorGenerator, BeaconValidatorsGenerator, BeaconValidatorsHint,
-    BeaconWithdrawalGenerator, BeaconWithdrawalsGenerator, CompressedBeaconValidatorBatchHint,
+    BeaconHistoricalSummaryHint, BeaconPartialBalancesHint, BeaconPartialValidatorsHint,
+    BeaconRandaoMixHint, BeaconSyncCommitteeHint, BeaconValidatorBatchHint,
+    BeaconValidatorGenerator, BeaconValidatorsAndBalancesHint, BeaconValidatorsGenerator,
+    BeaconValidatorsHint, BeaconWithdrawalGenerator, BeaconWithdrawalsGenerator,
+    CompressedBeaconValidatorBatchHint,
 };
+#[cfg(feature = "beacon")]
 use crate::frontend::eth::beacon::vars::{
     BeaconBalancesVariable, BeaconHeaderVariable, BeaconValidatorVariable,
     BeaconValidatorsVariable, BeaconWithdrawalVariable, BeaconWithdrawalsVariable,
 };
+#[cfg(feature = "mpt")]
 use crate::frontend::eth::storage::generators::{
     EthBlockGenerator, EthLogGenerator, EthStorageKeyGenerator, EthStorageProofHint,
 };
 use crate::frontend::hash::curta::digest_hint::HashDigestHint;
 use crate::frontend::hash::curta::proof_hint::HashProofHint;
+#[cfg(feature = "keccak")]
 use crate::frontend::hash::keccak::keccak256::Keccak256Generator;
 use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
 use crate::frontend::hint::asynchronous::generator::{AsyncHintDataRef, AsyncHintRef};
@@ -71,6 +80,7 @@ use crate::frontend::uint::num::u32::gates::arithmetic_u32::U32ArithmeticGenerat
 use crate::frontend::uint::num::u32::gates::comparison::ComparisonGenerator;
 use crate::frontend::uint::num::u32::gates::range_check_u32::U32RangeCheckGenerator;
 use crate::frontend::uint::num::u32::gates::subtraction_u32::U32SubtractionGenerator;
+use crate::frontend::uint::uint32::U32ByteDecompositionHint;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{Bytes32Variable, SubArrayExtractorHint, U256Variable};
 use crate::prelude::{ArrayVariable, BoolVariable, U32Variable, Variable};
@@ -170,6 +180,7 @@ macro_rules! register_watch_generator {
     };
 }
 
+#[cfg(feature = "beacon")]
 macro_rules! register_powers_of_two {
     ($r:ident, $hint:ident) => {
         $r.register_hint::<$hint<2>>();
@@ -196,6 +207,7 @@ macro_rules! register_powers_of_two {
     };
 }
 
+#[cfg(feature = "beacon")]
 macro_rules! register_powers_of_two_async {
     ($r:ident, $hint:ident) => {
         $r.register_async_hint::<$hint<2>>();
@@ -323,35 +335,44 @@ where
             SimpleGenerator::<L::Field, D>::id(&WireSplitGenerator::default());
         r.register_simple::<WireSplitGenerator>(wire_split_generator_id);
 
-        let eth_log_generator_id = EthLogGenerator::<L, D>::id();
-        r.register_simple::<EthLogGenerator<L, D>>(eth_log_generator_id);
+        #[cfg(feature = "mpt")]
+        {
+            let eth_log_generator_id = EthLogGenerator::<L, D>::id();
+            r.register_simple::<EthLogGenerator<L, D>>(eth_log_generator_id);
 
-        let eth_block_generator_id = EthBlockGenerator::<L, D>::id();
-        r.register_simple::<EthBlockGenerator<L, D>>(eth_block_generator_id);
+            let eth_block_generator_id = EthBlockGenerator::<L, D>::id();
+            r.register_simple::<EthBlockGenerator<L, D>>(eth_block_generator_id);
 
-        let eth_storage_key_generator_id = EthStorageKeyGenerator::<L, D>::id();
-        r.register_simple::<EthStorageKeyGenerator<L, D>>(eth_storage_key_generator_id);
+            let eth_storage_key_generator_id = EthStorageKeyGenerator::<L, D>::id();
+            r.register_simple::<EthStorageKeyGenerator<L, D>>(eth_storage_key_generator_id);
+        }
 
-        let keccak256_generator_id = Keccak256Generator::<L, D>::id();
-        r.register_simple::<Keccak256Generator<L, D>>(keccak256_generator_id);
+        #[cfg(feature = "keccak")]
+        {
+            let keccak256_generator_id = Keccak256Generator::<L, D>::id();
+            r.register_simple::<Keccak256Generator<L, D>>(keccak256_generator_id);
+        }
 
-        let beacon_balance_generator_id = BeaconBalanceGenerator::<L, D>::id();
-        r.register_simple::<BeaconBalanceGenerator<L, D>>(beacon_balance_generator_id);
+        #[cfg(feature = "beacon")]
+        {
+            let beacon_balance_generator_id = BeaconBalanceGenerator::<L, D>::id();
+            r.register_simple::<BeaconBalanceGenerator<L, D>>(beacon_balance_generator_id);
 
-        let beacon_balances_generator_id = BeaconBalancesGenerator::<L, D>::id();
-        r.register_simple::<BeaconBalancesGenerator<L, D>>(beacon_balances_generator_id);
+            let beacon_balances_generator_id = BeaconBalancesGenerator::<L, D>::id();
+            r.register_simple::<BeaconBalancesGenerator<L, D>>(beacon_balances_generator_id);
 
-        let beacon_validator_generator_id = BeaconValidatorGenerator::<L, D>::id();
-        r.register_simple::<BeaconValidatorGenerator<L, D>>(beacon_validator_generator_id);
+            let beacon_validator_generator_id = BeaconValidatorGenerator::<L, D>::id();
+            r.register_simple::<BeaconValidatorGenerator<L, D>>(beacon_validator_generator_id);
 
-        let beacon_validators_generator_id = BeaconValidatorsGenerator::<L, D>::id();
-        r.register_simple::<BeaconValidatorsGenerator<L, D>>(beacon_validators_generator_id);
+            let beacon_validators_generator_id = BeaconValidatorsGenerator::<L, D>::id();
+            r.register_simple::<BeaconValidatorsGenerator<L, D>>(beacon_validators_generator_id);
 
-        let beacon_withdrawal_generator_id = BeaconWithdrawalGenerator::<L, D>::id();
-        r.register_simple::<BeaconWithdrawalGenerator<L, D>>(beacon_withdrawal_generator_id);
+            let beacon_withdrawal_generator_id = BeaconWithdrawalGenerator::<L, D>::id();
+            r.register_simple::<BeaconWithdrawalGenerator<L, D>>(beacon_withdrawal_generator_id);
 
-        let beacon_withdrawals_generator_id = BeaconWithdrawalsGenerator::<L, D>::id();
-        r.register_simple::<BeaconWithdrawalsGenerator<L, D>>(beacon_withdrawals_generator_id);
+            let beacon_withdrawals_generator_id = BeaconWithdrawalsGenerator::<L, D>::id();
+            r.register_simple::<BeaconWithdrawalsGenerator<L, D>>(beacon_withdrawals_generator_id);
+        }
 
         let big_uint_div_rem_generator_id = BigUintDivRemGenerator::<L::Field, D>::id();
         r.register_simple::<BigUintDivRemGenerator<L::Field, D>>(big_uint_div_rem_generator_id);
@@ -368,20 +389,27 @@ where
         let comparison_generator_id = ComparisonGenerator::<L::Field, D>::id();
         r.register_simple::<ComparisonGenerator<L::Field, D>>(comparison_generator_id);
 
-        r.register_hint::<BeaconBalanceWitnessHint>();
+        #[cfg(feature = "beacon")]
+        {
+            r.register_hint::<BeaconBalanceWitnessHint>();
 
-        r.register_async_hint::<BeaconAllWithdrawalsHint>();
-        r.register_async_hint::<BeaconHeaderHint>();
-        r.register_async_hint::<BeaconHistoricalBlockHint>();
+            r.register_async_hint::<BeaconAllWithdrawalsHint>();
+            r.register_async_hint::<BeaconHeaderHint>();
+            r.register_async_hint::<BeaconHistoricalBlockHint>();
+        }
+        #[cfg(feature = "mpt")]
         r.register_async_hint::<EthStorageProofHint<L, D>>();
-        r.register_async_hint::<BeaconValidatorsHint>();
+        #[cfg(feature = "beacon")]
+        {
+            r.register_async_hint::<BeaconValidatorsHint>();
 
-        register_powers_of_two!(r, BeaconBalanceBatchWitnessHint);
-        register_powers_of_two!(r, BeaconValidatorBatchHint);
-        register_powers_of_two!(r, CompressedBeaconValidatorBatchHint);
+            register_powers_of_two!(r, BeaconBalanceBatchWitnessHint);
+            register_powers_of_two!(r, BeaconValidatorBatchHint);
+            register_powers_of_two!(r, CompressedBeaconValidatorBatchHint);
 
-        register_powers_of_two_async!(r, BeaconPartialBalancesHint);
-        register_powers_of_two_async!(r, BeaconPartialValidatorsHint);
+            register_powers_of_two_async!(r, BeaconPartialBalancesHint);
+            register_powers_of_two_async!(r, BeaconPartialValidatorsHint);
+        }
 
         let id = U32RangeCheckGenerator::<L::Field, D>::id();
         r.register_simple::<U32RangeCheckGenerator<L::Field, D>>(id);
@@ -394,9 +422,26 @@ where
 
         r.register_hint::<SubArrayExtractorHint>();
 
-        r.register_hint::<BeaconBlockRootsHint>();
+        r.register_hint::<U32ByteDecompositionHint>();
 
-        r.register_hint::<BeaconGraffitiHint>();
+        #[cfg(feature = "beacon")]
+        {
+            r.register_hint::<BeaconBlockRootHint>();
+
+            r.register_hint::<BeaconBlockRootsHint>();
+
+            r.register_hint::<BeaconGraffitiHint>();
+            r.register_hint::<BeaconExecutionPayloadHeaderHint>();
+
+            r.register_hint::<BeaconHistoricalSummaryHint>();
+
+            r.register_hint::<BeaconRandaoMixHint>();
+
+            r.register_hint::<BeaconValidatorsAndBalancesHint>();
+
+            r.register_hint::<BeaconGenesisValidatorsRootHint>();
+            r.register_hint::<BeaconSyncCommitteeHint>();
+        }
 
         r.register_hint::<HashDigestHint<SHA256, 64, false, 8>>();
         r.register_async_hint::<Async<HashDigestHint<SHA256, 64, false, 8>>>();
@@ -416,16 +461,20 @@ where
         r.register_hint::<HashProofHint<BLAKE2B, 96, true, 4>>();
         r.register_async_hint::<Async<HashProofHint<BLAKE2B, 96, true, 4>>>();
 
-        r.register_hint::<EcOpProofHint>();
-        r.register_async_hint::<Async<EcOpProofHint>>();
+        #[cfg(feature = "ecc")]
+        {
+            r.register_hint::<EcOpProofHint>();
+            r.register_async_hint::<Async<EcOpProofHint>>();
 
-        r.register_hint::<EcOpResultHint>();
-        r.register_async_hint::<Async<EcOpResultHint>>();
+            r.register_hint::<EcOpResultHint>();
+            r.register_async_hint::<Async<EcOpResultHint>>();
+        }
 
         let dummy_proof_generator_id =
             DummyProofGenerator::<L::Field, L::Config, D>::default().id();
         r.register_simple::<DummyProofGenerator<L::Field, L::Config, D>>(dummy_proof_generator_id);
 
+        #[cfg(feature = "beacon")]
         register_powers_of_two!(r, BeaconHeadersFromOffsetRangeHint);
 
         register_watch_generator!(
@@ -437,13 +486,26 @@ where
             U32Variable,
             U64Variable,
             U256Variable,
-            Bytes32Variable,
+            Bytes32Variable
+        );
+
+        #[cfg(feature = "beacon")]
+        register_watch_generator!(
+            r,
+            L,
+            D,
             BeaconValidatorsVariable,
             BeaconBalancesVariable,
             BeaconWithdrawalsVariable,
             BeaconWithdrawalVariable,
             BeaconValidatorVariable,
-            BeaconHeaderVariable,
+            BeaconHeaderVariable
+        );
+
+        register_watch_generator!(
+            r,
+            L,
+            D,
             PoseidonHashOutVariable,
             ArrayVariable<Bytes32Variable, 8192>
         );
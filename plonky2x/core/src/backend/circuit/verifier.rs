@@ -0,0 +1,87 @@
+use anyhow::Result;
+use plonky2::plonk::circuit_data::VerifierCircuitData;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+use super::{CircuitBuild, DecodedOutputs, PlonkParameters};
+use crate::frontend::builder::{CircuitIO, IOLabel};
+
+/// The verifier side of a circuit: the data needed to verify its proofs along with the layout of
+/// their public inputs.
+#[derive(Debug)]
+pub struct VerifierCircuit<L: PlonkParameters<D>, const D: usize> {
+    pub data: VerifierCircuitData<L::Field, L::Config, D>,
+    /// The number of input field elements, which precede the outputs in the public inputs.
+    pub num_input_elements: usize,
+    /// The names of the outputs registered with `register_public_output`.
+    pub output_labels: Vec<IOLabel>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> VerifierCircuit<L, D> {
+    /// Verifies a proof of the circuit.
+    pub fn verify(&self, proof: &ProofWithPublicInputs<L::Field, L::Config, D>) -> Result<()> {
+        self.data.verify(proof.clone())
+    }
+
+    /// Decodes the outputs registered with `register_public_output` from the flat public inputs of
+    /// a proof, so they can be read by name as typed values.
+    pub fn decode_public_inputs(&self, public_inputs: &[L::Field]) -> DecodedOutputs<L, D> {
+        assert_eq!(
+            public_inputs.len(),
+            self.data.common.num_public_inputs,
+            "expected {} public inputs",
+            self.data.common.num_public_inputs
+        );
+        DecodedOutputs::new(
+            &public_inputs[self.num_input_elements..],
+            &self.output_labels,
+        )
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
+    /// Extracts the verifier side of the circuit.
+    pub fn verifier(&self) -> VerifierCircuit<L, D> {
+        let num_input_elements = match &self.io {
+            CircuitIO::Elements(io) => io.input.len(),
+            CircuitIO::CyclicProof(io) => io.input.len(),
+            _ => panic!("named outputs require field io"),
+        };
+        VerifierCircuit {
+            data: self.data.verifier_data(),
+            num_input_elements,
+            output_labels: self.output_labels.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{H256, U256};
+
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_decode_public_inputs() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let amount = builder.read::<U256Variable>();
+        let root = builder.read::<Bytes32Variable>();
+        let doubled = builder.add(amount, amount);
+        builder.register_public_output("doubled", &doubled);
+        builder.register_public_output("root", &root);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<U256Variable>(U256::from(21));
+        input.write::<Bytes32Variable>(H256::repeat_byte(7));
+        let (proof, _) = circuit.prove(&input);
+
+        let verifier = circuit.verifier();
+        verifier.verify(&proof).unwrap();
+        let outputs = verifier.decode_public_inputs(&proof.public_inputs);
+        assert_eq!(outputs.get::<U256Variable>("doubled"), U256::from(42));
+        assert_eq!(outputs.get::<Bytes32Variable>("root"), H256::repeat_byte(7));
+    }
+}
@@ -6,22 +6,54 @@
 //!
 //! [1] : https://github.com/mir-protocol/plonky2/blob/main/plonky2/src/iop/generator.rs#L19
 
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use std::collections::HashSet;
 
 use anyhow::{anyhow, Error, Result};
 use log::trace;
+use plonky2::field::types::Field;
 use plonky2::iop::generator::{GeneratedValues, WitnessGeneratorRef};
 use plonky2::iop::witness::{PartialWitness, PartitionWitness, WitnessWrite};
 use plonky2::plonk::circuit_data::{CommonCircuitData, ProverOnlyCircuitData};
 use starkyx::maybe_rayon::rayon;
+use tokio::runtime::{Handle, Runtime};
 use tokio::sync::mpsc::unbounded_channel;
 use tokio::sync::oneshot;
 
+use super::prepared::PreparedInputs;
 use super::PlonkParameters;
 use crate::frontend::hint::asynchronous::generator::{AsyncHintDataRef, AsyncHintRef, HintPoll};
 use crate::frontend::hint::asynchronous::handler::HintHandler;
 
+/// Buffers used by witness generation that can be reused between witnesses of the same circuit.
+#[derive(Debug)]
+pub struct WitnessScratch<F: Field> {
+    pending_generator_indices: HashSet<usize>,
+    next_pending_generator_indices: HashSet<usize>,
+    generator_is_expired: Vec<bool>,
+    buffer: GeneratedValues<F>,
+}
+
+impl<F: Field> Default for WitnessScratch<F> {
+    fn default() -> Self {
+        Self {
+            pending_generator_indices: HashSet::new(),
+            next_pending_generator_indices: HashSet::new(),
+            generator_is_expired: Vec::new(),
+            buffer: GeneratedValues::empty(),
+        }
+    }
+}
+
+/// How witness generation treats the network-backed generators of a circuit.
+#[derive(Debug)]
+pub enum NetworkValues<'b> {
+    /// Run the generators and record the values they set.
+    Record(&'b mut PreparedInputs),
+    /// Set the recorded values instead of running the generators.
+    Replay(&'b PreparedInputs),
+}
+
 /// Given a `PartialWitness` that has only inputs set, populates the rest of the witness using the
 /// given set of generators.
 pub fn generate_witness<'a, L: PlonkParameters<D>, const D: usize>(
@@ -29,6 +61,70 @@ pub fn generate_witness<'a, L: PlonkParameters<D>, const D: usize>(
     prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
     common_data: &'a CommonCircuitData<L::Field, D>,
     async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+) -> Result<PartitionWitness<'a, L::Field>> {
+    generate_witness_with_scratch(
+        inputs,
+        prover_data,
+        common_data,
+        async_generator_refs,
+        None,
+        &mut WitnessScratch::default(),
+    )
+}
+
+/// Same as [`generate_witness`], but reuses the buffers in `scratch` and, if a runtime is given,
+/// runs the asynchronous hints on it instead of spawning a new runtime.
+pub fn generate_witness_with_scratch<'a, L: PlonkParameters<D>, const D: usize>(
+    inputs: PartialWitness<L::Field>,
+    prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
+    common_data: &'a CommonCircuitData<L::Field, D>,
+    async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    runtime: Option<&Handle>,
+    scratch: &mut WitnessScratch<L::Field>,
+) -> Result<PartitionWitness<'a, L::Field>> {
+    generate_witness_inner(
+        inputs,
+        prover_data,
+        common_data,
+        async_generator_refs,
+        runtime,
+        scratch,
+        None,
+    )
+}
+
+/// Same as [`generate_witness`], but records or replays the values of the network-backed
+/// generators as described by [`PreparedInputs`].
+///
+/// The network-backed generators are the ones whose id is in `network_generator_ids`, which is
+/// filled by the builder for the hints and generators that opt in.
+pub fn generate_witness_with_prepared<'a, L: PlonkParameters<D>, const D: usize>(
+    inputs: PartialWitness<L::Field>,
+    prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
+    common_data: &'a CommonCircuitData<L::Field, D>,
+    async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    network_generator_ids: &BTreeSet<String>,
+    network_values: NetworkValues,
+) -> Result<PartitionWitness<'a, L::Field>> {
+    generate_witness_inner(
+        inputs,
+        prover_data,
+        common_data,
+        async_generator_refs,
+        None,
+        &mut WitnessScratch::default(),
+        Some((network_generator_ids, network_values)),
+    )
+}
+
+fn generate_witness_inner<'a, L: PlonkParameters<D>, const D: usize>(
+    inputs: PartialWitness<L::Field>,
+    prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
+    common_data: &'a CommonCircuitData<L::Field, D>,
+    async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    runtime: Option<&Handle>,
+    scratch: &mut WitnessScratch<L::Field>,
+    network: Option<(&BTreeSet<String>, NetworkValues)>,
 ) -> Result<PartitionWitness<'a, L::Field>> {
     // If async hints are present, set up the a handler and initialize the generators with the
     // handler's communication channel.
@@ -39,15 +135,24 @@ pub fn generate_witness<'a, L: PlonkParameters<D>, const D: usize>(
             let (tx, rx) = unbounded_channel();
             // Initialize the hint handler.
             let mut hint_handler = HintHandler::<L, D>::new(rx);
-
-            // Spawn a runtime and run the hint handler.
-            let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-            rayon::spawn(move || {
-                let result = rt.block_on(hint_handler.run());
+            let run_handler = async move {
+                let result = hint_handler.run().await;
                 if let Err(e) = result {
                     tx_handler_error.send(e).unwrap();
                 }
-            });
+            };
+
+            // Run the hint handler on the given runtime, or spawn a runtime for it.
+            match runtime {
+                Some(handle) => {
+                    let handle = handle.clone();
+                    rayon::spawn(move || handle.block_on(run_handler));
+                }
+                None => {
+                    let rt = Runtime::new().expect("Failed to create runtime");
+                    rayon::spawn(move || rt.block_on(run_handler));
+                }
+            }
 
             BTreeMap::from_iter(
                 async_generator_refs
@@ -63,6 +168,8 @@ pub fn generate_witness<'a, L: PlonkParameters<D>, const D: usize>(
         common_data,
         async_generators,
         rx_handler_error,
+        scratch,
+        network,
     )
 }
 
@@ -105,6 +212,8 @@ pub async fn generate_witness_async<'a, L: PlonkParameters<D>, const D: usize>(
             common_data,
             async_generators,
             rx_handler_error,
+            &mut WitnessScratch::default(),
+            None,
         )
     })
 }
@@ -116,20 +225,46 @@ fn fill_witness_values<'a, L: PlonkParameters<D>, const D: usize>(
     common_data: &'a CommonCircuitData<L::Field, D>,
     mut async_generators: BTreeMap<usize, AsyncHintRef<L, D>>,
     mut rx_handler_error: oneshot::Receiver<Error>,
+    scratch: &mut WitnessScratch<L::Field>,
+    network: Option<(&BTreeSet<String>, NetworkValues)>,
 ) -> Result<PartitionWitness<'a, L::Field>> {
     let config = &common_data.config;
     let generators = &prover_data.generators;
     let generator_indices_by_watches = &prover_data.generator_indices_by_watches;
 
+    let WitnessScratch {
+        pending_generator_indices,
+        next_pending_generator_indices,
+        generator_is_expired,
+        buffer,
+    } = scratch;
+
     // Build a list of "pending" generators which are queued to be run. Initially, all generators
     // are queued.
-    let mut pending_generator_indices: HashSet<_> = (0..generators.len()).collect();
+    pending_generator_indices.clear();
+    pending_generator_indices.extend(0..generators.len());
+    next_pending_generator_indices.clear();
 
     // We also track a list of "expired" generators which have already returned false.
-    let mut generator_is_expired = vec![false; generators.len()];
+    generator_is_expired.clear();
+    generator_is_expired.resize(generators.len(), false);
     let mut remaining_generators = generators.len();
 
-    let mut buffer = GeneratedValues::empty();
+    // The generators whose values are recorded or replayed.
+    let (network_generators, mut network_values) = match network {
+        Some((ids, values)) => (
+            generators
+                .iter()
+                .enumerate()
+                .filter(|(_, g)| ids.contains(&g.0.id()))
+                .map(|(i, _)| i)
+                .collect::<HashSet<_>>(),
+            Some(values),
+        ),
+        None => (HashSet::new(), None),
+    };
+
+    buffer.target_values.clear();
     let mut witness = PartitionWitness::new(
         config.num_wires,
         common_data.degree(),
@@ -142,35 +277,51 @@ fn fill_witness_values<'a, L: PlonkParameters<D>, const D: usize>(
 
     // Keep running generators until we fail to make progress.
     while !pending_generator_indices.is_empty() {
-        let mut next_pending_generator_indices = HashSet::new();
-        for &generator_idx in &pending_generator_indices {
+        for &generator_idx in pending_generator_indices.iter() {
             if generator_is_expired[generator_idx] {
                 continue;
             }
 
-            // Run the generator, depending on whether it is an asyncronous or not.
-            if let Some(async_gen) = async_generators.get_mut(&generator_idx) {
-                // Check if the hint handler has returned an error, and if so, return it.
-                if let Ok(e) = rx_handler_error.try_recv() {
-                    return Err(e);
-                }
-                let pol = async_gen.0.run(&witness, &mut buffer);
-                match pol {
-                    HintPoll::InputPending => {}
-                    HintPoll::Pending => {
-                        next_pending_generator_indices.insert(generator_idx);
+            let is_network = network_generators.contains(&generator_idx);
+            if let (Some(NetworkValues::Replay(prepared)), true) = (&network_values, is_network) {
+                // Set the prepared values instead of running the generator.
+                let id = generators[generator_idx].0.id();
+                buffer
+                    .target_values
+                    .extend(prepared.replay(generator_idx, &id)?);
+                generator_is_expired[generator_idx] = true;
+                remaining_generators -= 1;
+            } else {
+                // Run the generator, depending on whether it is an asyncronous or not.
+                if let Some(async_gen) = async_generators.get_mut(&generator_idx) {
+                    // Check if the hint handler has returned an error, and if so, return it.
+                    if let Ok(e) = rx_handler_error.try_recv() {
+                        return Err(e);
                     }
-                    HintPoll::Ready => {
+                    let pol = async_gen.0.run(&witness, buffer);
+                    match pol {
+                        HintPoll::InputPending => {}
+                        HintPoll::Pending => {
+                            next_pending_generator_indices.insert(generator_idx);
+                        }
+                        HintPoll::Ready => {
+                            generator_is_expired[generator_idx] = true;
+                            remaining_generators -= 1;
+                        }
+                    }
+                } else {
+                    let finished = generators[generator_idx].0.run(&witness, buffer);
+                    if finished {
                         generator_is_expired[generator_idx] = true;
                         remaining_generators -= 1;
                     }
                 }
-            } else {
-                let finished = generators[generator_idx].0.run(&witness, &mut buffer);
-                if finished {
-                    generator_is_expired[generator_idx] = true;
-                    remaining_generators -= 1;
-                }
+            }
+
+            if let (Some(NetworkValues::Record(prepared)), true) = (&mut network_values, is_network)
+            {
+                let id = generators[generator_idx].0.id();
+                prepared.record(generator_idx, &id, &buffer.target_values);
             }
 
             // Merge any generated values into our witness, and get a list of newly-populated
@@ -195,7 +346,8 @@ fn fill_witness_values<'a, L: PlonkParameters<D>, const D: usize>(
             }
         }
 
-        pending_generator_indices = next_pending_generator_indices;
+        core::mem::swap(pending_generator_indices, next_pending_generator_indices);
+        next_pending_generator_indices.clear();
     }
 
     if remaining_generators > 0 {
@@ -215,7 +367,7 @@ fn fill_witness_values<'a, L: PlonkParameters<D>, const D: usize>(
 #[inline]
 fn get_generator_error<L: PlonkParameters<D>, const D: usize>(
     generators: &[WitnessGeneratorRef<L::Field, D>],
-    generator_is_expired: Vec<bool>,
+    generator_is_expired: &[bool],
 ) -> Error {
     let mut generators_not_run = Vec::new();
     for i in 0..generator_is_expired.len() {
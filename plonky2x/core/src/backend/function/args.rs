@@ -22,12 +22,35 @@ pub struct ProveArgs {
 
     #[arg(long, default_value = "/verifier-build")]
     pub wrapper_path: String,
+
+    /// Use the network values saved by `prepare-inputs` instead of fetching them.
+    #[arg(long)]
+    pub prepared_inputs: Option<String>,
+
+    /// Include the witness generation and proving times and the proof size in output.json.
+    #[arg(long)]
+    pub metrics: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(
+    about = "Fetch the network values needed to prove a circuit, so that the proof can be generated offline."
+)]
+pub struct PrepareInputsArgs {
+    pub input_json: String,
+
+    #[arg(long, default_value = "./build")]
+    pub build_dir: String,
+
+    #[arg(long, default_value = "./prepared_inputs.json")]
+    pub output: String,
 }
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     Build(BuildArgs),
     Prove(ProveArgs),
+    PrepareInputs(PrepareInputsArgs),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -2,6 +2,7 @@ pub mod args;
 pub mod request;
 pub mod result;
 
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufReader, Write};
 use std::{fs, path};
@@ -13,9 +14,10 @@ pub use request::*;
 pub use result::*;
 use serde::Serialize;
 
-use self::args::{BuildArgs, ProveArgs};
+use self::args::{BuildArgs, PrepareInputsArgs, ProveArgs};
 use crate::backend::circuit::*;
 use crate::backend::function::args::{Args, Commands};
+use crate::backend::prover::ProverContext;
 use crate::backend::wrapper::wrap::WrappedCircuit;
 use crate::frontend::builder::CircuitIO;
 use crate::prelude::CircuitBuilder;
@@ -45,6 +47,13 @@ pub trait Plonky2xFunction {
             AlgebraicHasher<InnerParameters::Field>,
         OuterParameters::Config: Serialize;
 
+    /// Captures the network values needed to prove the request and saves them to disk.
+    fn prepare_inputs<L: PlonkParameters<D>, const D: usize>(
+        args: PrepareInputsArgs,
+        request: ProofRequest<L, D>,
+    ) where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>;
+
     /// The entry point for the function when using the CLI.
     fn entrypoint();
 
@@ -81,6 +90,12 @@ impl<C: Circuit> Plonky2xFunction for C {
         circuit.save(&path, &gate_registry, &generator_registry);
         info!("Successfully saved circuit to disk at {}.", path);
 
+        // Save the io schema next to the circuit.
+        let schema_path = format!("{}/main.schema.json", args.build_dir);
+        let schema = serde_json::to_string_pretty(&circuit.io_schema()).unwrap();
+        fs::write(&schema_path, schema).unwrap();
+        info!("Successfully saved io schema to disk at {}.", schema_path);
+
         // Serialize the verifier contract to disk.
         if let CircuitIO::Bytes(_) = circuit.io {
             info!("Building verifier contract...");
@@ -163,35 +178,19 @@ impl<C: Circuit> Plonky2xFunction for C {
             None
         };
 
-        let mut generator_registry = HintRegistry::new();
-        let mut gate_registry = GateRegistry::new();
-        C::register_generators::<InnerParameters, D>(&mut generator_registry);
-        C::register_gates::<InnerParameters, D>(&mut gate_registry);
-
-        let mut path = match request {
-            ProofRequest::Bytes(_) => {
-                format!("{}/main.circuit", args.build_dir)
-            }
-            ProofRequest::Elements(ref request) => {
-                format!("{}/{}.circuit", args.build_dir, request.data.circuit_id)
+        let (circuit, input) =
+            load_circuit_and_input::<C, InnerParameters, D>(&args.build_dir, &request);
+        let (proof, output, metrics) = match args.prepared_inputs {
+            Some(ref path) => {
+                info!("Proving with the prepared inputs at {}...", path);
+                let prepared = PreparedInputs::load(path).unwrap();
+                ProverContext::new()
+                    .prove_with_prepared_and_metrics(&circuit, &input, &prepared)
+                    .unwrap_or_else(|e| panic!("failed to prove with prepared inputs: {:#}", e))
             }
-            ProofRequest::RecursiveProofs(ref request) => {
-                format!("{}/{}.circuit", args.build_dir, request.data.circuit_id)
-            }
-            _ => todo!(),
+            None => circuit.prove_with_metrics(&input),
         };
-        if fs::metadata(&path).is_err() {
-            path = format!("{}/main.circuit", args.build_dir);
-        }
-
-        info!("Loading circuit from {}...", path);
-        let circuit =
-            CircuitBuild::<InnerParameters, D>::load(&path, &gate_registry, &generator_registry)
-                .unwrap();
-        info!("Successfully loaded circuit.");
-
-        let input = request.input();
-        let (proof, output) = circuit.prove(&input);
+        let output: PublicOutput<InnerParameters, D> = output.into();
         info!(
             "Successfully generated proof, wrapping proof with {}",
             args.wrapper_path
@@ -248,15 +247,26 @@ impl<C: Circuit> Plonky2xFunction for C {
                 serde_json::from_reader::<BufReader<File>, BytesResultData>(rdr).unwrap();
 
             // Write full result with output bytes to output.json.
-            let result: ProofResult<OuterParameters, D> =
+            let mut result: ProofResult<OuterParameters, D> =
                 ProofResult::from_bytes(result_data.proof, output_bytes);
+            if args.metrics {
+                result = result.with_metrics(metrics);
+            }
             let json = serde_json::to_string_pretty(&result).unwrap();
             info!("output.json:\n{}", json);
             let mut file = File::create("output.json").unwrap();
             file.write_all(json.as_bytes()).unwrap();
             info!("Successfully saved full result to disk at output.json.");
         } else {
-            let result = ProofResult::from_proof_output(proof, output);
+            let labeled_output = match output {
+                PublicOutput::Elements(_) => circuit.labeled_output(&output),
+                _ => BTreeMap::new(),
+            };
+            let mut result =
+                ProofResult::from_proof_output(proof, output).with_labeled_output(labeled_output);
+            if args.metrics {
+                result = result.with_metrics(metrics);
+            }
             let json = serde_json::to_string_pretty(&result).unwrap();
             let mut file = File::create("output.json").unwrap();
             file.write_all(json.as_bytes()).unwrap();
@@ -264,6 +274,24 @@ impl<C: Circuit> Plonky2xFunction for C {
         }
     }
 
+    fn prepare_inputs<L: PlonkParameters<D>, const D: usize>(
+        args: PrepareInputsArgs,
+        request: ProofRequest<L, D>,
+    ) where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    {
+        let (circuit, input) = load_circuit_and_input::<C, L, D>(&args.build_dir, &request);
+        let prepared = ProverContext::new()
+            .prepare_inputs(&circuit, &input)
+            .unwrap_or_else(|e| panic!("failed to prepare inputs: {:#}", e));
+        prepared.save(&args.output).unwrap();
+        info!(
+            "Successfully saved the values of {} generators to disk at {}.",
+            prepared.len(),
+            args.output
+        );
+    }
+
     /// The entry point for the function when using the CLI.
     fn entrypoint() {
         type L = DefaultParameters;
@@ -281,6 +309,10 @@ impl<C: Circuit> Plonky2xFunction for C {
                 let request = ProofRequest::<L, D>::load(&args.input_json);
                 Self::prove::<L, Groth16WrapperParameters, D>(args, request);
             }
+            Commands::PrepareInputs(args) => {
+                let request = ProofRequest::<L, D>::load(&args.input_json);
+                Self::prepare_inputs::<L, D>(args, request);
+            }
         }
     }
 
@@ -321,3 +353,53 @@ contract FunctionVerifier is IFunctionVerifier, PlonkVerifier {
         generated_contract + &verifier_contract
     }
 }
+
+/// Loads the circuit that serves the request from the build directory, along with the request's
+/// input.
+fn load_circuit_and_input<C: Circuit, L: PlonkParameters<D>, const D: usize>(
+    build_dir: &str,
+    request: &ProofRequest<L, D>,
+) -> (CircuitBuild<L, D>, PublicInput<L, D>)
+where
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+{
+    let mut generator_registry = HintRegistry::new();
+    let mut gate_registry = GateRegistry::new();
+    C::register_generators::<L, D>(&mut generator_registry);
+    C::register_gates::<L, D>(&mut gate_registry);
+
+    let mut path = match request {
+        ProofRequest::Bytes(_) => {
+            format!("{}/main.circuit", build_dir)
+        }
+        ProofRequest::Elements(request) => {
+            format!("{}/{}.circuit", build_dir, request.data.circuit_id)
+        }
+        ProofRequest::RecursiveProofs(request) => {
+            format!("{}/{}.circuit", build_dir, request.data.circuit_id)
+        }
+        _ => todo!(),
+    };
+    if fs::metadata(&path).is_err() {
+        path = format!("{}/main.circuit", build_dir);
+    }
+
+    info!("Loading circuit from {}...", path);
+    let circuit = CircuitBuild::<L, D>::load(&path, &gate_registry, &generator_registry).unwrap();
+    info!("Successfully loaded circuit.");
+
+    let input = match request {
+        ProofRequest::Elements(ProofRequestBase {
+            data:
+                ElementsRequestData {
+                    named_input: Some(named_input),
+                    ..
+                },
+            ..
+        }) => circuit
+            .input_from_json(named_input)
+            .unwrap_or_else(|e| panic!("invalid input: {:#}", e)),
+        _ => request.input(),
+    };
+    (circuit, input)
+}
@@ -24,9 +24,14 @@ pub struct BytesRequestData {
 #[serde(rename_all = "camelCase")]
 pub struct ElementsRequestData<L: PlonkParameters<D>, const D: usize> {
     pub circuit_id: String,
+    #[serde(default)]
     #[serde(serialize_with = "serialize_elements")]
     #[serde(deserialize_with = "deserialize_elements")]
     pub input: Vec<L::Field>,
+    /// The input as an object keyed by the names in the io schema of the circuit. When set, it
+    /// takes precedence over `input`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub named_input: Option<serde_json::Value>,
 }
 
 /// Fields for a function request that uses recursive proofs io.
@@ -96,6 +101,7 @@ impl<L: PlonkParameters<D>, const D: usize> ProofRequest<L, D> {
                 data: ElementsRequestData {
                     circuit_id: circuit_id.to_string(),
                     input: input.clone(),
+                    named_input: None,
                 },
             }),
             PublicInput::RecursiveProofs(input, _) => {
@@ -1,10 +1,12 @@
 use core::fmt::Debug;
 use core::panic;
+use std::collections::BTreeMap;
 
 use plonky2::plonk::proof::ProofWithPublicInputs;
 use serde::{Deserialize, Serialize};
 
 use crate::backend::circuit::{PlonkParameters, PublicOutput};
+use crate::backend::prover::ProveMetrics;
 use crate::utils::serde::{
     deserialize_elements, deserialize_hex, deserialize_proof_with_pis, serialize_elements,
     serialize_hex, serialize_proof_with_pis,
@@ -25,6 +27,9 @@ pub struct BytesResultData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ElementsResultData<L: PlonkParameters<D>, const D: usize> {
     pub output: Vec<L::Field>,
+    /// The output split by the names registered with `register_public_output`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labeled_output: BTreeMap<String, Vec<L::Field>>,
     #[serde(serialize_with = "serialize_proof_with_pis")]
     #[serde(deserialize_with = "deserialize_proof_with_pis")]
     pub proof: ProofWithPublicInputs<L::Field, L::Config, D>,
@@ -45,6 +50,9 @@ pub struct RecursiveProofsResultData<L: PlonkParameters<D>, const D: usize> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProofResultBase<D> {
     pub data: D,
+    /// The timing and size metrics of the proof, if they were requested with `--metrics`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<ProveMetrics>,
 }
 
 /// The standard result format for "functions".
@@ -75,15 +83,28 @@ impl<L: PlonkParameters<D>, const D: usize> ProofResult<L, D> {
                     output,
                     proof: bincode::serialize(&proof).unwrap(),
                 };
-                ProofResult::Bytes(ProofResultBase { data })
+                ProofResult::Bytes(ProofResultBase {
+                    data,
+                    metrics: None,
+                })
             }
             PublicOutput::Elements(output) => {
-                let data = ElementsResultData { output, proof };
-                ProofResult::Elements(ProofResultBase { data })
+                let data = ElementsResultData {
+                    output,
+                    labeled_output: BTreeMap::new(),
+                    proof,
+                };
+                ProofResult::Elements(ProofResultBase {
+                    data,
+                    metrics: None,
+                })
             }
             PublicOutput::Proofs(output) => {
                 let data = RecursiveProofsResultData { output, proof };
-                ProofResult::RecursiveProofs(ProofResultBase { data })
+                ProofResult::RecursiveProofs(ProofResultBase {
+                    data,
+                    metrics: None,
+                })
             }
             PublicOutput::None() => todo!(),
         }
@@ -91,7 +112,58 @@ impl<L: PlonkParameters<D>, const D: usize> ProofResult<L, D> {
 
     pub fn from_bytes(proof: Vec<u8>, output: Vec<u8>) -> Self {
         let data = BytesResultData { output, proof };
-        ProofResult::Bytes(ProofResultBase { data })
+        ProofResult::Bytes(ProofResultBase {
+            data,
+            metrics: None,
+        })
+    }
+
+    /// Attaches the timing and size metrics of the proof to the result.
+    ///
+    /// The size in the metrics is replaced by the size of the proof that the result serializes,
+    /// which for a wrapped proof is the size of the wrapped proof.
+    pub fn with_metrics(mut self, metrics: ProveMetrics) -> Self {
+        match &mut self {
+            ProofResult::Bytes(result) => {
+                let proof_bytes = result.data.proof.len();
+                result.metrics = Some(ProveMetrics {
+                    proof_bytes,
+                    ..metrics
+                });
+            }
+            ProofResult::Elements(result) => {
+                let proof_bytes = bincode::serialize(&result.data.proof).unwrap().len();
+                result.metrics = Some(ProveMetrics {
+                    proof_bytes,
+                    ..metrics
+                });
+            }
+            ProofResult::RecursiveProofs(result) => {
+                let proof_bytes = bincode::serialize(&result.data.proof).unwrap().len();
+                result.metrics = Some(ProveMetrics {
+                    proof_bytes,
+                    ..metrics
+                });
+            }
+        }
+        self
+    }
+
+    /// Attaches the output split by its registered names, which only field io results carry.
+    pub fn with_labeled_output(mut self, labeled_output: BTreeMap<String, Vec<L::Field>>) -> Self {
+        if let ProofResult::Elements(result) = &mut self {
+            result.data.labeled_output = labeled_output;
+        }
+        self
+    }
+
+    /// The timing and size metrics attached to the result, if any.
+    pub fn metrics(&self) -> Option<&ProveMetrics> {
+        match self {
+            ProofResult::Bytes(result) => result.metrics.as_ref(),
+            ProofResult::Elements(result) => result.metrics.as_ref(),
+            ProofResult::RecursiveProofs(result) => result.metrics.as_ref(),
+        }
     }
 
     pub fn as_proof_and_output(
@@ -115,3 +187,93 @@ impl<L: PlonkParameters<D>, const D: usize> ProofResult<L, D> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_proof_result_metrics_roundtrip() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<U64Variable>();
+        let b = builder.read::<U64Variable>();
+        let c = builder.add(a, b);
+        builder.write(c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<U64Variable>(1);
+        input.write::<U64Variable>(2);
+        let (proof, output, metrics) = circuit.prove_with_metrics(&input);
+        assert_eq!(metrics.proof_bytes, proof.to_bytes().len());
+
+        let result = ProofResult::<L, D>::from_proof_output(proof.clone(), output.into())
+            .with_metrics(metrics);
+        let attached = *result.metrics().unwrap();
+        assert_eq!(attached.witness_ms, metrics.witness_ms);
+        assert_eq!(attached.prove_ms, metrics.prove_ms);
+        let json = serde_json::to_string(&result).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let serialized: ProveMetrics = serde_json::from_value(value["metrics"].clone()).unwrap();
+        assert_eq!(serialized, attached);
+
+        // The size is that of the proof as it is serialized in the result.
+        let serialized_proof = hex::decode(
+            value["data"]["proof"]
+                .as_str()
+                .unwrap()
+                .trim_start_matches("0x"),
+        )
+        .unwrap();
+        assert_eq!(attached.proof_bytes, serialized_proof.len());
+
+        let decoded: ProofResult<L, D> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.metrics(), Some(&attached));
+
+        let bytes_result =
+            ProofResult::<L, D>::from_bytes(vec![0u8; 7], vec![1u8; 3]).with_metrics(metrics);
+        assert_eq!(bytes_result.metrics().unwrap().proof_bytes, 7);
+
+        let without_metrics =
+            ProofResult::<L, D>::from_proof_output(proof, circuit.prove(&input).1.into());
+        let json = serde_json::to_string(&without_metrics).unwrap();
+        assert!(!json.contains("metrics"));
+    }
+
+    #[test]
+    fn test_proof_result_labeled_output() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<U64Variable>();
+        let b = builder.read::<U64Variable>();
+        let c = builder.add(a, b);
+        builder.register_public_output("sum", &c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<U64Variable>(1);
+        input.write::<U64Variable>(2);
+        let (proof, output) = circuit.prove(&input);
+        let labeled_output = circuit.labeled_output(&output);
+
+        let result = ProofResult::<L, D>::from_proof_output(proof, output)
+            .with_labeled_output(labeled_output.clone());
+        let json = serde_json::to_string(&result).unwrap();
+        let decoded: ProofResult<L, D> = serde_json::from_str(&json).unwrap();
+        match decoded {
+            ProofResult::Elements(result) => {
+                assert_eq!(result.data.labeled_output, labeled_output);
+                assert_eq!(
+                    U64Variable::from_elements::<GoldilocksField>(
+                        &result.data.labeled_output["sum"]
+                    ),
+                    3
+                );
+            }
+            _ => panic!("expected a field io result"),
+        }
+    }
+}
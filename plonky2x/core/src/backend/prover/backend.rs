@@ -0,0 +1,86 @@
+use anyhow::Result;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+use super::ProverContext;
+use crate::backend::circuit::{CircuitBuild, CircuitOutput, PlonkParameters, PublicInput};
+
+/// The hardware or service that generates the proofs of a circuit.
+///
+/// Pass a backend to [`CircuitBuild::prove_with`] or [`LocalProver::with_backend`] to choose where
+/// a proof is generated. [`ProverContext`] generates it on the CPU of this machine, exactly like
+/// [`CircuitBuild::prove`].
+///
+/// [`LocalProver::with_backend`]: super::LocalProver::with_backend
+pub trait ProvingBackend<L: PlonkParameters<D>, const D: usize> {
+    #[allow(clippy::type_complexity)]
+    fn prove(
+        &self,
+        circuit: &CircuitBuild<L, D>,
+        input: &PublicInput<L, D>,
+    ) -> Result<(
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+    )>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>;
+}
+
+impl<L: PlonkParameters<D>, const D: usize> ProvingBackend<L, D> for ProverContext {
+    fn prove(
+        &self,
+        circuit: &CircuitBuild<L, D>,
+        input: &PublicInput<L, D>,
+    ) -> Result<(
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+    )>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        ProverContext::prove(self, circuit, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::prover::LocalProver;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_prover_context_backend_matches_prove() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<U64Variable>();
+        let b = builder.read::<U64Variable>();
+        let c = builder.mul(a, b);
+        builder.write(c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<U64Variable>(6);
+        input.write::<U64Variable>(7);
+        let (proof, output) = circuit.prove(&input);
+        let (backend_proof, mut backend_output) =
+            circuit.prove_with(&ProverContext::new(), &input).unwrap();
+        circuit.verify(&backend_proof, &input, &backend_output);
+
+        assert_eq!(backend_proof, proof);
+        assert_eq!(backend_output, output);
+        assert_eq!(backend_output.read::<U64Variable>(), 42);
+
+        let (local_proof, local_output) = LocalProver::new()
+            .with_backend(ProverContext::new())
+            .prove(&circuit, &input)
+            .unwrap()
+            .materialize()
+            .unwrap();
+        assert_eq!(local_proof, proof);
+        assert_eq!(local_output, *output);
+    }
+}
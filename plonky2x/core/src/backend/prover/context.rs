@@ -0,0 +1,243 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use log::debug;
+use plonky2::iop::witness::PartialWitness;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2::plonk::prover::prove_with_partition_witness;
+use plonky2::util::timing::TimingTree;
+
+use crate::backend::circuit::{
+    generate_witness_with_prepared, CircuitBuild, CircuitOutput, NetworkValues, PlonkParameters,
+    PreparedInputs, PublicInput, PublicOutput,
+};
+use crate::backend::prover::ProveMetrics;
+
+/// Generates proofs in this process, either directly or from inputs prepared ahead of time. The
+/// witness and the prover's polynomials are always held in memory.
+#[derive(Debug, Clone, Default)]
+pub struct ProverContext {}
+
+impl ProverContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates a proof for the circuit.
+    #[allow(clippy::type_complexity)]
+    pub fn prove<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        circuit: &CircuitBuild<L, D>,
+        input: &PublicInput<L, D>,
+    ) -> Result<(
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+    )>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        Ok(circuit.prove(input))
+    }
+
+    /// Runs witness generation for the given input and captures the values of the network-backed
+    /// generators, so that the proof can later be generated without network access using
+    /// [`ProverContext::prove_with_prepared`].
+    pub fn prepare_inputs<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        circuit: &CircuitBuild<L, D>,
+        input: &PublicInput<L, D>,
+    ) -> Result<PreparedInputs> {
+        let mut prepared = PreparedInputs::new(circuit.id());
+        let mut pw = PartialWitness::new();
+        circuit.io.set_witness(&mut pw, input);
+        generate_witness_with_prepared(
+            pw,
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            &circuit.async_hints,
+            &circuit.network_generator_ids,
+            NetworkValues::Record(&mut prepared),
+        )?;
+        Ok(prepared)
+    }
+
+    /// Generates a proof for the circuit using the values of the network-backed generators in
+    /// `prepared` instead of running them. Fails if `prepared` was captured for another circuit
+    /// or misses the values of a generator.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_with_prepared<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        circuit: &CircuitBuild<L, D>,
+        input: &PublicInput<L, D>,
+        prepared: &PreparedInputs,
+    ) -> Result<(
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+    )>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let (proof, output, _, _) = self.prove_with_prepared_timed(circuit, input, prepared)?;
+        Ok((proof, output))
+    }
+
+    /// Like [`ProverContext::prove_with_prepared`], but also returns the timing and size metrics
+    /// of the proof.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_with_prepared_and_metrics<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        circuit: &CircuitBuild<L, D>,
+        input: &PublicInput<L, D>,
+        prepared: &PreparedInputs,
+    ) -> Result<(
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+        ProveMetrics,
+    )>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let (proof, output, witness_time, prove_time) =
+            self.prove_with_prepared_timed(circuit, input, prepared)?;
+        let metrics = ProveMetrics::new(witness_time, prove_time, proof.to_bytes().len());
+        Ok((proof, output, metrics))
+    }
+
+    /// Like [`ProverContext::prove_with_prepared`], but also returns the time spent generating
+    /// the witness and the time spent generating the proof from it.
+    #[allow(clippy::type_complexity)]
+    fn prove_with_prepared_timed<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        circuit: &CircuitBuild<L, D>,
+        input: &PublicInput<L, D>,
+        prepared: &PreparedInputs,
+    ) -> Result<(
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+        Duration,
+        Duration,
+    )>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        prepared.check_circuit(&circuit.id())?;
+
+        let start_time = Instant::now();
+        let mut pw = PartialWitness::new();
+        circuit.io.set_witness(&mut pw, input);
+        let witness = generate_witness_with_prepared(
+            pw,
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            &circuit.async_hints,
+            &circuit.network_generator_ids,
+            NetworkValues::Replay(prepared),
+        )?;
+        let witness_time = start_time.elapsed();
+        debug!("Witness generation took {:?}", witness_time);
+
+        let start_time = Instant::now();
+        let proof_with_pis = prove_with_partition_witness::<L::Field, L::Config, D>(
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            witness,
+            &mut TimingTree::default(),
+        )?;
+        let output = CircuitOutput::new(
+            PublicOutput::from_proof_with_pis(&circuit.io, &proof_with_pis),
+            &circuit.output_labels,
+        );
+        let prove_time = start_time.elapsed();
+        debug!("proving took: {:?}", witness_time + prove_time);
+        Ok((proof_with_pis, output, witness_time, prove_time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::frontend::hint::simple::hint::Hint;
+    use crate::frontend::vars::ValueStream;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    thread_local! {
+        static NETWORK_ENABLED: Cell<bool> = Cell::new(true);
+    }
+
+    /// A hint that stands in for an RPC call.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockRpcHint;
+
+    impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for MockRpcHint {
+        const NETWORK: bool = true;
+
+        fn hint(
+            &self,
+            input_stream: &mut ValueStream<L, D>,
+            output_stream: &mut ValueStream<L, D>,
+        ) {
+            assert!(
+                NETWORK_ENABLED.with(|enabled| enabled.get()),
+                "network access is disabled"
+            );
+            let slot = input_stream.read_value::<U64Variable>();
+            output_stream.write_value::<U64Variable>(slot * 3 + 7);
+        }
+    }
+
+    fn mock_rpc_circuit() -> CircuitBuild<L, D> {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let slot = builder.read::<U64Variable>();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&slot);
+        let output_stream = builder.hint(input_stream, MockRpcHint);
+        let fetched = output_stream.read::<U64Variable>(&mut builder);
+        let result = builder.add(fetched, slot);
+        builder.register_public_output("result", &result);
+        builder.build()
+    }
+
+    #[test]
+    fn test_prove_with_prepared_inputs() {
+        let circuit = mock_rpc_circuit();
+        let mut input = circuit.input();
+        input.write::<U64Variable>(5);
+
+        let context = ProverContext::new();
+        let prepared = context.prepare_inputs(&circuit, &input).unwrap();
+        assert_eq!(prepared.len(), 1);
+        let prepared: PreparedInputs =
+            serde_json::from_str(&serde_json::to_string(&prepared).unwrap()).unwrap();
+
+        NETWORK_ENABLED.with(|enabled| enabled.set(false));
+        let (proof, mut output) = context
+            .prove_with_prepared(&circuit, &input, &prepared)
+            .unwrap();
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.get::<U64Variable>("result"), 27);
+        assert_eq!(output.read::<U64Variable>(), 27);
+
+        let missing = PreparedInputs::new(circuit.id());
+        let err = context
+            .prove_with_prepared(&circuit, &input, &missing)
+            .unwrap_err();
+        assert!(err.to_string().contains("missing"));
+
+        let other = PreparedInputs::new("other".to_string());
+        assert!(context
+            .prove_with_prepared(&circuit, &input, &other)
+            .is_err());
+    }
+}
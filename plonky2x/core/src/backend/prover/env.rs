@@ -4,7 +4,7 @@ use anyhow::Result;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 
 use super::local::LocalProver;
-use super::{ProverOutput, ProverOutputs, RemoteProver};
+use super::{ProveMetrics, ProverOutput, ProverOutputs, RemoteProver};
 use crate::backend::circuit::{CircuitBuild, CircuitSerializer, PlonkParameters, PublicInput};
 
 /// A prover that can generate proofs locally or remotely based on the env variable `PROVER` which
@@ -62,4 +62,38 @@ impl EnvProver {
             LocalProver::new().batch_prove(&circuit, inputs)
         }
     }
+
+    /// Like `batch_prove`, but also returns the timing and size metrics of each proof when the
+    /// proofs are generated locally. The remote prover does not report metrics.
+    #[allow(clippy::type_complexity)]
+    pub fn batch_prove_with_metrics<L: PlonkParameters<D>, S: CircuitSerializer, const D: usize>(
+        &self,
+        circuit_id: &str,
+        inputs: &[PublicInput<L, D>],
+    ) -> Result<(ProverOutputs<L, D>, Option<Vec<ProveMetrics>>)>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        if env::var("PROVER").unwrap_or("local".to_string()) == "remote" {
+            let outputs = self.batch_prove::<L, S, D>(circuit_id, inputs)?;
+            return Ok((outputs, None));
+        }
+        let gate_serializer = S::gate_registry::<L, D>();
+        let generator_serializer = S::generator_registry::<L, D>();
+        let circuit_path = format!("./build/{}.circuit", circuit_id);
+        let circuit =
+            CircuitBuild::<L, D>::load(&circuit_path, &gate_serializer, &generator_serializer)
+                .unwrap();
+        let mut proofs = Vec::new();
+        let mut outputs = Vec::new();
+        let mut metrics = Vec::new();
+        for input in inputs {
+            let (proof, output, proof_metrics) = circuit.prove_with_metrics(input);
+            proofs.push(proof);
+            outputs.push(output.into());
+            metrics.push(proof_metrics);
+        }
+        Ok((ProverOutputs::Local(proofs, outputs), Some(metrics)))
+    }
 }
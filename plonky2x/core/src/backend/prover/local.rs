@@ -2,19 +2,30 @@ use anyhow::Result;
 use log::debug;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 
-use super::{ProverOutput, ProverOutputs};
+use super::{ProverContext, ProverOutput, ProverOutputs, ProvingBackend};
 use crate::backend::circuit::{CircuitBuild, PlonkParameters, PublicInput};
 
-/// A prover that generates proofs locally.
+/// A prover that generates proofs locally with the given [`ProvingBackend`].
 #[derive(Debug, Clone)]
-pub struct LocalProver;
+pub struct LocalProver<B = ProverContext> {
+    backend: B,
+}
 
 impl LocalProver {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self {}
+        Self {
+            backend: ProverContext::new(),
+        }
     }
 
+    /// Sets the backend used to generate proofs.
+    pub fn with_backend<B>(self, backend: B) -> LocalProver<B> {
+        LocalProver { backend }
+    }
+}
+
+impl<B> LocalProver<B> {
     #[allow(clippy::type_complexity)]
     pub fn prove<L: PlonkParameters<D>, const D: usize>(
         &self,
@@ -22,11 +33,12 @@ impl LocalProver {
         input: &PublicInput<L, D>,
     ) -> Result<ProverOutput<L, D>>
     where
+        B: ProvingBackend<L, D>,
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
             AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
     {
-        let (proof, output) = circuit.prove(input);
-        Ok(ProverOutput::Local(proof, output))
+        let (proof, output) = self.backend.prove(circuit, input)?;
+        Ok(ProverOutput::Local(proof, output.into()))
     }
 
     /// Generates a batch of proofs with the given input.
@@ -37,6 +49,7 @@ impl LocalProver {
         inputs: &[PublicInput<L, D>],
     ) -> Result<ProverOutputs<L, D>>
     where
+        B: ProvingBackend<L, D>,
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
             AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
     {
@@ -0,0 +1,25 @@
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Timing and size metrics of a single proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProveMetrics {
+    /// The time spent generating the witness, in milliseconds.
+    pub witness_ms: u64,
+    /// The time spent generating the proof from the witness, in milliseconds.
+    pub prove_ms: u64,
+    /// The size of the serialized proof, in bytes. Results that serialize a different proof, such
+    /// as a wrapped one, report the size of the proof they serialize.
+    pub proof_bytes: usize,
+}
+
+impl ProveMetrics {
+    pub fn new(witness_time: Duration, prove_time: Duration, proof_bytes: usize) -> Self {
+        Self {
+            witness_ms: witness_time.as_millis() as u64,
+            prove_ms: prove_time.as_millis() as u64,
+            proof_bytes,
+        }
+    }
+}
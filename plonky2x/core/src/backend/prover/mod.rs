@@ -1,14 +1,22 @@
+mod backend;
+mod context;
 mod env;
 mod local;
+mod metrics;
 mod remote;
 mod service;
+mod warm;
 
 use anyhow::Result;
+pub use backend::ProvingBackend;
+pub use context::ProverContext;
 pub use env::EnvProver;
 pub use local::LocalProver;
+pub use metrics::ProveMetrics;
 use plonky2::plonk::proof::ProofWithPublicInputs;
 pub use remote::RemoteProver;
 pub use service::{BatchProofId, ProofId, ProofService};
+pub use warm::Prover;
 
 use super::circuit::{PlonkParameters, PublicOutput};
 
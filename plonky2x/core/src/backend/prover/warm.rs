@@ -0,0 +1,200 @@
+use std::time::Instant;
+
+use log::debug;
+use plonky2::iop::witness::PartialWitness;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2::plonk::prover::prove_with_partition_witness;
+use plonky2::util::timing::TimingTree;
+use tokio::runtime::Runtime;
+
+use crate::backend::circuit::{
+    generate_witness_with_scratch, CircuitBuild, CircuitOutput, PlonkParameters, PublicInput,
+    PublicOutput, WitnessScratch,
+};
+use crate::backend::prover::ProveMetrics;
+
+/// A prover for a single circuit that reuses its resources between proofs.
+///
+/// The prover owns the circuit, serves the asynchronous hints of every proof on the same runtime
+/// and reuses the witness generation buffers. The preprocessed data, including the FFT root tables,
+/// is already computed when the circuit is built, so compared to [`CircuitBuild::prove`] this only
+/// saves the runtime setup and the buffer allocations of each proof.
+#[derive(Debug)]
+pub struct Prover<L: PlonkParameters<D>, const D: usize> {
+    circuit: CircuitBuild<L, D>,
+    runtime: Option<Runtime>,
+    scratch: WitnessScratch<L::Field>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Prover<L, D> {
+    pub fn new(circuit: CircuitBuild<L, D>) -> Self {
+        // Only circuits with asynchronous hints need a runtime to serve them.
+        let runtime = (!circuit.async_hints.is_empty())
+            .then(|| Runtime::new().expect("failed to create tokio runtime"));
+
+        Self {
+            circuit,
+            runtime,
+            scratch: WitnessScratch::default(),
+        }
+    }
+
+    /// The circuit this prover generates proofs for.
+    pub fn circuit(&self) -> &CircuitBuild<L, D> {
+        &self.circuit
+    }
+
+    pub fn into_circuit(self) -> CircuitBuild<L, D> {
+        self.circuit
+    }
+
+    /// Generates a proof for the circuit. The proof can be verified using
+    /// [`CircuitBuild::verify`].
+    pub fn prove(
+        &mut self,
+        input: &PublicInput<L, D>,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+    )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let (proof, output, _) = self.prove_with_metrics(input);
+        (proof, output)
+    }
+
+    /// Generates a proof for the circuit, along with the same timing and size metrics as
+    /// [`CircuitBuild::prove_with_metrics`], so that the latency of both can be compared.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_with_metrics(
+        &mut self,
+        input: &PublicInput<L, D>,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        CircuitOutput<L, D>,
+        ProveMetrics,
+    )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let start_time = Instant::now();
+        let mut pw = PartialWitness::new();
+        self.circuit.io.set_witness(&mut pw, input);
+        let witness = generate_witness_with_scratch(
+            pw,
+            &self.circuit.data.prover_only,
+            &self.circuit.data.common,
+            &self.circuit.async_hints,
+            self.runtime.as_ref().map(|rt| rt.handle()),
+            &mut self.scratch,
+        )
+        .unwrap();
+        let witness_time = start_time.elapsed();
+        debug!("Witness generation took {:?}", witness_time);
+
+        let proof_with_pis = prove_with_partition_witness::<L::Field, L::Config, D>(
+            &self.circuit.data.prover_only,
+            &self.circuit.data.common,
+            witness,
+            &mut TimingTree::default(),
+        )
+        .unwrap();
+        let output = CircuitOutput::new(
+            PublicOutput::from_proof_with_pis(&self.circuit.io, &proof_with_pis),
+            &self.circuit.output_labels,
+        );
+        let elapsed_time = start_time.elapsed();
+        debug!("proving took: {:?}", elapsed_time);
+        let metrics = ProveMetrics::new(
+            witness_time,
+            elapsed_time - witness_time,
+            proof_with_pis.to_bytes().len(),
+        );
+        (proof_with_pis, output, metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    fn sha256_circuit() -> CircuitBuild<L, D> {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let data = builder.read::<BytesVariable<128>>();
+        let digest = builder.curta_sha256(&data.0);
+        builder.write(digest);
+        builder.build()
+    }
+
+    #[test]
+    fn test_prover_sequential_proofs() {
+        let mut prover = Prover::new(sha256_circuit());
+
+        for seed in 0..3u8 {
+            let data = [seed; 128];
+            let mut input = prover.circuit().input();
+            input.write::<BytesVariable<128>>(data);
+
+            let (proof, mut output) = prover.prove(&input);
+            prover.circuit().verify(&proof, &input, &output);
+            assert_eq!(
+                output.read::<Bytes32Variable>(),
+                H256::from_slice(&Sha256::digest(data))
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_prover_latency_against_circuit_prove() {
+        env_logger::try_init().unwrap_or_default();
+        const NUM_PROOFS: u64 = 5;
+        let circuit = sha256_circuit();
+        let inputs = (0..NUM_PROOFS as u8)
+            .map(|seed| {
+                let mut input = circuit.input();
+                input.write::<BytesVariable<128>>([seed; 128]);
+                input
+            })
+            .collect::<Vec<_>>();
+
+        let (mut cold_witness_ms, mut cold_prove_ms) = (0, 0);
+        for input in inputs.iter() {
+            let (proof, output, metrics) = circuit.prove_with_metrics(input);
+            circuit.verify(&proof, input, &output);
+            cold_witness_ms += metrics.witness_ms;
+            cold_prove_ms += metrics.prove_ms;
+        }
+
+        let mut prover = Prover::new(circuit);
+        let (mut warm_witness_ms, mut warm_prove_ms) = (0, 0);
+        for input in inputs.iter() {
+            let (proof, output, metrics) = prover.prove_with_metrics(input);
+            prover.circuit().verify(&proof, input, &output);
+            warm_witness_ms += metrics.witness_ms;
+            warm_prove_ms += metrics.prove_ms;
+        }
+
+        log::info!(
+            "CircuitBuild::prove per proof: witness {}ms, prove {}ms",
+            cold_witness_ms / NUM_PROOFS,
+            cold_prove_ms / NUM_PROOFS
+        );
+        log::info!(
+            "Prover::prove per proof: witness {}ms, prove {}ms",
+            warm_witness_ms / NUM_PROOFS,
+            warm_prove_ms / NUM_PROOFS
+        );
+    }
+}
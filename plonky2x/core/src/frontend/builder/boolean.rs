@@ -1,3 +1,5 @@
+use plonky2::iop::target::BoolTarget;
+
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::vars::{BoolVariable, Variable};
@@ -17,6 +19,18 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         // "one" will be within boolean range.
         BoolVariable::from_variables_unsafe(&[one])
     }
+
+    /// Constrains `x` to be 0 or 1, i.e. `x * (x - 1) == 0`, and returns it as a `BoolVariable`.
+    pub fn assert_bool(&mut self, x: Variable) -> BoolVariable {
+        self.api.assert_bool(BoolTarget::new_unsafe(x.0));
+        BoolVariable::from_variables_unsafe(&[x])
+    }
+
+    /// Returns `!(lhs && rhs)`.
+    pub fn nand(&mut self, lhs: BoolVariable, rhs: BoolVariable) -> BoolVariable {
+        let lhs_and_rhs = self.and(lhs, rhs);
+        self.not(lhs_and_rhs)
+    }
 }
 
 #[cfg(test)]
@@ -27,6 +41,7 @@ mod tests {
 
     use super::*;
     use crate::backend::circuit::DefaultParameters;
+    use crate::utils::test_utils::assert_constraint_failure;
 
     type L = DefaultParameters;
     const D: usize = 2;
@@ -42,4 +57,71 @@ mod tests {
         let value = pw.try_get_target(b.variable.0).unwrap();
         assert_eq!(GoldilocksField::ONE, value);
     }
+
+    #[test]
+    fn test_bool_truth_tables() {
+        let rows = [(false, false), (false, true), (true, false), (true, true)];
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        for _ in rows.iter() {
+            let x = builder.read::<BoolVariable>();
+            let y = builder.read::<BoolVariable>();
+            let outputs = [
+                builder.and(x, y),
+                builder.or(x, y),
+                builder.xor(x, y),
+                builder.not(x),
+                builder.nand(x, y),
+            ];
+            for output in outputs {
+                builder.write(output);
+            }
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for (x, y) in rows.iter() {
+            input.write::<BoolVariable>(*x);
+            input.write::<BoolVariable>(*y);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for (x, y) in rows {
+            assert_eq!(output.read::<BoolVariable>(), x && y, "{} and {}", x, y);
+            assert_eq!(output.read::<BoolVariable>(), x || y, "{} or {}", x, y);
+            assert_eq!(output.read::<BoolVariable>(), x ^ y, "{} xor {}", x, y);
+            assert_eq!(output.read::<BoolVariable>(), !x, "not {}", x);
+            assert_eq!(output.read::<BoolVariable>(), !(x && y), "{} nand {}", x, y);
+        }
+    }
+
+    #[test]
+    fn test_assert_bool() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let x = builder.read::<Variable>();
+        let b = builder.assert_bool(x);
+        let t = builder._true();
+        let f = builder._false();
+        let selected = builder.select(b, t, f);
+        builder.write(selected);
+
+        let circuit = builder.build();
+        for value in [false, true] {
+            let mut input = circuit.input();
+            input.write::<Variable>(GoldilocksField::from_bool(value));
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<BoolVariable>(), value);
+        }
+
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let x = builder.read::<Variable>();
+                builder.assert_bool(x);
+            },
+            |input| input.write::<Variable>(GoldilocksField::TWO),
+            None,
+        );
+    }
 }
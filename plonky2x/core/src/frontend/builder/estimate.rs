@@ -0,0 +1,288 @@
+use core::cell::RefCell;
+
+use plonky2::util::log2_ceil;
+use starkyx::machine::hash::blake::blake2b::BLAKE2B;
+use starkyx::machine::hash::sha::sha256::SHA256;
+use starkyx::machine::hash::sha::sha512::SHA512;
+
+use super::{CircuitBuilder, CircuitIO};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::curta::request::HashRequest;
+use crate::frontend::vars::CircuitVariable;
+
+/// The rows of the accelerator verifiers, keyed by the shape of the accelerator requests they were
+/// counted for.
+#[derive(Debug, Default)]
+pub(crate) struct AcceleratorRowsCache(RefCell<Option<(Vec<usize>, usize)>>);
+
+/// An estimate of the size of a circuit and of the resources needed to prove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitEstimate {
+    /// The approximate number of rows (gates) of the circuit before padding.
+    pub approx_rows: usize,
+    /// The approximate log2 of the number of rows after padding to a power of two.
+    pub approx_degree_bits: usize,
+    /// A rough estimate of the peak memory used by the prover, dominated by the low-degree
+    /// extensions of the committed polynomials.
+    pub approx_prover_memory_bytes: usize,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Estimates the size of the circuit as it is currently defined.
+    ///
+    /// This can be called at any point while building. Gates that were already added are counted
+    /// exactly, while the constraints that are only added at build time (accelerators, batched
+    /// range checks and public input hashing) are estimated.
+    pub fn estimate(&self) -> CircuitEstimate {
+        let approx_rows = self.api.num_gates()
+            + self.estimate_accelerator_rows()
+            + self.range_checks.estimate_rows(&self.api.config)
+            + self.estimate_public_input_rows();
+        let approx_degree_bits = log2_ceil(approx_rows.max(1));
+
+        let config = &self.api.config;
+        let lde_size = (1usize << approx_degree_bits) << config.fri_config.rate_bits;
+        let num_partial_products = config.num_routed_wires.div_ceil(8);
+        let num_polys = config.num_wires
+            + config.num_routed_wires
+            + config.num_constants
+            + config.num_challenges * (1 + num_partial_products + 8);
+        let approx_prover_memory_bytes = num_polys * lde_size * std::mem::size_of::<u64>();
+
+        CircuitEstimate {
+            approx_rows,
+            approx_degree_bits,
+            approx_prover_memory_bytes,
+        }
+    }
+
+    /// Asserts that the estimated degree of the circuit does not exceed `2^max_degree_bits`.
+    ///
+    /// This fails fast while the circuit is being defined, instead of after an expensive build.
+    #[track_caller]
+    pub fn assert_max_degree_bits(&self, max_degree_bits: usize) {
+        let estimate = self.estimate();
+        if estimate.approx_degree_bits > max_degree_bits {
+            panic!(
+                "circuit exceeds the maximum degree of 2^{}: estimated {} rows (degree 2^{})",
+                max_degree_bits, estimate.approx_rows, estimate.approx_degree_bits
+            );
+        }
+    }
+
+    /// Counts the rows added when the accelerators are constrained at build time.
+    ///
+    /// The constraints are added to a scratch builder, which is only used to count gates and is
+    /// never built. The count only depends on the number, type and length of the accelerator
+    /// requests, so it is cached until a request is added.
+    fn estimate_accelerator_rows(&self) -> usize {
+        #[cfg(feature = "ecc")]
+        let has_ec_ops_accelerator = self.ec_25519_ops_accelerator.is_some();
+        #[cfg(not(feature = "ecc"))]
+        let has_ec_ops_accelerator = false;
+        if self.blake2b_accelerator.is_none()
+            && self.sha256_accelerator.is_none()
+            && self.sha512_accelerator.is_none()
+            && !has_ec_ops_accelerator
+        {
+            return 0;
+        }
+
+        let shape = self.accelerator_shape();
+        if let Some((cached_shape, rows)) = self.accelerator_rows.0.borrow().as_ref() {
+            if *cached_shape == shape {
+                return *rows;
+            }
+        }
+
+        let mut scratch = CircuitBuilder::<L, D>::new();
+        if let Some(accelerator) = self.blake2b_accelerator.clone() {
+            scratch.curta_constrain_hash::<BLAKE2B, 96, true, 4>(accelerator);
+        }
+        if let Some(accelerator) = self.sha256_accelerator.clone() {
+            scratch.curta_constrain_hash::<SHA256, 64, false, 8>(accelerator);
+        }
+        if let Some(accelerator) = self.sha512_accelerator.clone() {
+            scratch.curta_constrain_hash::<SHA512, 80, false, 8>(accelerator);
+        }
+        #[cfg(feature = "ecc")]
+        if let Some(accelerator) = self.ec_25519_ops_accelerator.clone() {
+            scratch.curta_constrain_ec_op(accelerator);
+        }
+
+        let rows =
+            scratch.api.num_gates() + scratch.range_checks.estimate_rows(&scratch.api.config);
+        *self.accelerator_rows.0.borrow_mut() = Some((shape, rows));
+        rows
+    }
+
+    /// Describes the accelerator requests by their type and message length, which is all that the
+    /// size of the accelerator verifiers depends on.
+    fn accelerator_shape(&self) -> Vec<usize> {
+        let hash_shape = |requests: Option<&Vec<HashRequest>>| {
+            let requests = requests.map(Vec::as_slice).unwrap_or_default();
+            let mut shape = vec![requests.len()];
+            shape.extend(requests.iter().map(|request| match request {
+                HashRequest::Fixed(message) => 2 * message.len(),
+                HashRequest::Variable(message, _, _) => 2 * message.len() + 1,
+            }));
+            shape
+        };
+
+        let mut shape = Vec::new();
+        shape.extend(hash_shape(
+            self.blake2b_accelerator.as_ref().map(|a| &a.hash_requests),
+        ));
+        shape.extend(hash_shape(
+            self.sha256_accelerator.as_ref().map(|a| &a.hash_requests),
+        ));
+        shape.extend(hash_shape(
+            self.sha512_accelerator.as_ref().map(|a| &a.hash_requests),
+        ));
+        #[cfg(feature = "ecc")]
+        if let Some(accelerator) = self.ec_25519_ops_accelerator.as_ref() {
+            shape.push(accelerator.ec_op_requests.len());
+            shape.extend(
+                accelerator
+                    .ec_op_requests
+                    .iter()
+                    .map(|request| request.req_type() as usize),
+            );
+        }
+        shape
+    }
+
+    /// Estimates the rows needed to register and hash the public inputs.
+    fn estimate_public_input_rows(&self) -> usize {
+        let pending = match self.io {
+            CircuitIO::Bytes(ref io) => (io.input.len() + io.output.len()) * 8,
+            CircuitIO::Elements(ref io) => io.input.len() + io.output.len(),
+            CircuitIO::RecursiveProofs(ref io) => {
+                io.output.iter().map(|v| v.variables().len()).sum::<usize>()
+            }
+            CircuitIO::CyclicProof(_) | CircuitIO::None() => 0,
+        };
+        let num_public_inputs = self.api.num_public_inputs() + pending;
+
+        // One row for the public input gate and one poseidon permutation per 8 public inputs.
+        1 + num_public_inputs.div_ceil(8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    /// Asserts that the estimated rows are within 15% of the rows of the built circuit before
+    /// padding, i.e. its gates plus the public input gate and the public input hash.
+    fn assert_estimate_close(builder: CircuitBuilder<L, D>) {
+        let estimate = builder.estimate();
+        let circuit = builder.build();
+        let stats = circuit.stats();
+        let rows = stats.num_gates + 1 + stats.num_public_inputs.div_ceil(8);
+        assert!(
+            estimate.approx_rows.abs_diff(rows) * 100 <= rows * 15,
+            "estimated {} rows but the circuit has {} rows",
+            estimate.approx_rows,
+            rows
+        );
+    }
+
+    #[test]
+    fn test_estimate_arithmetic() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let mut acc = builder.read::<U64Variable>();
+        for _ in 0..256 {
+            let x = builder.read::<U64Variable>();
+            acc = builder.add(acc, x);
+        }
+        builder.write(acc);
+
+        let estimate = builder.estimate();
+        assert!(estimate.approx_rows >= builder.api.num_gates());
+        assert!(estimate.approx_prover_memory_bytes > 0);
+        assert_estimate_close(builder);
+    }
+
+    #[test]
+    fn test_estimate_sha256() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let leaf = builder.read::<Bytes32Variable>();
+        let mut digest = leaf;
+        for _ in 0..8 {
+            digest = builder.curta_sha256_pair(digest, leaf);
+        }
+        builder.write(digest);
+
+        assert_estimate_close(builder);
+    }
+
+    #[test]
+    fn test_estimate_cached_accelerator_rows() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let leaf = builder.read::<Bytes32Variable>();
+        let digest = builder.curta_sha256_pair(leaf, leaf);
+        let first = builder.estimate();
+        assert_eq!(builder.estimate(), first);
+
+        // A new request changes the shape of the accelerator, so the rows are counted again.
+        builder.curta_sha256_pair(digest, leaf);
+        let second = builder.estimate();
+        assert!(second.approx_rows >= first.approx_rows);
+        let cached_shape = builder.accelerator_rows.0.borrow().clone().unwrap().0;
+        assert_eq!(cached_shape, builder.accelerator_shape());
+    }
+
+    #[test]
+    #[cfg(feature = "ssz")]
+    fn test_estimate_ssz() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let root = builder.read::<Bytes32Variable>();
+        let leaf = builder.read::<Bytes32Variable>();
+        let branch = builder.read::<ArrayVariable<Bytes32Variable, 24>>();
+        let gindex = builder.read::<U64Variable>();
+        builder.ssz_verify_proof(root, leaf, branch.as_slice(), gindex);
+
+        assert_estimate_close(builder);
+    }
+
+    #[test]
+    #[cfg(feature = "beacon")]
+    fn test_estimate_beacon() {
+        use crate::frontend::eth::beacon::vars::BeaconValidatorVariable;
+        use crate::frontend::eth::vars::BLSPubkeyVariable;
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let subtree_root = builder.read::<Bytes32Variable>();
+        let length = builder.read::<U64Variable>();
+        let validators = (0..8)
+            .map(|_| builder.read::<BeaconValidatorVariable>())
+            .collect::<Vec<_>>();
+        let pubkey = builder.read::<BLSPubkeyVariable>();
+        builder.beacon_assert_pubkey_absent_from_subtree(subtree_root, length, &validators, pubkey);
+
+        assert_estimate_close(builder);
+    }
+
+    #[test]
+    fn test_assert_max_degree_bits() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let x = builder.read::<U64Variable>();
+        let y = builder.add(x, x);
+        builder.write(y);
+        builder.assert_max_degree_bits(20);
+    }
+
+    #[test]
+    #[should_panic(expected = "circuit exceeds the maximum degree")]
+    fn test_assert_max_degree_bits_fails() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let leaf = builder.read::<Bytes32Variable>();
+        let digest = builder.curta_sha256_pair(leaf, leaf);
+        builder.write(digest);
+        builder.assert_max_degree_bits(4);
+    }
+}
@@ -1,3 +1,5 @@
+use core::ops::Range;
+
 use plonky2::iop::witness::{PartialWitness, WitnessWrite};
 use plonky2::plonk::circuit_data::{CommonCircuitData, VerifierCircuitTarget};
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
@@ -6,8 +8,12 @@ use serde::{Deserialize, Serialize};
 
 use super::CircuitBuilder;
 use crate::backend::circuit::{PlonkParameters, PublicInput};
-use crate::frontend::vars::EvmVariable;
-use crate::prelude::{ByteVariable, CircuitVariable, Variable};
+use crate::frontend::eth::vars::{AddressVariable, BLSPubkeyVariable};
+use crate::frontend::vars::{EvmVariable, ValueSerializable};
+use crate::prelude::{
+    BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable, U128Variable,
+    U256Variable, U32Variable, U64Variable, Variable,
+};
 use crate::utils::serde::{
     deserialize_proof_with_pis_target_option, deserialize_proof_with_pis_target_vec,
     deserialize_verifier_circuit_target_option, serialize_proof_with_pis_target_option,
@@ -53,6 +59,88 @@ pub struct CyclicProofIO<const D: usize> {
     pub closed: bool,
 }
 
+/// How a value is encoded in the JSON inputs and outputs of a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueEncoding {
+    /// A `0x`-prefixed hex string of the value bytes.
+    Hex,
+    /// A non-negative integer, as a JSON number or a decimal string.
+    Decimal,
+    /// A JSON `true` or `false`.
+    Bool,
+}
+
+/// A variable that can be described in the IO schema of a circuit.
+///
+/// The byte length of a value in the schema is the length of its `ValueSerializable` encoding.
+pub trait SchemaVariable: ValueSerializable {
+    /// The name of the variable type in the schema.
+    fn schema_type() -> String;
+
+    /// How the value is encoded in JSON.
+    fn encoding() -> ValueEncoding;
+}
+
+macro_rules! impl_schema_variable {
+    ($type:ty, $encoding:expr) => {
+        impl SchemaVariable for $type {
+            fn schema_type() -> String {
+                stringify!($type).to_string()
+            }
+
+            fn encoding() -> ValueEncoding {
+                $encoding
+            }
+        }
+    };
+}
+
+impl_schema_variable!(Variable, ValueEncoding::Decimal);
+impl_schema_variable!(BoolVariable, ValueEncoding::Bool);
+impl_schema_variable!(U32Variable, ValueEncoding::Decimal);
+impl_schema_variable!(U64Variable, ValueEncoding::Decimal);
+impl_schema_variable!(U128Variable, ValueEncoding::Decimal);
+impl_schema_variable!(U256Variable, ValueEncoding::Decimal);
+impl_schema_variable!(ByteVariable, ValueEncoding::Hex);
+impl_schema_variable!(Bytes32Variable, ValueEncoding::Hex);
+impl_schema_variable!(AddressVariable, ValueEncoding::Hex);
+impl_schema_variable!(BLSPubkeyVariable, ValueEncoding::Hex);
+
+impl<const N: usize> SchemaVariable for BytesVariable<N> {
+    fn schema_type() -> String {
+        format!("BytesVariable<{}>", N)
+    }
+
+    fn encoding() -> ValueEncoding {
+        ValueEncoding::Hex
+    }
+}
+
+/// A name given to a range of the field elements of the circuit input or output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IOLabel {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub variable_type: String,
+    pub byte_length: usize,
+    pub encoding: ValueEncoding,
+    pub range: Range<usize>,
+}
+
+impl IOLabel {
+    fn new<V: SchemaVariable>(name: &str, start: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            variable_type: V::schema_type(),
+            byte_length: V::nb_value_bytes(),
+            encoding: V::encoding(),
+            range: start..start + V::nb_elements(),
+        }
+    }
+}
+
 /// A schema for what the inputs and outputs are for a circuit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
@@ -318,6 +406,44 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 
+    /// Reads a variable from the circuit input and records `name` for the field elements it
+    /// occupies, so that the input can be given as a labeled object.
+    pub fn read_named<V: SchemaVariable>(&mut self, name: &str) -> V {
+        assert!(
+            self.input_labels.iter().all(|label| label.name != name),
+            "public input {} is already registered",
+            name
+        );
+        self.try_init_field_io();
+        let start = match self.io {
+            CircuitIO::Elements(ref io) => io.input.len(),
+            CircuitIO::RecursiveProofs(ref io) => io.input.len(),
+            CircuitIO::CyclicProof(ref io) => io.input.len(),
+            _ => panic!("field io is not enabled"),
+        };
+        let variable = self.read::<V>();
+        self.input_labels.push(IOLabel::new::<V>(name, start));
+        variable
+    }
+
+    /// Writes a variable to the circuit output and records `name` for the field elements it
+    /// occupies, so that the output can be decoded as a labeled object.
+    pub fn register_public_output<V: SchemaVariable>(&mut self, name: &str, variable: &V) {
+        assert!(
+            self.output_labels.iter().all(|label| label.name != name),
+            "public output {} is already registered",
+            name
+        );
+        self.try_init_field_io();
+        let start = match self.io {
+            CircuitIO::Elements(ref io) => io.output.len(),
+            CircuitIO::CyclicProof(ref io) => io.output.len(),
+            _ => panic!("field io is not enabled"),
+        };
+        self.write(variable.clone());
+        self.output_labels.push(IOLabel::new::<V>(name, start));
+    }
+
     // @audit
     pub fn evm_write<V: EvmVariable>(&mut self, variable: V) {
         self.try_init_evm_io();
@@ -328,6 +454,22 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 
+    /// Writes a keccak256 commitment to the input bytes read so far as an output, so that an
+    /// on-chain verifier can bind the proof to its inputs by checking that the commitment equals
+    /// `keccak256(inputs)`.
+    ///
+    /// Requires evm io, and should be called after every input has been read.
+    #[cfg(feature = "keccak")]
+    pub fn commit_inputs(&mut self) -> Bytes32Variable {
+        let input = match self.io {
+            CircuitIO::Bytes(ref io) => io.input.clone(),
+            _ => panic!("commit_inputs requires evm io"),
+        };
+        let commitment = self.keccak256(&input);
+        self.evm_write(commitment);
+        commitment
+    }
+
     // @audit
     pub fn proof_write<V: CircuitVariable>(&mut self, variable: V) {
         self.try_init_proof_io();
@@ -337,3 +479,43 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 }
+
+#[cfg(all(test, feature = "keccak"))]
+mod tests {
+    use ethers::types::H256;
+    use ethers::utils::keccak256;
+
+    use crate::backend::circuit::DefaultParameters;
+    use crate::prelude::*;
+    use crate::utils::bytes32;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_commit_inputs() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.evm_read::<Bytes32Variable>();
+        let b = builder.evm_read::<U64Variable>();
+        builder.commit_inputs();
+        builder.evm_write(a);
+        builder.evm_write(b);
+        let circuit = builder.build();
+
+        let a = bytes32!("0x5c1fd3d5ba1a9e4fcb1b2e8f7b5d2e2bd0de5bd91f0c6d0eca8d2ef6c5f0ea3e");
+        let b = 0xdeadbeefu64;
+        let mut input = circuit.input();
+        input.evm_write::<Bytes32Variable>(a);
+        input.evm_write::<U64Variable>(b);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let input_bytes = [a.as_bytes(), &b.to_be_bytes()].concat();
+        assert_eq!(
+            output.evm_read::<Bytes32Variable>(),
+            H256::from(keccak256(&input_bytes))
+        );
+        assert_eq!(output.evm_read::<Bytes32Variable>(), a);
+        assert_eq!(output.evm_read::<U64Variable>(), b);
+    }
+}
@@ -1,12 +1,16 @@
 mod boolean;
+pub mod estimate;
 pub mod io;
 pub mod permutation;
 mod proof;
+mod range_check;
 pub mod watch;
 
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use std::collections::HashMap;
+#[cfg(feature = "beacon")]
 use std::env;
+use std::path::PathBuf;
 
 use backtrace::Backtrace;
 use ethers::providers::{Http, Middleware, Provider};
@@ -21,7 +25,10 @@ use starkyx::machine::hash::sha::sha256::SHA256;
 use starkyx::machine::hash::sha::sha512::SHA512;
 use tokio::runtime::Runtime;
 
-pub use self::io::CircuitIO;
+pub use self::estimate::AcceleratorRowsCache;
+pub use self::io::{CircuitIO, IOLabel, SchemaVariable, ValueEncoding};
+use self::range_check::RangeChecks;
+#[cfg(feature = "ecc")]
 use super::ecc::curve25519::curta::accelerator::EcOpAccelerator;
 use super::hash::blake2::curta::BLAKE2BAccelerator;
 use super::hash::sha::sha256::curta::SHA256Accelerator;
@@ -32,7 +39,8 @@ use crate::backend::circuit::{CircuitBuild, DefaultParameters, MockCircuitBuild,
 use crate::frontend::hint::asynchronous::generator::AsyncHintDataRef;
 use crate::frontend::vars::{BoolVariable, CircuitVariable, Variable};
 use crate::prelude::ArrayVariable;
-use crate::utils::eth::beacon::BeaconClient;
+#[cfg(feature = "beacon")]
+use crate::utils::eth::beacon::{BeaconClient, BeaconConfig};
 
 /// The universal builder for building circuits using `plonky2x`.
 pub struct CircuitBuilder<L: PlonkParameters<D>, const D: usize> {
@@ -40,16 +48,32 @@ pub struct CircuitBuilder<L: PlonkParameters<D>, const D: usize> {
     pub io: CircuitIO<D>,
     pub execution_client: Option<Provider<Http>>,
     pub chain_id: Option<u64>,
+    #[cfg(feature = "beacon")]
     pub beacon_client: Option<BeaconClient>,
+    /// A second beacon client that must agree with the first on the critical beacon leaves.
+    #[cfg(feature = "beacon")]
+    pub beacon_agreement_client: Option<BeaconClient>,
+    /// Set through [`CircuitBuilder::set_beacon_config`], which validates it.
+    #[cfg(feature = "beacon")]
+    pub(crate) beacon_config: BeaconConfig,
     pub debug: bool,
     pub debug_variables: HashMap<usize, String>,
     pub(crate) hints: Vec<Box<dyn HintGenerator<L, D>>>,
     pub(crate) async_hints: Vec<AsyncHintDataRef<L, D>>,
     pub(crate) async_hints_indices: Vec<usize>,
+    pub(crate) range_checks: RangeChecks,
+    pub(crate) input_labels: Vec<IOLabel>,
+    pub(crate) output_labels: Vec<IOLabel>,
+    pub(crate) accelerator_rows: AcceleratorRowsCache,
+    /// The ids of the generators that fetch their values from the network.
+    pub(crate) network_generator_ids: BTreeSet<String>,
+    /// Set through [`CircuitBuilder::set_mapreduce_proof_dir`].
+    pub(crate) mapreduce_proof_dir: Option<PathBuf>,
 
     pub blake2b_accelerator: Option<BLAKE2BAccelerator>,
     pub sha256_accelerator: Option<SHA256Accelerator>,
     pub sha512_accelerator: Option<SHA512Accelerator>,
+    #[cfg(feature = "ecc")]
     pub ec_25519_ops_accelerator: Option<EcOpAccelerator>,
 }
 
@@ -68,10 +92,15 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     pub fn new() -> Self {
         let config = CircuitConfig::standard_recursion_config();
         let api = CircuitAPI::new(config);
-        let mut builder = Self {
+        Self {
             api,
             io: CircuitIO::new(),
-            beacon_client: None,
+            #[cfg(feature = "beacon")]
+            beacon_client: env::var("CONSENSUS_RPC_URL").ok().map(BeaconClient::new),
+            #[cfg(feature = "beacon")]
+            beacon_agreement_client: None,
+            #[cfg(feature = "beacon")]
+            beacon_config: BeaconConfig::default(),
             execution_client: None,
             chain_id: None,
             debug: false,
@@ -79,18 +108,18 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             hints: Vec::new(),
             async_hints: Vec::new(),
             async_hints_indices: Vec::new(),
+            range_checks: RangeChecks::default(),
+            input_labels: Vec::new(),
+            output_labels: Vec::new(),
+            accelerator_rows: AcceleratorRowsCache::default(),
+            network_generator_ids: BTreeSet::new(),
+            mapreduce_proof_dir: None,
             blake2b_accelerator: None,
             sha256_accelerator: None,
             sha512_accelerator: None,
+            #[cfg(feature = "ecc")]
             ec_25519_ops_accelerator: None,
-        };
-
-        if let Ok(rpc_url) = env::var("CONSENSUS_RPC_URL") {
-            let client = BeaconClient::new(rpc_url);
-            builder.set_beacon_client(client);
         }
-
-        builder
     }
 
     pub fn set_debug(&mut self) {
@@ -127,10 +156,36 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.chain_id.unwrap()
     }
 
+    #[cfg(feature = "beacon")]
     pub fn set_beacon_client(&mut self, client: BeaconClient) {
         self.beacon_client = Some(client);
     }
 
+    /// Sets the beacon client along with a second, independent one.
+    ///
+    /// With `require_agreement`, the leaves that beacon proofs hang off (the state root and the
+    /// validators root) are fetched from both nodes during witness generation, which fails with a
+    /// consensus mismatch if they disagree. Otherwise the second client is not used.
+    ///
+    /// The clients are not serialized with the circuit. A circuit read back from disk that
+    /// requires agreement connects to `CONSENSUS_AGREEMENT_RPC_URL` for its second node.
+    #[cfg(feature = "beacon")]
+    pub fn set_beacon_clients(
+        &mut self,
+        primary: BeaconClient,
+        secondary: BeaconClient,
+        require_agreement: bool,
+    ) {
+        self.beacon_client = Some(primary);
+        self.beacon_agreement_client = require_agreement.then_some(secondary);
+    }
+
+    /// Sets the chain parameters of the beacon gadgets, which default to mainnet.
+    #[cfg(feature = "beacon")]
+    pub fn set_beacon_config(&mut self, config: BeaconConfig) {
+        self.beacon_config = config;
+    }
+
     /// Adds all the constraints nedded before building the circuit and registering hints.
     fn pre_build(&mut self) {
         let blake2b_accelerator = self.blake2b_accelerator.clone();
@@ -148,11 +203,13 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             self.curta_constrain_hash::<SHA512, 80, false, 8>(accelerator);
         }
 
-        let ec_ops_accelerator = self.ec_25519_ops_accelerator.clone();
-        if let Some(accelerator) = ec_ops_accelerator {
+        #[cfg(feature = "ecc")]
+        if let Some(accelerator) = self.ec_25519_ops_accelerator.clone() {
             self.curta_constrain_ec_op(accelerator);
         }
 
+        self.constrain_range_checks();
+
         for (index, gen_ref) in self
             .async_hints_indices
             .iter()
@@ -242,12 +299,17 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// Build the circuit.
     pub fn build(mut self) -> CircuitBuild<L, D> {
         self.pre_build();
+        let num_gates = self.api.num_gates();
         let data = self.api.build();
         let async_hints = Self::async_hint_map(&data.prover_only.generators, self.async_hints);
         CircuitBuild {
             data,
             io: self.io,
             async_hints,
+            num_gates,
+            input_labels: self.input_labels,
+            output_labels: self.output_labels,
+            network_generator_ids: self.network_generator_ids,
         }
     }
 
@@ -255,6 +317,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// common_data, if will still return the data and success as false.
     pub fn try_build(mut self) -> (CircuitBuild<L, D>, bool) {
         self.pre_build();
+        let num_gates = self.api.num_gates();
         let (data, success) = self.api.try_build_with_options(true);
         let async_hints = Self::async_hint_map(&data.prover_only.generators, self.async_hints);
         (
@@ -262,6 +325,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                 data,
                 io: self.io,
                 async_hints,
+                num_gates,
+                input_labels: self.input_labels,
+                output_labels: self.output_labels,
+                network_generator_ids: self.network_generator_ids,
             },
             success,
         )
@@ -280,11 +347,32 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 
+    /// The number of gates added to the circuit so far.
+    ///
+    /// Useful for attributing gate costs to gadgets while the circuit is being built. After the
+    /// circuit is built, the same count is reported by [`CircuitBuild::stats`].
+    pub fn num_gates(&self) -> usize {
+        self.api.num_gates()
+    }
+
     /// Add simple generator.
     pub fn add_simple_generator<G: SimpleGenerator<L::Field, D> + Clone>(&mut self, generator: G) {
         self.api.add_simple_generator(generator)
     }
 
+    /// Add a simple generator that fetches its values from the network.
+    ///
+    /// The values set by the generator are captured when preparing the inputs of a proof, so that
+    /// the proof can later be generated without network access.
+    pub fn add_network_simple_generator<G: SimpleGenerator<L::Field, D> + Clone>(
+        &mut self,
+        generator: G,
+    ) {
+        self.network_generator_ids
+            .insert(SimpleGenerator::<L::Field, D>::id(&generator));
+        self.api.add_simple_generator(generator)
+    }
+
     /// Initializes a variable with no value in the circuit.
     pub fn init<V: CircuitVariable>(&mut self) -> V {
         V::init(self)
@@ -306,6 +394,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 
     /// Initializes a variable with a constant value in the circuit.
+    ///
+    /// Constants are interned per field element, so declaring the same constant more than once,
+    /// such as a fixed block root in many merkle branches, reuses the same targets.
     pub fn constant<V: CircuitVariable>(&mut self, value: V::ValueType<L::Field>) -> V {
         V::constant(self, value)
     }
@@ -357,6 +448,21 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         V::from_targets(&targets)
     }
 
+    /// If condition is true, yields the outputs of `then_branch` else the outputs of `else_branch`.
+    ///
+    /// Both branches are built into the circuit, so any constraints they add must hold regardless
+    /// of the condition. Returning a tuple selects several outputs together.
+    pub fn if_then_else<V: CircuitVariable>(
+        &mut self,
+        condition: BoolVariable,
+        then_branch: impl FnOnce(&mut Self) -> V,
+        else_branch: impl FnOnce(&mut Self) -> V,
+    ) -> V {
+        let then_value = then_branch(self);
+        let else_value = else_branch(self);
+        self.select(condition, then_value, else_value)
+    }
+
     /// Returns 1 if i1 is zero, 0 otherwise as a boolean.
     #[must_use]
     pub fn is_zero(&mut self, i1: Variable) -> BoolVariable {
@@ -384,6 +490,36 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
     // @end-audit
 
+    /// Returns 1 if `needle` is equal to at least one member of `set` and 0 otherwise.
+    #[must_use]
+    pub fn is_in_set<V: CircuitVariable>(&mut self, needle: V, set: &[V]) -> BoolVariable {
+        let mut result = self._false();
+        for member in set.iter() {
+            let is_member = self.is_equal(needle.clone(), member.clone());
+            result = self.or(result, is_member);
+        }
+        result
+    }
+
+    /// Fails if `needle` is not equal to any member of `set`.
+    pub fn assert_in_set<V: CircuitVariable>(&mut self, needle: V, set: &[V]) {
+        assert!(!set.is_empty(), "cannot assert membership in an empty set");
+        let needle_targets = needle.targets();
+        if needle_targets.len() == 1 {
+            // For a single target, the product of the differences is zero iff needle is a member.
+            let mut product = self.api.one();
+            for member in set.iter() {
+                let diff = self.api.sub(needle_targets[0], member.targets()[0]);
+                product = self.api.mul(product, diff);
+            }
+            self.api.assert_zero(product);
+        } else {
+            let is_member = self.is_in_set(needle, set);
+            let true_v = self._true();
+            self.assert_is_equal(is_member, true_v);
+        }
+    }
+
     /// Connects two variables.
     pub fn connect<V: CircuitVariable>(&mut self, i1: V, i2: V) {
         let i1 = i1.targets();
@@ -413,8 +549,10 @@ pub(crate) mod tests {
 
     use log::debug;
 
+    use crate::frontend::eth::vars::BLSPubkeyVariable;
     use crate::prelude::*;
     use crate::utils;
+    use crate::utils::bytes32;
 
     #[test]
     fn test_simple_circuit_with_field_io() {
@@ -473,4 +611,116 @@ pub(crate) mod tests {
         let xor = output.evm_read::<ByteVariable>();
         debug!("{}", xor);
     }
+
+    #[test]
+    fn test_constant_interned() {
+        let root = bytes32!("0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670");
+        let stats = |declarations: usize| {
+            let mut builder = DefaultBuilder::new();
+            let input = builder.read::<Bytes32Variable>();
+            let first = builder.constant::<Bytes32Variable>(root);
+            for _ in 0..declarations {
+                let constant = builder.constant::<Bytes32Variable>(root);
+                assert_eq!(constant.targets(), first.targets());
+                builder.assert_is_equal(input, constant);
+            }
+            builder.build().stats()
+        };
+        assert_eq!(stats(1), stats(16));
+    }
+
+    #[test]
+    fn test_num_gates_sha256() {
+        let mut builder = DefaultBuilder::new();
+        let data = builder.read::<BytesVariable<32>>();
+
+        let start = builder.num_gates();
+        let digest = builder.sha256(&data.0);
+        let sha256_gates = builder.num_gates() - start;
+        builder.write(digest);
+        debug!("sha256 of one block added {} gates", sha256_gates);
+
+        // A single compression of bit-decomposed 32-bit words costs thousands of gates.
+        assert!((1_000..50_000).contains(&sha256_gates));
+    }
+
+    fn committee() -> Vec<[u8; 48]> {
+        (0..4u8)
+            .map(|i| {
+                let mut pubkey = [0u8; 48];
+                pubkey[0] = 0x80;
+                pubkey[47] = i;
+                pubkey
+            })
+            .collect()
+    }
+
+    fn prove_pubkey_in_committee(pubkey: [u8; 48]) -> bool {
+        let mut builder = DefaultBuilder::new();
+        let needle = builder.read::<BLSPubkeyVariable>();
+        let set = committee()
+            .into_iter()
+            .map(|pubkey| builder.constant::<BLSPubkeyVariable>(pubkey))
+            .collect::<Vec<_>>();
+        let is_member = builder.is_in_set(needle, &set);
+        builder.write(is_member);
+        builder.assert_in_set(needle, &set);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BLSPubkeyVariable>(pubkey);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        output.read::<BoolVariable>()
+    }
+
+    #[test]
+    fn test_assert_in_set() {
+        assert!(prove_pubkey_in_committee(committee()[2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_in_set_absent() {
+        let mut pubkey = committee()[2];
+        pubkey[46] = 1;
+        prove_pubkey_in_committee(pubkey);
+    }
+
+    #[test]
+    fn test_is_in_set_absent() {
+        let mut builder = DefaultBuilder::new();
+        let needle = builder.read::<BLSPubkeyVariable>();
+        let set = committee()
+            .into_iter()
+            .map(|pubkey| builder.constant::<BLSPubkeyVariable>(pubkey))
+            .collect::<Vec<_>>();
+        let is_member = builder.is_in_set(needle, &set);
+        builder.write(is_member);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BLSPubkeyVariable>([0xffu8; 48]);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert!(!output.read::<BoolVariable>());
+    }
+
+    #[test]
+    fn test_assert_in_set_single_target() {
+        let mut builder = DefaultBuilder::new();
+        let needle = builder.read::<Variable>();
+        let set = builder.constant_vec::<Variable>(&[
+            GoldilocksField::from_canonical_u64(3),
+            GoldilocksField::from_canonical_u64(5),
+            GoldilocksField::from_canonical_u64(7),
+        ]);
+        builder.assert_in_set(needle, &set);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(5));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
 }
@@ -25,6 +25,34 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             .verify_proof::<P::Config>(proof_with_pis, inner_verifier_data, inner_common_data);
     }
 
+    /// Asserts that the public input at `index_a` of proof `a` equals the public input at
+    /// `index_b` of proof `b`.
+    ///
+    /// This is useful to make sibling proofs of an aggregation tree agree on a shared value, such as
+    /// the block root that they were both computed against.
+    pub fn assert_proofs_share_input(
+        &mut self,
+        a: &ProofWithPublicInputsTarget<D>,
+        b: &ProofWithPublicInputsTarget<D>,
+        index_a: usize,
+        index_b: usize,
+    ) {
+        assert!(
+            index_a < a.public_inputs.len(),
+            "proof has {} public inputs, index {} is out of range",
+            a.public_inputs.len(),
+            index_a
+        );
+        assert!(
+            index_b < b.public_inputs.len(),
+            "proof has {} public inputs, index {} is out of range",
+            b.public_inputs.len(),
+            index_b
+        );
+        self.api
+            .connect(a.public_inputs[index_a], b.public_inputs[index_b]);
+    }
+
     // @ audit
     pub fn constant_verifier_data<P: PlonkParameters<D, Field = L::Field>>(
         &mut self,
@@ -57,3 +85,85 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         vd
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Field;
+    use plonky2::plonk::config::PoseidonGoldilocksConfig;
+    use plonky2::plonk::proof::ProofWithPublicInputs;
+
+    use crate::backend::circuit::{CircuitBuild, DefaultParameters};
+    use crate::frontend::builder::CircuitBuilder;
+    use crate::frontend::vars::Variable;
+    use crate::utils::test_utils::assert_constraint_failure;
+
+    type L = DefaultParameters;
+    type F = GoldilocksField;
+    type C = PoseidonGoldilocksConfig;
+    const D: usize = 2;
+
+    /// A child circuit with a shared input and an output that depends on it.
+    fn child_circuit() -> CircuitBuild<L, D> {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let shared = builder.read::<Variable>();
+        let value = builder.read::<Variable>();
+        let output = builder.mul(shared, value);
+        builder.write(output);
+        builder.build()
+    }
+
+    /// A parent circuit that verifies two child proofs and joins them on their shared input.
+    fn join_children(builder: &mut CircuitBuilder<L, D>, child: &CircuitBuild<L, D>) {
+        let verifier_data = builder.constant_verifier_data::<L>(&child.data);
+        let a = builder.proof_read(&child.data.common);
+        builder.verify_proof::<L>(&a, &verifier_data, &child.data.common);
+        let b = builder.proof_read(&child.data.common);
+        builder.verify_proof::<L>(&b, &verifier_data, &child.data.common);
+        builder.assert_proofs_share_input(&a, &b, 0, 0);
+        builder.proof_write(Variable(a.public_inputs[0]));
+    }
+
+    fn prove_child(
+        child: &CircuitBuild<L, D>,
+        shared: u64,
+        value: u64,
+    ) -> ProofWithPublicInputs<F, C, D> {
+        let mut input = child.input();
+        input.write::<Variable>(F::from_canonical_u64(shared));
+        input.write::<Variable>(F::from_canonical_u64(value));
+        child.prove(&input).0
+    }
+
+    #[test]
+    fn test_assert_proofs_share_input() {
+        let child = child_circuit();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        join_children(&mut builder, &child);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.proof_write(prove_child(&child, 7, 2));
+        input.proof_write(prove_child(&child, 7, 3));
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.proof_read::<Variable>(), F::from_canonical_u64(7));
+    }
+
+    #[test]
+    fn test_assert_proofs_share_input_mismatch() {
+        let child = child_circuit();
+        let a = prove_child(&child, 7, 2);
+        let b = prove_child(&child, 8, 3);
+
+        assert_constraint_failure::<L, D>(
+            |builder| join_children(builder, &child),
+            |input| {
+                input.proof_write(a);
+                input.proof_write(b);
+            },
+            None,
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use plonky2::iop::target::Target;
+use plonky2::plonk::circuit_data::CircuitConfig;
+
+use super::CircuitBuilder;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::vars::Variable;
+
+/// Values that have been queued for a range check.
+///
+/// Instead of decomposing every value into bits, range checks are accumulated while the circuit
+/// is being defined and discharged at build time as lookups into a single table per range. A table
+/// is only added to the circuit if at least one value was checked against it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RangeChecks {
+    u8_targets: Vec<Target>,
+    u16_targets: Vec<Target>,
+}
+
+impl RangeChecks {
+    /// Estimates the number of rows used by the lookups and tables once the checks are discharged.
+    pub(crate) fn estimate_rows(&self, config: &CircuitConfig) -> usize {
+        let lookups_per_row = config.num_routed_wires / 2;
+        let table_entries_per_row = config.num_routed_wires / 3;
+        let table_rows = |num_lookups: usize, table_len: usize| {
+            if num_lookups == 0 {
+                0
+            } else {
+                num_lookups.div_ceil(lookups_per_row)
+                    + table_len.div_ceil(table_entries_per_row)
+                    + 1
+            }
+        };
+        table_rows(self.u8_targets.len(), 1 << 8) + table_rows(self.u16_targets.len(), 1 << 16)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Asserts that `value` is in the range `[0, 2^8)`.
+    ///
+    /// The check is batched with all other `u8` range checks into a single lookup argument that is
+    /// added when the circuit is built.
+    pub fn range_check_u8(&mut self, value: Variable) {
+        self.range_checks.u8_targets.push(value.0);
+    }
+
+    /// Asserts that `value` is in the range `[0, 2^16)`.
+    ///
+    /// The check is batched with all other `u16` range checks into a single lookup argument that is
+    /// added when the circuit is built.
+    pub fn range_check_u16(&mut self, value: Variable) {
+        self.range_checks.u16_targets.push(value.0);
+    }
+
+    /// Discharges all the queued range checks as lookups.
+    pub(crate) fn constrain_range_checks(&mut self) {
+        let range_checks = std::mem::take(&mut self.range_checks);
+
+        if !range_checks.u8_targets.is_empty() {
+            let table = Arc::new((0..1u16 << 8).map(|i| (i, i)).collect::<Vec<_>>());
+            let lut_index = self.api.add_lookup_table_from_pairs(table);
+            for target in range_checks.u8_targets {
+                self.api.add_lookup_from_index(target, lut_index);
+            }
+        }
+
+        if !range_checks.u16_targets.is_empty() {
+            let table = Arc::new((0..=u16::MAX).map(|i| (i, i)).collect::<Vec<_>>());
+            let lut_index = self.api.add_lookup_table_from_pairs(table);
+            for target in range_checks.u16_targets {
+                self.api.add_lookup_from_index(target, lut_index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    fn prove_range_checks(u8_values: &[u64], u16_values: &[u64]) {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let u8_variables = u8_values
+            .iter()
+            .map(|_| {
+                let v = builder.init_unsafe::<Variable>();
+                builder.range_check_u8(v);
+                v
+            })
+            .collect::<Vec<_>>();
+        let u16_variables = u16_values
+            .iter()
+            .map(|_| {
+                let v = builder.init_unsafe::<Variable>();
+                builder.range_check_u16(v);
+                v
+            })
+            .collect::<Vec<_>>();
+        let circuit = builder.build();
+
+        let mut pw = PartialWitness::new();
+        for (v, value) in u8_variables.iter().zip(u8_values) {
+            pw.set_target(v.0, GoldilocksField::from_canonical_u64(*value));
+        }
+        for (v, value) in u16_variables.iter().zip(u16_values) {
+            pw.set_target(v.0, GoldilocksField::from_canonical_u64(*value));
+        }
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    fn test_range_check_u8() {
+        prove_range_checks(&[0, 1, 127, 255], &[]);
+    }
+
+    #[test]
+    fn test_range_check_u16() {
+        prove_range_checks(&[], &[0, 256, 4096, 65535]);
+    }
+
+    #[test]
+    fn test_range_check_mixed() {
+        prove_range_checks(&[0, 255], &[255, 65535]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_check_u8_out_of_range() {
+        prove_range_checks(&[255, 256], &[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_check_u16_out_of_range() {
+        prove_range_checks(&[], &[65535, 65536]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_u32_variable_out_of_range() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let v = builder.init::<U32Variable>();
+        let circuit = builder.build();
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(v.variable.0, GoldilocksField::from_canonical_u64(1 << 32));
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+}
@@ -1,9 +1,11 @@
+use anyhow::Result;
 pub use curve25519_dalek::edwards::CompressedEdwardsY;
 use starkyx::chip::ec::point::{AffinePoint, AffinePointRegister};
 use starkyx::chip::ec::EllipticCurve;
 use starkyx::chip::register::Register;
 
 use crate::frontend::curta::field::variable::FieldVariable;
+use crate::frontend::vars::ensure_nb_value_bytes;
 use crate::prelude::*;
 
 #[derive(Debug, Clone)]
@@ -67,6 +69,27 @@ impl<E: EllipticCurve> CircuitVariable for AffinePointVariable<E> {
     }
 }
 
+/// Encoded as the concatenation of the encodings of its coordinates.
+impl<E: EllipticCurve> ValueSerializable for AffinePointVariable<E> {
+    fn nb_value_bytes() -> usize {
+        FieldVariable::<E::BaseField>::nb_value_bytes() * 2
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        let mut bytes = FieldVariable::<E::BaseField>::to_bytes::<F>(value.x)?;
+        bytes.extend(FieldVariable::<E::BaseField>::to_bytes::<F>(value.y)?);
+        Ok(bytes)
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        let (x, y) = bytes.split_at(bytes.len() / 2);
+        let x = FieldVariable::<E::BaseField>::from_bytes::<F>(x)?;
+        let y = FieldVariable::<E::BaseField>::from_bytes::<F>(y)?;
+        Ok(AffinePoint::new(x, y))
+    }
+}
+
 impl<E: EllipticCurve> AffinePointVariable<E> {
     pub fn read_from_stark(register: &AffinePointRegister<E>, public_inputs: &[Variable]) -> Self {
         AffinePointVariable {
@@ -117,3 +140,19 @@ impl CircuitVariable for CompressedEdwardsYVariable {
         Self(Bytes32Variable::from_variables_unsafe(variables))
     }
 }
+
+/// Encoded as the raw 32 bytes.
+impl ValueSerializable for CompressedEdwardsYVariable {
+    fn nb_value_bytes() -> usize {
+        32
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(value.as_bytes().to_vec())
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        Ok(CompressedEdwardsY(bytes.try_into().unwrap()))
+    }
+}
@@ -1,5 +1,6 @@
 use core::marker::PhantomData;
 
+use anyhow::{ensure, Result};
 use itertools::Itertools;
 use num::One;
 use num_bigint::BigUint;
@@ -9,6 +10,7 @@ use starkyx::math::prelude::PrimeField64;
 use starkyx::polynomial::to_u16_le_limbs_polynomial;
 
 use crate::frontend::uint::num::u32::gadgets::multiple_comparison::list_lte_circuit;
+use crate::frontend::vars::ensure_nb_value_bytes;
 use crate::prelude::*;
 
 #[derive(Debug, Clone)]
@@ -44,7 +46,7 @@ impl<P: FieldParameters> CircuitVariable for FieldVariable<P> {
 
         // Check that each limb is within u16.
         for limb in self.limbs.iter() {
-            builder.api.range_check(limb.0, 16);
+            builder.range_check_u16(*limb);
         }
 
         // Check that the value is less than the modulus.
@@ -110,6 +112,28 @@ impl<P: FieldParameters> CircuitVariable for FieldVariable<P> {
     }
 }
 
+/// Encoded as the big-endian bytes of the field element, left padded to the byte width of its
+/// limbs.
+impl<P: FieldParameters> ValueSerializable for FieldVariable<P> {
+    fn nb_value_bytes() -> usize {
+        P::NB_LIMBS * P::NB_BITS_PER_LIMB / 8
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        let be_bytes = value.to_bytes_be();
+        let mut bytes = vec![0u8; Self::nb_value_bytes() - be_bytes.len()];
+        bytes.extend(be_bytes);
+        Ok(bytes)
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        let value = BigUint::from_bytes_be(bytes);
+        ensure!(value < P::modulus(), "value exceeds the field modulus");
+        Ok(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use num::{One, Zero};
@@ -1,4 +1,5 @@
 pub mod builder;
+#[cfg(feature = "ecc")]
 pub mod ec;
 pub mod field;
 pub mod proof;
@@ -1,40 +1,64 @@
-use array_macro::array;
 use ethers::types::{H256, U256};
+use plonky2::iop::target::BoolTarget;
 
 use super::generators::{
     BeaconAllWithdrawalsHint, BeaconBalanceBatchWitnessHint, BeaconBalanceGenerator,
-    BeaconBalanceWitnessHint, BeaconBalancesGenerator, BeaconBlockRootsHint, BeaconGraffitiHint,
-    BeaconHeaderHint, BeaconHeadersFromOffsetRangeHint, BeaconHistoricalBlockHint,
-    BeaconPartialBalancesHint, BeaconPartialValidatorsHint, BeaconValidatorBatchHint,
-    BeaconValidatorGenerator, BeaconValidatorSubtreeHint, BeaconValidatorSubtreePoseidonHint,
-    BeaconValidatorSubtreesHint, BeaconValidatorsHint, BeaconWithdrawalGenerator,
-    BeaconWithdrawalsGenerator, CompressedBeaconValidatorBatchHint, CLOSE_SLOT_BLOCK_ROOT_DEPTH,
-    FAR_SLOT_BLOCK_ROOT_DEPTH, FAR_SLOT_HISTORICAL_SUMMARY_DEPTH,
+    BeaconBalanceWitnessHint, BeaconBalancesGenerator, BeaconBlockRootHint, BeaconBlockRootsHint,
+    BeaconExecutionPayloadHeaderHint, BeaconGraffitiHint, BeaconHeaderHint,
+    BeaconHeadersFromOffsetRangeHint, BeaconHistoricalBlockHint, BeaconHistoricalSummaryHint,
+    BeaconNodes, BeaconPartialBalancesHint, BeaconPartialValidatorsHint, BeaconRandaoMixHint,
+    BeaconValidatorBatchHint, BeaconValidatorGenerator, BeaconValidatorSubtreeHint,
+    BeaconValidatorSubtreePoseidonHint, BeaconValidatorSubtreesHint,
+    BeaconValidatorsAndBalancesHint, BeaconValidatorsHint, BeaconWithdrawalGenerator,
+    BeaconWithdrawalsGenerator, CompressedBeaconValidatorBatchHint, EXECUTION_PAYLOAD_PROOF_DEPTH,
+    VALIDATORS_AND_BALANCES_BRANCH_DEPTH,
 };
+use super::state::BeaconState;
 use super::vars::{
     BeaconBalancesVariable, BeaconHeaderVariable, BeaconValidatorVariable,
     BeaconValidatorsVariable, BeaconWithdrawalVariable, BeaconWithdrawalsVariable,
-    CompressedBeaconValidatorVariable,
+    CompressedBeaconValidatorVariable, ExecutionPayloadHeaderVariable, GweiVariable,
+    HistoricalSummaryVariable,
 };
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
-use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::eth::vars::{AddressVariable, BLSPubkeyVariable};
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{
-    Bytes32Variable, CircuitVariable, EvmVariable, SSZVariable, VariableStream,
+    Bytes32Variable, CircuitVariable, EvmVariable, SSZVariable, Ssz, VariableStream,
 };
-use crate::prelude::{ArrayVariable, BoolVariable, ByteVariable, BytesVariable, U256Variable};
+use crate::prelude::{
+    ArrayVariable, BoolVariable, ByteVariable, BytesVariable, U256Variable, U32Variable, Variable,
+};
+use crate::utils::eth::beacon::{BeaconFork, BeaconStateId};
 use crate::utils::eth::concat_g_indices;
 
 /// The gindex for blockRoot -> validatorsRoot.
 const VALIDATORS_ROOT_GINDEX: u64 = 363;
 
-/// The gindex for blockRoot -> stateRoot.
-const STATE_ROOT_GINDEX: u64 = 11;
+/// The gindex for blockRoot -> validatorsRoot from Electra on, where the state has more than 32
+/// fields.
+const ELECTRA_VALIDATORS_ROOT_GINDEX: u64 = 715;
+
+/// The depth of the proof from blockRoot -> validatorsRoot.
+const VALIDATORS_ROOT_PROOF_DEPTH: usize = 8;
+
+/// The index of stateRoot among the fields of a block.
+const STATE_ROOT_FIELD: usize = 3;
 
 /// The gindex for blockRoot -> balancesRoot.
 const BALANCES_ROOT_GINDEX: u64 = 364;
 
+/// The gindex for blockRoot -> balancesRoot from Electra on.
+const ELECTRA_BALANCES_ROOT_GINDEX: u64 = 716;
+
+/// The gindex for blockRoot -> the common ancestor of validatorsRoot and balancesRoot.
+const VALIDATORS_AND_BALANCES_GINDEX: u64 = 45;
+
+/// The gindex for blockRoot -> the common ancestor of validatorsRoot and balancesRoot from
+/// Electra on.
+const ELECTRA_VALIDATORS_AND_BALANCES_GINDEX: u64 = 89;
+
 /// The gindex for blockRoot -> withdrawalsRoot.
 const WITHDRAWALS_ROOT_GINDEX: u64 = 3230;
 
@@ -47,96 +71,267 @@ const BALANCE_BASE_GINDEX: u64 = 549755813888;
 /// The gindex for withdrawalsRoot -> withdrawals[i].
 const WITHDRAWAL_BASE_GINDEX: u64 = 32;
 
-/// The gindex for blockRoot -> state -> state.historicalSummaries[0].
-const HISTORICAL_SUMMARIES_BASE_GINDEX: u64 = 12717129728;
+/// The index of historicalSummaries among the fields of the state.
+const HISTORICAL_SUMMARIES_FIELD: u64 = 27;
 
-/// The gindex for state.historicalSummaries[i] -> block_summary/block_roots -> block_roots[0].
-const HISTORICAL_SUMMARY_BLOCK_ROOT_GINDEX: u64 = 16384;
+/// The gindex for state.historicalSummaries[i] -> block_summary/block_roots.
+const HISTORICAL_SUMMARY_BLOCK_SUMMARY_ROOT_GINDEX: u64 = 2;
 
-/// The gindex for blockRoot -> state -> state.block_roots[0].
-const CLOSE_SLOT_BLOCK_ROOT_GINDEX: u64 = 2924544;
+/// The log2 of HISTORICAL_ROOTS_LIMIT, the maximum length of state.historical_summaries.
+const HISTORICAL_ROOTS_LIMIT_LOG2: usize = 24;
 
-/// The log2 of the validator registry limit.
-const VALIDATOR_REGISTRY_LIMIT_LOG2: usize = 40;
+/// The index of randaoMixes among the fields of the state.
+const RANDAO_MIXES_FIELD: u64 = 13;
 
-/// The depth of the proof from the blockRoot -> balancesRoot.
-const BALANCES_PROOF_DEPTH: usize = 8;
+/// The prefix of withdrawal credentials that point to an execution layer address.
+const ETH1_ADDRESS_WITHDRAWAL_CREDENTIALS_PREFIX: [u8; 12] = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
 
-/// The depth of the proof from the blockRoot -> blockRoots.
-const BLOCK_ROOTS_PROOF_DEPTH: usize = 8;
+/// The maximum effective balance of a validator, in Gwei.
+const MAX_EFFECTIVE_BALANCE: u64 = 32_000_000_000;
+
+/// The epoch used by the consensus specs for epochs that are not yet set.
+const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+
+/// The log2 of the validator registry limit.
+const VALIDATOR_REGISTRY_LIMIT_LOG2: usize = 40;
 
 /// The depth of the proof from blockRoot -> graffiti.
 const GRAFFITI_PROOF_DEPTH: usize = 7;
 
-/// The gindex for stateRoot -> validators;
-const VALIDATORS_GINDEX: usize = 43;
+/// The index of validators among the fields of the state.
+const VALIDATORS_FIELD: u64 = 11;
 
-/// The gindex for stateRoot -> balances;
-const BALANCES_GINDEX: usize = 44;
+/// The index of balances among the fields of the state.
+const BALANCES_FIELD: u64 = 12;
 
-/// The gindex for blockRoot -> blockRoots.
-const BLOCK_ROOTS_GINDEX: usize = 357;
+/// The index of blockRoots among the fields of the state.
+const BLOCK_ROOTS_FIELD: u64 = 5;
 
 /// The gindex for blockRoot -> graffiti.
 const GRAFFITI_GINDEX: usize = 194;
 
-/// Beacon chain constant SLOTS_PER_EPOCH.
-const SLOTS_PER_EPOCH: u64 = 32;
-
-/// Beacon chain constant SLOTS_PER_HISTORICAL_ROOT.
-const SLOTS_PER_HISTORICAL_ROOT: usize = 8192;
-
-/// Beacon chain constant CAPELLA_FORK_EPOCH (mainnet specific).
-const CAPELLA_FORK_EPOCH: u64 = 194048;
+/// The gindex for blockRoot -> body -> executionPayload.
+const EXECUTION_PAYLOAD_GINDEX: u64 = 201;
 
 /// Beacon chain constant MAX_WITHDRAWALS_PER_PAYLOAD.
 const MAX_WITHDRAWALS_PER_PAYLOAD: usize = 16;
 
+/// The block that a beacon accessor is queried at: either a block root or a state id, which is
+/// resolved with [`CircuitBuilder::beacon_get_block_root`].
+pub trait BeaconBlockId {
+    fn block_root<L: PlonkParameters<D>, const D: usize>(
+        self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable;
+}
+
+impl BeaconBlockId for Bytes32Variable {
+    fn block_root<L: PlonkParameters<D>, const D: usize>(
+        self,
+        _: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        self
+    }
+}
+
+impl BeaconBlockId for BeaconStateId {
+    fn block_root<L: PlonkParameters<D>, const D: usize>(
+        self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        builder.beacon_get_block_root(self)
+    }
+}
+
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Get the epoch of a slot under the builder's `BeaconConfig`.
+    pub fn beacon_slot_to_epoch(&mut self, slot: U64Variable) -> U64Variable {
+        let slots_per_epoch = self.constant::<U64Variable>(self.beacon_config.slots_per_epoch);
+        self.div(slot, slots_per_epoch)
+    }
+
+    /// Get the first slot of an epoch under the builder's `BeaconConfig`.
+    pub fn beacon_epoch_to_slot(&mut self, epoch: U64Variable) -> U64Variable {
+        let slots_per_epoch = self.constant::<U64Variable>(self.beacon_config.slots_per_epoch);
+        self.mul(epoch, slots_per_epoch)
+    }
+
+    /// Get the unix timestamp at the start of a slot under the builder's `BeaconConfig`.
+    pub fn beacon_slot_to_timestamp(&mut self, slot: U64Variable) -> U64Variable {
+        let seconds_per_slot = self.constant::<U64Variable>(self.beacon_config.seconds_per_slot);
+        let genesis_time = self.constant::<U64Variable>(self.beacon_config.genesis_time);
+        let seconds_since_genesis = self.mul(slot, seconds_per_slot);
+        self.add(genesis_time, seconds_since_genesis)
+    }
+
+    /// Get the gindex for blockRoot -> state -> the field of the state at index `field`, in the
+    /// state tree of the builder's `BeaconConfig::fork`. The depth of the proof of the field is the
+    /// log2 of its gindex.
+    fn beacon_state_field_gindex(&self, field: u64) -> u64 {
+        let state_depth = self.beacon_config.fork.state_depth();
+        concat_g_indices(&[
+            BeaconHeaderVariable::field_gindex(STATE_ROOT_FIELD) as usize,
+            (1 << state_depth) + field as usize,
+        ]) as u64
+    }
+
+    /// Get the gindex for blockRoot -> state -> state.block_roots[slot % SLOTS_PER_HISTORICAL_ROOT].
+    pub fn beacon_block_roots_gindex(&mut self, slot: U64Variable) -> U64Variable {
+        let slots_per_historical_root = self.beacon_config.slots_per_historical_root;
+        let block_roots_gindex = self.beacon_state_field_gindex(BLOCK_ROOTS_FIELD);
+        let base = self
+            .constant::<U64Variable>(block_roots_gindex << self.beacon_config.block_roots_depth());
+        let slots_per_historical = self.constant::<U64Variable>(slots_per_historical_root);
+        let index = self.rem(slot, slots_per_historical);
+        self.add(base, index)
+    }
+
+    /// Get the gindex for blockRoot -> state -> state.randao_mixes[epoch %
+    /// EPOCHS_PER_HISTORICAL_VECTOR].
+    pub fn beacon_randao_mixes_gindex(&mut self, epoch: U64Variable) -> U64Variable {
+        let epochs_per_historical_vector = self.beacon_config.epochs_per_historical_vector;
+        let randao_mixes_gindex = self.beacon_state_field_gindex(RANDAO_MIXES_FIELD);
+        let base = self.constant::<U64Variable>(
+            randao_mixes_gindex << self.beacon_config.randao_mixes_depth(),
+        );
+        let epochs_per_historical = self.constant::<U64Variable>(epochs_per_historical_vector);
+        let index = self.rem(epoch, epochs_per_historical);
+        self.add(base, index)
+    }
+
+    /// The nodes that the network hints fetch their responses from.
+    pub(crate) fn beacon_nodes(&self) -> BeaconNodes {
+        BeaconNodes::new(
+            self.beacon_client.clone(),
+            self.beacon_agreement_client.clone(),
+        )
+    }
+
     /// Get the first B validators for a given block root.
     pub fn beacon_get_partial_validators<const B: usize>(
         &mut self,
         block_root: Bytes32Variable,
     ) -> BeaconValidatorsVariable {
         let b_log2 = (B as f64).log2().ceil() as usize;
-        let hint = BeaconPartialValidatorsHint::<B> {};
+        let gindex = self.beacon_state_field_gindex(VALIDATORS_FIELD)
+            << (VALIDATOR_REGISTRY_LIMIT_LOG2 + 1 - b_log2);
+        let nb_branches = gindex.ilog2() as usize;
+        let hint = BeaconPartialValidatorsHint::<B> {
+            depth: nb_branches,
+            nodes: self.beacon_nodes(),
+        };
         let mut input_stream = VariableStream::new();
         input_stream.write(&block_root);
 
         let output_stream = self.async_hint(input_stream, hint);
         let partial_validators_root = output_stream.read::<Bytes32Variable>(self);
-        let nb_branches = BALANCES_PROOF_DEPTH + (VALIDATOR_REGISTRY_LIMIT_LOG2 + 1 - b_log2);
         let mut proof = Vec::new();
         for _ in 0..nb_branches {
             proof.push(output_stream.read::<Bytes32Variable>(self));
         }
 
-        let gindex = VALIDATORS_GINDEX * (2usize.pow(41 - b_log2 as u32));
-        let gindex = concat_g_indices(&[STATE_ROOT_GINDEX as usize, gindex]);
-        self.ssz_verify_proof_const(block_root, partial_validators_root, &proof, gindex as u64);
+        self.ssz_verify_proof_const(block_root, partial_validators_root, &proof, gindex);
         BeaconValidatorsVariable {
             block_root,
             validators_root: partial_validators_root,
         }
     }
 
-    /// Get the validators for a given block root.
-    pub fn beacon_get_validators(
+    /// Resolves a state id (e.g. `finalized`) into a block root.
+    ///
+    /// The root is checked to be the root of a block header, and for `BeaconStateId::Slot` the
+    /// slot of that header is checked to be the given slot. Which block is `head`, `finalized` or
+    /// `justified` cannot be proven from the root alone, so for those ids circuits should expose
+    /// the root or its slot as an output for the verifier to check.
+    pub fn beacon_get_block_root(&mut self, state_id: BeaconStateId) -> Bytes32Variable {
+        let hint = BeaconBlockRootHint {
+            state_id,
+            nodes: self.beacon_nodes(),
+        };
+        let output_stream = self.hint(VariableStream::new(), hint);
+        let block_root = output_stream.read::<Bytes32Variable>(self);
+
+        let header = self.beacon_get_block_header(block_root);
+        if let BeaconStateId::Slot(slot) = state_id {
+            let slot = self.constant::<U64Variable>(slot);
+            self.assert_is_equal(header.slot, slot);
+        }
+        block_root
+    }
+
+    /// Get the validators for a given block.
+    pub fn beacon_get_validators(&mut self, block: impl BeaconBlockId) -> BeaconValidatorsVariable {
+        let block_root = block.block_root(self);
+        self.beacon_get_validators_with_length(block_root).0
+    }
+
+    /// Get the number of validators for a given block.
+    pub fn beacon_get_validator_count(&mut self, block: impl BeaconBlockId) -> U64Variable {
+        let block_root = block.block_root(self);
+        self.beacon_get_validators_with_length(block_root).1
+    }
+
+    /// Verifies a proof of `validators_root` against `block_root`, without fetching anything.
+    ///
+    /// The fork is inferred from the length of the branch: a branch of depth 8 is checked at the
+    /// gindex of the validators root up to Deneb, and a branch of depth 9 at its gindex from
+    /// Electra on.
+    pub fn beacon_verify_validators_root(
         &mut self,
         block_root: Bytes32Variable,
-    ) -> BeaconValidatorsVariable {
+        validators_root: Bytes32Variable,
+        branch: &[Bytes32Variable],
+    ) {
+        let gindex = match branch.len() {
+            VALIDATORS_ROOT_PROOF_DEPTH => VALIDATORS_ROOT_GINDEX,
+            depth if depth == VALIDATORS_ROOT_PROOF_DEPTH + 1 => ELECTRA_VALIDATORS_ROOT_GINDEX,
+            depth => panic!("invalid validators root proof depth {}", depth),
+        };
+        self.ssz_verify_proof_const(block_root, validators_root, branch, gindex);
+    }
+
+    /// The depth of the proof of the validators root under the builder's fork.
+    fn beacon_validators_root_proof_depth(&self) -> usize {
+        match self.beacon_config.fork {
+            BeaconFork::Deneb => VALIDATORS_ROOT_PROOF_DEPTH,
+            BeaconFork::Electra => VALIDATORS_ROOT_PROOF_DEPTH + 1,
+        }
+    }
+
+    /// Get the validators for a given block root along with the length of the validators list.
+    ///
+    /// The proof of the validators root is as deep as the builder's `BeaconConfig::fork` requires.
+    /// The length is proven by checking that `validators_root = sha256(merkle_root, length_leaf)`,
+    /// where `length_leaf` is the little-endian encoding of the length.
+    fn beacon_get_validators_with_length(
+        &mut self,
+        block_root: Bytes32Variable,
+    ) -> (BeaconValidatorsVariable, U64Variable) {
         let mut input_stream = VariableStream::new();
         input_stream.write(&block_root);
-        let hint = BeaconValidatorsHint::new();
+        let depth = self.beacon_validators_root_proof_depth();
+        let hint = BeaconValidatorsHint::new(depth).with_nodes(self.beacon_nodes());
         let output_stream = self.async_hint(input_stream, hint);
 
         let validators_root = output_stream.read::<Bytes32Variable>(self);
-        let proof = array![_ => output_stream.read::<Bytes32Variable>(self); 8];
-        self.ssz_verify_proof_const(block_root, validators_root, &proof, VALIDATORS_ROOT_GINDEX);
-        BeaconValidatorsVariable {
-            block_root,
-            validators_root,
-        }
+        let proof = (0..depth)
+            .map(|_| output_stream.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
+        self.beacon_verify_validators_root(block_root, validators_root, &proof);
+
+        let merkle_root = output_stream.read::<Bytes32Variable>(self);
+        let length = output_stream.read::<U64Variable>(self);
+        let length_leaf = self.ssz_length_leaf(length);
+        let reconstructed_root = self.ssz_hash_leafs(&[merkle_root, length_leaf]);
+        self.assert_is_equal(validators_root, reconstructed_root);
+
+        (
+            BeaconValidatorsVariable {
+                block_root,
+                validators_root,
+            },
+            length,
+        )
     }
 
     /// Get a beacon validator from a given dynamic index.
@@ -147,7 +342,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> BeaconValidatorVariable {
         let generator =
             BeaconValidatorGenerator::new_with_index_variable(self, validators.block_root, index);
-        self.add_simple_generator(generator.clone());
+        self.add_network_simple_generator(generator.clone());
         let validator_root = self.ssz_hash_tree_root(generator.validator);
         let mut gindex = self.constant::<U64Variable>(VALIDATOR_BASE_GINDEX);
         gindex = self.add(gindex, index);
@@ -169,7 +364,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let mut input_stream = VariableStream::new();
         input_stream.write(&balances.block_root);
         input_stream.write(&start_idx);
-        let hint = BeaconValidatorBatchHint::<B> {};
+        let hint = BeaconValidatorBatchHint::<B> {
+            nodes: self.beacon_nodes(),
+        };
         let output_stream = self.hint(input_stream, hint);
         output_stream.read::<ArrayVariable<BeaconValidatorVariable, B>>(self)
     }
@@ -183,7 +380,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let mut input_stream = VariableStream::new();
         input_stream.write(&balances.block_root);
         input_stream.write(&start_idx);
-        let hint = CompressedBeaconValidatorBatchHint::<B> {};
+        let hint = CompressedBeaconValidatorBatchHint::<B> {
+            nodes: self.beacon_nodes(),
+        };
         let output_stream = self.hint(input_stream, hint);
         output_stream.read::<ArrayVariable<CompressedBeaconValidatorVariable, B>>(self)
     }
@@ -196,7 +395,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> BeaconValidatorVariable {
         let generator =
             BeaconValidatorGenerator::new_with_index_const(self, validators.block_root, index);
-        self.add_simple_generator(generator.clone());
+        self.add_network_simple_generator(generator.clone());
         let validator_root = self.ssz_hash_tree_root(generator.validator);
         let gindex = VALIDATOR_BASE_GINDEX + index;
         self.ssz_verify_proof_const(
@@ -210,6 +409,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 
     /// Gets a validator from a given pubkey. Returns the validator index along with the validator
     /// data.
+    ///
+    /// The index is constrained to be the position of the validator in the validators list, i.e.
+    /// `validators[index].pubkey == pubkey`, so it can be exposed as an output or passed to the
+    /// other index based gadgets.
     pub fn beacon_get_validator_by_pubkey(
         &mut self,
         validators: BeaconValidatorsVariable,
@@ -217,10 +420,17 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> (U64Variable, BeaconValidatorVariable) {
         let generator =
             BeaconValidatorGenerator::new_with_pubkey_variable(self, validators.block_root, pubkey);
-        self.add_simple_generator(generator.clone());
+        self.add_network_simple_generator(generator.clone());
+
+        // Bound the index by the validator registry limit, so that the gindex does not wrap and the
+        // proof pins down the whole index rather than only its low bits. Both limbs are already
+        // checked to be u32s, and the registry limit of 2^40 leaves 8 bits for the high limb.
+        let index = generator.validator_idx;
+        self.range_check_u8(index.limbs[1].variable);
+
         let validator_root = self.ssz_hash_tree_root(generator.validator);
         let mut gindex = self.constant::<U64Variable>(VALIDATOR_BASE_GINDEX);
-        gindex = self.add(gindex, generator.validator_idx);
+        gindex = self.add(gindex, index);
         self.ssz_verify_proof(
             validators.validators_root,
             validator_root,
@@ -231,16 +441,159 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         (generator.validator_idx, generator.validator)
     }
 
-    /// Get the balances for a given block root.
-    pub fn beacon_get_balances(&mut self, block_root: Bytes32Variable) -> BeaconBalancesVariable {
-        let generator =
-            BeaconBalancesGenerator::new(self, self.beacon_client.clone().unwrap(), block_root);
-        self.add_simple_generator(generator.clone());
+    /// Computes the SSZ root of a vector of pubkeys, such as the pubkeys field of a
+    /// `SyncCommittee`.
+    ///
+    /// Each pubkey is merkleized as two chunks, its 48 bytes followed by 16 zero bytes, and the
+    /// pubkey roots are merkleized with zero chunks padding them to a power of two. Vectors have no
+    /// length mixed into their root.
+    pub fn hash_pubkey_list(&mut self, pubkeys: &[BLSPubkeyVariable]) -> Bytes32Variable {
+        let leafs = pubkeys
+            .iter()
+            .map(|pubkey| pubkey.hash_tree_root(self))
+            .collect::<Vec<_>>();
+        self.ssz_merkleize(&leafs)
+    }
+
+    /// Returns whether two validator pubkeys are equal.
+    ///
+    /// The 384 bits of each pubkey are packed into field elements of at most 63 bits before being
+    /// compared, which is much cheaper than comparing each byte or bit individually.
+    pub fn pubkeys_equal(&mut self, a: BLSPubkeyVariable, b: BLSPubkeyVariable) -> BoolVariable {
+        let a_bits = a
+            .targets()
+            .into_iter()
+            .map(BoolTarget::new_unsafe)
+            .collect::<Vec<_>>();
+        let b_bits = b
+            .targets()
+            .into_iter()
+            .map(BoolTarget::new_unsafe)
+            .collect::<Vec<_>>();
+
+        let mut result = self._true();
+        for (a_chunk, b_chunk) in a_bits.chunks(63).zip(b_bits.chunks(63)) {
+            let a_packed = Variable(self.api.le_sum(a_chunk.iter()));
+            let b_packed = Variable(self.api.le_sum(b_chunk.iter()));
+            let chunk_eq = self.is_equal(a_packed, b_packed);
+            result = self.and(result, chunk_eq);
+        }
+        result
+    }
+
+    /// Proves that no validator in `validators` has the given pubkey.
+    ///
+    /// The validators list is not sorted by pubkey, so absence is proven with an exhaustive scan
+    /// of the first `B` validators. The root of the subtree holding them is proven against the
+    /// block root and the length of the list is proven against the validators root. The length is
+    /// asserted to be at most `B`, so the scan covers every registered validator, and the scan
+    /// itself is constrained by `beacon_assert_pubkey_absent_from_subtree`.
+    ///
+    /// `B` must be a power of two. The circuit hashes and compares every one of the `B` validators,
+    /// so it grows linearly with `B` and an exhaustive scan is only feasible for small registries
+    /// such as devnets. Mainnet has over 2^20 validators, which does not fit in a single circuit:
+    /// there, each subtree has to be proven absent in its own circuit with
+    /// `beacon_assert_pubkey_absent_from_subtree`, and the subtree roots checked against the
+    /// validators root when aggregating them.
+    pub fn beacon_prove_validator_absent<const B: usize>(
+        &mut self,
+        validators: BeaconValidatorsVariable,
+        pubkey: BLSPubkeyVariable,
+    ) {
+        let (registry, length) = self.beacon_get_validators_with_length(validators.block_root);
+        self.assert_is_equal(registry.validators_root, validators.validators_root);
+
+        let subtree = self.beacon_get_partial_validators::<B>(validators.block_root);
+        let start_idx = self.zero::<U64Variable>();
+        let batch = self.beacon_witness_validator_batch::<B>(validators, start_idx);
+        self.beacon_assert_pubkey_absent_from_subtree(
+            subtree.validators_root,
+            length,
+            batch.as_slice(),
+            pubkey,
+        );
+    }
+
+    /// Asserts that none of the first `length` validators of the subtree with root `subtree_root`
+    /// has the given pubkey.
+    ///
+    /// `validators` must witness every leaf of the subtree. The leaves below `length` are the hash
+    /// tree roots of the witnessed validators, whose pubkeys are compared with `pubkey`. The leaves
+    /// past `length` are constrained to be empty, so the witnessed validators there are ignored.
+    /// The subtree rebuilt from these leaves must match `subtree_root`.
+    pub fn beacon_assert_pubkey_absent_from_subtree(
+        &mut self,
+        subtree_root: Bytes32Variable,
+        length: U64Variable,
+        validators: &[BeaconValidatorVariable],
+        pubkey: BLSPubkeyVariable,
+    ) {
+        assert!(
+            validators.len().is_power_of_two(),
+            "the subtree must have a power of two number of leaves"
+        );
+        let max_length = self.constant::<U64Variable>(validators.len() as u64);
+        let fits = self.lte(length, max_length);
+        let true_bool = self._true();
+        self.assert_is_equal(fits, true_bool);
+
+        let false_bool = self._false();
+        let empty_leaf = self.constant::<Bytes32Variable>(H256::zero());
+        let mut leaves = Vec::with_capacity(validators.len());
+        for (i, validator) in validators.iter().enumerate() {
+            let index = self.constant::<U64Variable>(i as u64);
+            let is_registered = self.lt(index, length);
+
+            let validator_root = self.ssz_hash_tree_root(*validator);
+            leaves.push(self.select(is_registered, validator_root, empty_leaf));
+
+            let is_match = self.pubkeys_equal(validator.pubkey, pubkey);
+            let is_registered_match = self.and(is_registered, is_match);
+            self.assert_is_equal(is_registered_match, false_bool);
+        }
+
+        let root = self.ssz_hash_leafs(&leaves);
+        self.assert_is_equal(root, subtree_root);
+    }
+
+    /// Asserts that none of the validators with the given pubkeys is slashed, which also requires
+    /// each of them to be registered.
+    pub fn beacon_assert_none_slashed(
+        &mut self,
+        validators: BeaconValidatorsVariable,
+        pubkeys: &[BLSPubkeyVariable],
+    ) {
+        let set = pubkeys
+            .iter()
+            .map(|pubkey| self.beacon_get_validator_by_pubkey(validators, *pubkey).1)
+            .collect::<Vec<_>>();
+        self.beacon_assert_validators_not_slashed(&set);
+    }
+
+    /// Asserts that none of the given validators is slashed.
+    pub fn beacon_assert_validators_not_slashed(&mut self, validators: &[BeaconValidatorVariable]) {
+        let false_bool = self._false();
+        for validator in validators.iter() {
+            self.assert_is_equal(validator.slashed, false_bool);
+        }
+    }
+
+    /// Get the balances for a given block.
+    pub fn beacon_get_balances(&mut self, block: impl BeaconBlockId) -> BeaconBalancesVariable {
+        let block_root = block.block_root(self);
+        let gindex = self.beacon_state_field_gindex(BALANCES_FIELD);
+        let generator = BeaconBalancesGenerator::new(
+            self,
+            self.beacon_client.clone().unwrap(),
+            block_root,
+            gindex.ilog2() as usize,
+        );
+        self.add_network_simple_generator(generator.clone());
         self.ssz_verify_proof_const(
             block_root,
             generator.balances_root,
             &generator.proof,
-            BALANCES_ROOT_GINDEX,
+            gindex,
         );
         BeaconBalancesVariable {
             block_root,
@@ -248,27 +601,125 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 
+    /// The gindices of the validators root, the balances root and their common ancestor under the
+    /// builder's fork.
+    fn beacon_validators_and_balances_gindices(&self) -> (u64, u64, u64) {
+        match self.beacon_config.fork {
+            BeaconFork::Deneb => (
+                VALIDATORS_ROOT_GINDEX,
+                BALANCES_ROOT_GINDEX,
+                VALIDATORS_AND_BALANCES_GINDEX,
+            ),
+            BeaconFork::Electra => (
+                ELECTRA_VALIDATORS_ROOT_GINDEX,
+                ELECTRA_BALANCES_ROOT_GINDEX,
+                ELECTRA_VALIDATORS_AND_BALANCES_GINDEX,
+            ),
+        }
+    }
+
+    /// Get the validators and the balances for a given block.
+    ///
+    /// Both roots are witnessed by a single hint and verified with a multiproof that shares the
+    /// path from their common ancestor to the block root, which saves the hashes of that path
+    /// compared to calling `beacon_get_validators` and `beacon_get_balances`. As with those, the
+    /// length of the validators list is proven and the proofs follow the builder's fork. Both
+    /// views hold the same block root variable.
+    pub fn beacon_get_validators_and_balances(
+        &mut self,
+        block: impl BeaconBlockId,
+    ) -> (BeaconValidatorsVariable, BeaconBalancesVariable) {
+        let block_root = block.block_root(self);
+        let (validators_gindex, balances_gindex, ancestor_gindex) =
+            self.beacon_validators_and_balances_gindices();
+        let shared_depth =
+            self.beacon_validators_root_proof_depth() - VALIDATORS_AND_BALANCES_BRANCH_DEPTH - 1;
+
+        let mut input = VariableStream::new();
+        input.write(&block_root);
+        let hint = BeaconValidatorsAndBalancesHint {
+            shared_depth,
+            nodes: self.beacon_nodes(),
+        };
+        let output = self.hint(input, hint);
+        let validators_root = output.read::<Bytes32Variable>(self);
+        let balances_root = output.read::<Bytes32Variable>(self);
+        let validators_branch = output
+            .read::<ArrayVariable<Bytes32Variable, VALIDATORS_AND_BALANCES_BRANCH_DEPTH>>(self);
+        let balances_branch = output
+            .read::<ArrayVariable<Bytes32Variable, VALIDATORS_AND_BALANCES_BRANCH_DEPTH>>(self);
+        let shared_branch = (0..shared_depth)
+            .map(|_| output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
+        let merkle_root = output.read::<Bytes32Variable>(self);
+        let length = output.read::<U64Variable>(self);
+
+        // The validators root is the last leaf of the left child of the common ancestor and the
+        // balances root is the first leaf of its right child. Restoring a branch only uses the
+        // low bits of the gindex, which select the path below the ancestor.
+        let left = self.ssz_restore_merkle_root_const(
+            validators_root,
+            validators_branch.as_slice(),
+            validators_gindex,
+        );
+        let right = self.ssz_restore_merkle_root_const(
+            balances_root,
+            balances_branch.as_slice(),
+            balances_gindex,
+        );
+        let ancestor = self.curta_sha256_pair(left, right);
+        self.ssz_verify_proof_const(block_root, ancestor, &shared_branch, ancestor_gindex);
+
+        let length_leaf = self.ssz_length_leaf(length);
+        let reconstructed_root = self.ssz_hash_leafs(&[merkle_root, length_leaf]);
+        self.assert_is_equal(validators_root, reconstructed_root);
+
+        (
+            BeaconValidatorsVariable {
+                block_root,
+                validators_root,
+            },
+            BeaconBalancesVariable {
+                block_root,
+                root: balances_root,
+            },
+        )
+    }
+
+    /// Get a handle to the beacon state at a given block.
+    ///
+    /// The validators and balances roots are verified with the shared proof of
+    /// `beacon_get_validators_and_balances`, and queries through the handle reuse them.
+    pub fn beacon_state(&mut self, block: impl BeaconBlockId) -> BeaconState {
+        let block_root = block.block_root(self);
+        let (validators, balances) = self.beacon_get_validators_and_balances(block_root);
+        BeaconState::new(block_root, validators, balances)
+    }
+
     /// Get the first B balances for a given block root.
     pub fn beacon_get_partial_balances<const B: usize>(
         &mut self,
         block_root: Bytes32Variable,
     ) -> BeaconBalancesVariable {
         let b_log2 = (B as f64).log2().ceil() as usize;
-        let hint = BeaconPartialBalancesHint::<B> {};
+        let gindex = self.beacon_state_field_gindex(BALANCES_FIELD)
+            << (VALIDATOR_REGISTRY_LIMIT_LOG2 + 1 - b_log2);
+        let nb_branches = gindex.ilog2() as usize;
+        let hint = BeaconPartialBalancesHint::<B> {
+            depth: nb_branches,
+            nodes: self.beacon_nodes(),
+        };
         let mut input_stream = VariableStream::new();
         input_stream.write(&block_root);
 
         let output_stream = self.async_hint(input_stream, hint);
         let partial_balances_root = output_stream.read::<Bytes32Variable>(self);
-        let nb_branches = BALANCES_PROOF_DEPTH + (VALIDATOR_REGISTRY_LIMIT_LOG2 + 1 - b_log2);
         let mut proof = Vec::new();
         for _ in 0..nb_branches {
             proof.push(output_stream.read::<Bytes32Variable>(self));
         }
 
-        let gindex = BALANCES_GINDEX * (2usize.pow(41 - b_log2 as u32));
-        let gindex = concat_g_indices(&[STATE_ROOT_GINDEX as usize, gindex]);
-        self.ssz_verify_proof_const(block_root, partial_balances_root, &proof, gindex as u64);
+        self.ssz_verify_proof_const(block_root, partial_balances_root, &proof, gindex);
         BeaconBalancesVariable {
             block_root,
             root: partial_balances_root,
@@ -283,7 +734,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> Vec<Bytes32Variable> {
         let mut input_stream = VariableStream::new();
         input_stream.write::<Bytes32Variable>(&block_root);
-        let hint = BeaconValidatorSubtreesHint::<B, N> {};
+        let hint = BeaconValidatorSubtreesHint::<B, N> {
+            nodes: self.beacon_nodes(),
+        };
         let output_stream = self.async_hint(input_stream, hint);
         let num_batches = N / B;
         let mut subtrees = Vec::new();
@@ -295,13 +748,18 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 
     /// Given a batch size, limit, and subtree root, witness the `B` validators within that subtree.
+    ///
+    /// Use [`BeaconValidatorVariable::subtree_leaf`] to hash the witnessed validators, so that the
+    /// placeholders padding the subtree past the end of the registry hash to empty leaves.
     pub fn beacon_witness_validator_subtree<const B: usize, const N: usize>(
         &mut self,
         subtree_hash: Bytes32Variable,
     ) -> Vec<BeaconValidatorVariable> {
         let mut input_stream = VariableStream::new();
         input_stream.write::<Bytes32Variable>(&subtree_hash);
-        let hint = BeaconValidatorSubtreeHint::<B, N> {};
+        let hint = BeaconValidatorSubtreeHint::<B, N> {
+            nodes: self.beacon_nodes(),
+        };
         let output_stream = self.async_hint(input_stream, hint);
         let mut subtrees = Vec::new();
         for _i in 0..B {
@@ -323,7 +781,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input_stream.write::<Bytes32Variable>(&block_root);
         input_stream.write::<Bytes32Variable>(&withdrawal_credentials);
         input_stream.write::<U64Variable>(&start_index);
-        let hint = BeaconValidatorSubtreePoseidonHint::<B> {};
+        let hint = BeaconValidatorSubtreePoseidonHint::<B> {
+            nodes: self.beacon_nodes(),
+        };
         let output_stream = self.async_hint(input_stream, hint);
         let mut subtrees = Vec::new();
         for _ in 0..B {
@@ -334,6 +794,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 
     /// Serializes a list of u64s into a single leaf according to the SSZ spec.
+    ///
+    /// Each u64 is laid out little-endian, i.e. byte-reversed relative to
+    /// [`ValueSerializable::to_bytes`](crate::frontend::vars::ValueSerializable::to_bytes).
     pub fn beacon_u64s_to_leaf(&mut self, u64s: [U64Variable; 4]) -> Bytes32Variable {
         let mut leaf = self.init_unsafe::<Bytes32Variable>();
         let bytes = [
@@ -361,7 +824,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input_stream.write(&balances.block_root);
         input_stream.write(&index);
 
-        let hint = BeaconBalanceWitnessHint {};
+        let hint = BeaconBalanceWitnessHint {
+            nodes: self.beacon_nodes(),
+        };
         let output_stream = self.hint(input_stream, hint);
         output_stream.read::<U64Variable>(self)
     }
@@ -375,7 +840,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let mut input_stream = VariableStream::new();
         input_stream.write(&balances.block_root);
         input_stream.write(&start_idx);
-        let hint = BeaconBalanceBatchWitnessHint::<B> {};
+        let hint = BeaconBalanceBatchWitnessHint::<B> {
+            nodes: self.beacon_nodes(),
+        };
         let output_stream = self.hint(input_stream, hint);
         output_stream.read::<ArrayVariable<U64Variable, B>>(self)
     }
@@ -388,7 +855,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> U64Variable {
         let generator =
             BeaconBalanceGenerator::new_with_index_variable(self, balances.block_root, index);
-        self.add_simple_generator(generator.clone());
+        self.add_network_simple_generator(generator.clone());
         let mut gindex = self.constant::<U64Variable>(BALANCE_BASE_GINDEX);
         let four = self.constant::<U64Variable>(4);
 
@@ -422,6 +889,219 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         generator.balance
     }
 
+    /// Returns whether a validator has 0x01 withdrawal credentials pointing to the given execution
+    /// layer address.
+    pub fn beacon_has_withdrawal_address(
+        &mut self,
+        validator: &BeaconValidatorVariable,
+        withdrawal_address: AddressVariable,
+    ) -> BoolVariable {
+        let prefix = self.constant::<BytesVariable<12>>(ETH1_ADDRESS_WITHDRAWAL_CREDENTIALS_PREFIX);
+        let mut credentials = prefix.0.to_vec();
+        credentials.extend_from_slice(&withdrawal_address.0 .0);
+        let expected_credentials = BytesVariable::<32>(credentials.try_into().unwrap());
+        self.is_equal(validator.withdrawal_credentials.0, expected_credentials)
+    }
+
+    /// Returns whether a validator has 0x01 withdrawal credentials.
+    pub fn beacon_has_eth1_withdrawal_credential(
+        &mut self,
+        validator: &BeaconValidatorVariable,
+    ) -> BoolVariable {
+        let prefix = self.constant::<ByteVariable>(ETH1_ADDRESS_WITHDRAWAL_CREDENTIALS_PREFIX[0]);
+        self.is_equal(validator.withdrawal_credentials.0 .0[0], prefix)
+    }
+
+    /// Returns whether a validator is eligible for a full withdrawal sweep at `epoch`.
+    ///
+    /// This is `is_fully_withdrawable_validator` from the consensus specs. A validator whose
+    /// withdrawable epoch is `FAR_FUTURE_EPOCH` is never withdrawable, even when `epoch` does not
+    /// fit in a u64.
+    pub fn beacon_is_fully_withdrawable(
+        &mut self,
+        validator: BeaconValidatorVariable,
+        balance: GweiVariable,
+        epoch: U256Variable,
+    ) -> BoolVariable {
+        let has_eth1_credential = self.beacon_has_eth1_withdrawal_credential(&validator);
+
+        let far_future_epoch = self.constant::<U256Variable>(U256::from(FAR_FUTURE_EPOCH));
+        let is_far_future = self.is_equal(validator.withdrawable_epoch, far_future_epoch);
+        let is_set = self.not(is_far_future);
+        let is_reached = self.lte(validator.withdrawable_epoch, epoch);
+        let is_withdrawable_epoch = self.and(is_set, is_reached);
+
+        let zero = self.zero::<GweiVariable>();
+        let has_balance = self.gt(balance, zero);
+
+        let result = self.and(has_eth1_credential, is_withdrawable_epoch);
+        self.and(result, has_balance)
+    }
+
+    /// Returns whether a validator is eligible for a partial withdrawal sweep.
+    ///
+    /// This is `is_partially_withdrawable_validator` from the consensus specs.
+    pub fn beacon_is_partially_withdrawable(
+        &mut self,
+        validator: BeaconValidatorVariable,
+        balance: GweiVariable,
+    ) -> BoolVariable {
+        let has_eth1_credential = self.beacon_has_eth1_withdrawal_credential(&validator);
+
+        let max_effective_balance =
+            self.constant::<U256Variable>(U256::from(MAX_EFFECTIVE_BALANCE));
+        let has_max_effective_balance =
+            self.is_equal(validator.effective_balance, max_effective_balance);
+
+        let max_balance = self.constant::<GweiVariable>(MAX_EFFECTIVE_BALANCE);
+        let has_excess_balance = self.gt(balance, max_balance);
+
+        let result = self.and(has_eth1_credential, has_max_effective_balance);
+        self.and(result, has_excess_balance)
+    }
+
+    /// Returns the sum of the balances of all validators in the range `[0, max_validators)` whose
+    /// withdrawal credentials point to `withdrawal_address`.
+    ///
+    /// Each validator and balance is opened against the block root, so the result is fully
+    /// constrained by it. The sum is checked to not overflow a u64.
+    pub fn beacon_total_balance_for_credential(
+        &mut self,
+        block_root: Bytes32Variable,
+        withdrawal_address: AddressVariable,
+        max_validators: usize,
+    ) -> U64Variable {
+        let validators = self.beacon_get_validators(block_root);
+        let balances = self.beacon_get_balances(block_root);
+
+        let zero = self.zero::<U64Variable>();
+        let true_bool = self._true();
+        let mut total = zero;
+        for i in 0..max_validators {
+            let validator = self.beacon_get_validator_const(validators, i as u64);
+            let index = self.constant::<U64Variable>(i as u64);
+            let balance = self.beacon_get_balance(balances, index);
+
+            let is_match = self.beacon_has_withdrawal_address(&validator, withdrawal_address);
+            let amount = self.select(is_match, balance, zero);
+            let new_total = self.add(total, amount);
+
+            // The addition wraps around if and only if the new total is smaller than the old one.
+            let no_overflow = self.lte(total, new_total);
+            self.assert_is_equal(no_overflow, true_bool);
+            total = new_total;
+        }
+        total
+    }
+
+    /// Returns whether a validator initiated an exit in the epoch range `[epoch_lo, epoch_hi]`.
+    ///
+    /// Validators that have not initiated an exit have an exit epoch of `FAR_FUTURE_EPOCH` and are
+    /// never counted, even if `epoch_hi` is `FAR_FUTURE_EPOCH`.
+    pub fn beacon_exited_in_range(
+        &mut self,
+        validator: &BeaconValidatorVariable,
+        epoch_lo: U64Variable,
+        epoch_hi: U64Variable,
+    ) -> BoolVariable {
+        let epoch_lo = epoch_lo.to_u256(self);
+        let epoch_hi = epoch_hi.to_u256(self);
+        let far_future_epoch = self.constant::<U256Variable>(U256::from(FAR_FUTURE_EPOCH));
+        let is_far_future = self.is_equal(validator.exit_epoch, far_future_epoch);
+        let has_exited = self.not(is_far_future);
+        let after_lo = self.lte(epoch_lo, validator.exit_epoch);
+        let before_hi = self.lte(validator.exit_epoch, epoch_hi);
+        let in_range = self.and(after_lo, before_hi);
+        self.and(has_exited, in_range)
+    }
+
+    /// Counts the validators in the range `[0, scan_limit)` that initiated an exit in the epoch
+    /// range `[epoch_lo, epoch_hi]`.
+    ///
+    /// Each validator is opened against the validators root. For scan limits that do not fit in a
+    /// single circuit, use `beacon_count_exits_in_range_chunk` as the map stage of a mapreduce.
+    pub fn beacon_count_exits_in_range(
+        &mut self,
+        validators: BeaconValidatorsVariable,
+        epoch_lo: U64Variable,
+        epoch_hi: U64Variable,
+        scan_limit: usize,
+    ) -> U32Variable {
+        let zero = self.zero::<U32Variable>();
+        let one = self.one::<U32Variable>();
+        let mut count = zero;
+        for i in 0..scan_limit {
+            let validator = self.beacon_get_validator_const(validators, i as u64);
+            let exited = self.beacon_exited_in_range(&validator, epoch_lo, epoch_hi);
+            let increment = self.select(exited, one, zero);
+            count = self.add(count, increment);
+        }
+        count
+    }
+
+    /// Counts the validators at the given indices that initiated an exit in the epoch range
+    /// `[epoch_lo, epoch_hi]`.
+    ///
+    /// The partial counts of disjoint chunks can be summed in the reduce stage of a mapreduce.
+    pub fn beacon_count_exits_in_range_chunk<const B: usize>(
+        &mut self,
+        validators: BeaconValidatorsVariable,
+        indices: ArrayVariable<U64Variable, B>,
+        epoch_lo: U64Variable,
+        epoch_hi: U64Variable,
+    ) -> U32Variable {
+        let zero = self.zero::<U32Variable>();
+        let one = self.one::<U32Variable>();
+        let mut count = zero;
+        for index in indices.as_vec() {
+            let validator = self.beacon_get_validator(validators, index);
+            let exited = self.beacon_exited_in_range(&validator, epoch_lo, epoch_hi);
+            let increment = self.select(exited, one, zero);
+            count = self.add(count, increment);
+        }
+        count
+    }
+
+    /// Returns whether a validator is active at `epoch`, i.e. whether
+    /// `activation_epoch <= epoch < exit_epoch`.
+    /// Reference: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#is_active_validator
+    pub fn beacon_is_active_validator(
+        &mut self,
+        validator: &BeaconValidatorVariable,
+        epoch: U64Variable,
+    ) -> BoolVariable {
+        let epoch = epoch.to_u256(self);
+        let is_activated = self.lte(validator.activation_epoch, epoch);
+        let has_exited = self.lte(validator.exit_epoch, epoch);
+        let has_not_exited = self.not(has_exited);
+        self.and(is_activated, has_not_exited)
+    }
+
+    /// Counts the validators in the range `[0, max_scan)` that are active at `epoch`.
+    ///
+    /// Each validator is opened against the validators root, but the scan is bounded: validators
+    /// at index `max_scan` and above are never looked at. The result is therefore only the number
+    /// of active validators of the registry if the circuit also proves that the registry has at
+    /// most `max_scan` validators, e.g. with `beacon_get_validator_count`. Indices past the end of
+    /// the registry open to the empty validator, which is never active.
+    pub fn beacon_count_active_validators(
+        &mut self,
+        validators: BeaconValidatorsVariable,
+        epoch: U64Variable,
+        max_scan: usize,
+    ) -> U64Variable {
+        let zero = self.zero::<U64Variable>();
+        let one = self.one::<U64Variable>();
+        let mut count = zero;
+        for i in 0..max_scan {
+            let validator = self.beacon_get_validator_const(validators, i as u64);
+            let is_active = self.beacon_is_active_validator(&validator, epoch);
+            let increment = self.select(is_active, one, zero);
+            count = self.add(count, increment);
+        }
+        count
+    }
+
     /// Get the withdrawals for a given block root.
     pub fn beacon_get_withdrawals(
         &mut self,
@@ -429,7 +1109,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> BeaconWithdrawalsVariable {
         let generator =
             BeaconWithdrawalsGenerator::new(self, self.beacon_client.clone().unwrap(), block_root);
-        self.add_simple_generator(generator.clone());
+        self.add_network_simple_generator(generator.clone());
         self.ssz_verify_proof_const(
             block_root,
             generator.withdrawals_root,
@@ -451,8 +1131,12 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 
         let mut withdrawals_hint_input = VariableStream::new();
         withdrawals_hint_input.write(&block_root);
-        let withdrawals_hint_output =
-            self.async_hint(withdrawals_hint_input, BeaconAllWithdrawalsHint {});
+        let withdrawals_hint_output = self.async_hint(
+            withdrawals_hint_input,
+            BeaconAllWithdrawalsHint {
+                nodes: self.beacon_nodes(),
+            },
+        );
 
         let withdrawals = withdrawals_hint_output
             .read::<ArrayVariable<BeaconWithdrawalVariable, MAX_WITHDRAWALS_PER_PAYLOAD>>(self);
@@ -489,7 +1173,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             withdrawals,
             idx,
         );
-        self.add_simple_generator(generator.clone());
+        self.add_network_simple_generator(generator.clone());
         let mut gindex = self.constant::<U64Variable>(WITHDRAWAL_BASE_GINDEX);
         gindex = self.add(gindex, idx);
         let leaf = self.ssz_hash_tree_root(generator.withdrawal);
@@ -497,11 +1181,15 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         generator.withdrawal
     }
 
-    /// Get block header from block root.
-    pub fn beacon_get_block_header(&mut self, block_root: Bytes32Variable) -> BeaconHeaderVariable {
+    /// Get the header of a given block.
+    pub fn beacon_get_block_header(&mut self, block: impl BeaconBlockId) -> BeaconHeaderVariable {
+        let block_root = block.block_root(self);
         let mut slot_hint_input = VariableStream::new();
         slot_hint_input.write(&block_root);
-        let slot_hint_output = self.async_hint(slot_hint_input, BeaconHeaderHint {});
+        let hint = BeaconHeaderHint {
+            nodes: self.beacon_nodes(),
+        };
+        let slot_hint_output = self.async_hint(slot_hint_input, hint);
         let header = slot_hint_output.read::<BeaconHeaderVariable>(self);
 
         let restored_root = self.ssz_hash_tree_root(header);
@@ -510,30 +1198,50 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         header
     }
 
-    /// Get a historical block root using state.block_roots for close slots and historical_summaries for slots > 8192 slots away.
+    /// Get a historical block root using state.block_roots for close slots and historical_summaries
+    /// for slots at least SLOTS_PER_HISTORICAL_ROOT slots away.
     pub fn beacon_get_historical_block(
         &mut self,
         block_root: Bytes32Variable,
         source_slot: U64Variable,
         target_slot: U64Variable,
     ) -> Bytes32Variable {
+        let slots_per_historical_root = self.beacon_config.slots_per_historical_root;
+        let block_roots_depth = self.beacon_config.block_roots_depth();
+        let block_roots_gindex = self.beacon_state_field_gindex(BLOCK_ROOTS_FIELD);
+        let historical_summaries_gindex = self.beacon_historical_summaries_base_gindex();
+        let close_slot_block_root_depth = block_roots_gindex.ilog2() as usize + block_roots_depth;
+        // The far slot proof also holds the state summary root, the sibling of the block summary
+        // root.
+        let far_slot_block_root_depth = block_roots_depth + 1;
+        let far_slot_historical_summary_depth = historical_summaries_gindex.ilog2() as usize;
+
         let mut hint_input = VariableStream::new();
         hint_input.write(&block_root);
         hint_input.write(&target_slot);
-        let hint_output = self.async_hint(hint_input, BeaconHistoricalBlockHint {});
+        let hint = BeaconHistoricalBlockHint {
+            close_slot_block_root_depth,
+            far_slot_block_root_depth,
+            far_slot_historical_summary_depth,
+            nodes: self.beacon_nodes(),
+        };
+        let hint_output = self.async_hint(hint_input, hint);
 
         let target_block_root = hint_output.read::<Bytes32Variable>(self);
-        let close_slot_block_root_proof =
-            hint_output.read::<ArrayVariable<Bytes32Variable, CLOSE_SLOT_BLOCK_ROOT_DEPTH>>(self);
-        let far_slot_block_root_proof =
-            hint_output.read::<ArrayVariable<Bytes32Variable, FAR_SLOT_BLOCK_ROOT_DEPTH>>(self);
+        let close_slot_block_root_proof = (0..close_slot_block_root_depth)
+            .map(|_| hint_output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
+        let far_slot_block_root_proof = (0..far_slot_block_root_depth)
+            .map(|_| hint_output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
         let far_slot_historical_summary_root = hint_output.read::<Bytes32Variable>(self);
-        let far_slot_historical_summary_proof = hint_output
-            .read::<ArrayVariable<Bytes32Variable, FAR_SLOT_HISTORICAL_SUMMARY_DEPTH>>(self);
+        let far_slot_historical_summary_proof = (0..far_slot_historical_summary_depth)
+            .map(|_| hint_output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
 
-        // Use close slot logic if (source - target) < 8192
+        // Use close slot logic if (source - target) < SLOTS_PER_HISTORICAL_ROOT
         let source_sub_target = self.sub(source_slot, target_slot);
-        let slots_per_historical = self.constant::<U64Variable>(SLOTS_PER_HISTORICAL_ROOT as u64);
+        let slots_per_historical = self.constant::<U64Variable>(slots_per_historical_root);
         let one_u64 = self.constant::<U64Variable>(1);
         let slots_per_historical_sub_one = self.sub(slots_per_historical, one_u64);
         let is_close_slot = self.lte(source_sub_target, slots_per_historical_sub_one);
@@ -541,38 +1249,36 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let block_roots_array_index = self.rem(target_slot, slots_per_historical);
 
         // Close slot logic
-        let mut close_slot_block_root_gindex =
-            self.constant::<U64Variable>(CLOSE_SLOT_BLOCK_ROOT_GINDEX);
-        close_slot_block_root_gindex =
-            self.add(close_slot_block_root_gindex, block_roots_array_index);
+        let close_slot_block_root_gindex = self.beacon_block_roots_gindex(target_slot);
         let restored_close_slot_block_root = self.ssz_restore_merkle_root(
             target_block_root,
-            &close_slot_block_root_proof.as_vec(),
+            &close_slot_block_root_proof,
             close_slot_block_root_gindex,
         );
         let valid_close_slot = self.is_equal(restored_close_slot_block_root, block_root);
 
         // Far slot logic
-        let capella_slot = self.constant::<U64Variable>(CAPELLA_FORK_EPOCH * SLOTS_PER_EPOCH);
+        let capella_slot = self.constant::<U64Variable>(self.beacon_config.capella_fork_slot());
         let slots_since_capella = self.sub(target_slot, capella_slot);
         let historical_summary_array_index = self.div(slots_since_capella, slots_per_historical);
         let mut historical_summary_gindex =
-            self.constant::<U64Variable>(HISTORICAL_SUMMARIES_BASE_GINDEX);
+            self.constant::<U64Variable>(historical_summaries_gindex);
         historical_summary_gindex =
             self.add(historical_summary_gindex, historical_summary_array_index);
         let restored_far_slot_block_root = self.ssz_restore_merkle_root(
             far_slot_historical_summary_root,
-            &far_slot_historical_summary_proof.as_vec(),
+            &far_slot_historical_summary_proof,
             historical_summary_gindex,
         );
         let valid_far_slot_block_root = self.is_equal(restored_far_slot_block_root, block_root);
 
-        let mut far_slot_block_root_gindex =
-            self.constant::<U64Variable>(HISTORICAL_SUMMARY_BLOCK_ROOT_GINDEX);
+        let mut far_slot_block_root_gindex = self.constant::<U64Variable>(
+            HISTORICAL_SUMMARY_BLOCK_SUMMARY_ROOT_GINDEX << block_roots_depth,
+        );
         far_slot_block_root_gindex = self.add(far_slot_block_root_gindex, block_roots_array_index);
         let restored_far_slot_historical_root = self.ssz_restore_merkle_root(
             target_block_root,
-            &far_slot_block_root_proof.as_vec(),
+            &far_slot_block_root_proof,
             far_slot_block_root_gindex,
         );
         let valid_far_slot_historical_root = self.is_equal(
@@ -589,24 +1295,101 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         target_block_root
     }
 
-    pub fn beacon_get_block_roots(
+    /// Get the gindex for blockRoot -> state -> state.historicalSummaries[0].
+    fn beacon_historical_summaries_base_gindex(&self) -> u64 {
+        // The list root is mixed in with its length, so its entries are below its left child.
+        self.beacon_state_field_gindex(HISTORICAL_SUMMARIES_FIELD)
+            << (HISTORICAL_ROOTS_LIMIT_LOG2 + 1)
+    }
+
+    /// Get the entry at `index` of `state.historical_summaries` for the state of a block.
+    pub fn beacon_get_historical_summary(
         &mut self,
         block_root: Bytes32Variable,
-    ) -> ArrayVariable<Bytes32Variable, SLOTS_PER_HISTORICAL_ROOT> {
+        index: U64Variable,
+    ) -> HistoricalSummaryVariable {
+        let base_gindex = self.beacon_historical_summaries_base_gindex();
+        let depth = base_gindex.ilog2() as usize;
+
         let mut input = VariableStream::new();
         input.write(&block_root);
-        let output = self.hint(input, BeaconBlockRootsHint {});
-        let block_roots_root = output.read::<Bytes32Variable>(self);
-        let proof = output.read::<ArrayVariable<Bytes32Variable, BLOCK_ROOTS_PROOF_DEPTH>>(self);
-        let block_roots =
-            output.read::<ArrayVariable<Bytes32Variable, SLOTS_PER_HISTORICAL_ROOT>>(self);
-        self.ssz_verify_proof_const(
-            block_root,
-            block_roots_root,
-            proof.as_slice(),
-            BLOCK_ROOTS_GINDEX as u64,
+        input.write(&index);
+        let hint = BeaconHistoricalSummaryHint {
+            config: self.beacon_config,
+            depth,
+            nodes: self.beacon_nodes(),
+        };
+        let output = self.hint(input, hint);
+        let summary = output.read::<HistoricalSummaryVariable>(self);
+        let proof = (0..depth)
+            .map(|_| output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
+
+        // Bound the index by the list limit, so that the proof pins down the whole index.
+        self.api
+            .range_check(index.limbs[0].variable.0, HISTORICAL_ROOTS_LIMIT_LOG2);
+        self.api.assert_zero(index.limbs[1].variable.0);
+
+        let summary_root = self.ssz_hash_tree_root(summary);
+        let mut gindex = self.constant::<U64Variable>(base_gindex);
+        gindex = self.add(gindex, index);
+        self.ssz_verify_proof(block_root, summary_root, &proof, gindex);
+        summary
+    }
+
+    /// Verifies that `block_root` is the entry at `index` of the `block_roots` vector summarized
+    /// by `summary`, given the SSZ proof from the block summary root.
+    pub fn beacon_verify_historical_block_root(
+        &mut self,
+        summary: HistoricalSummaryVariable,
+        index: U64Variable,
+        block_root: Bytes32Variable,
+        proof: &[Bytes32Variable],
+    ) {
+        let slots_per_historical_root = self.beacon_config.slots_per_historical_root;
+        let depth = self.beacon_config.block_roots_depth();
+        assert_eq!(
+            proof.len(),
+            depth,
+            "a block_roots proof has {} siblings for a SLOTS_PER_HISTORICAL_ROOT of {}",
+            depth,
+            slots_per_historical_root
+        );
+
+        self.api.range_check(index.limbs[0].variable.0, depth);
+        self.api.assert_zero(index.limbs[1].variable.0);
+
+        let mut gindex = self.constant::<U64Variable>(1 << depth);
+        gindex = self.add(gindex, index);
+        self.ssz_verify_proof(summary.block_summary_root, block_root, proof, gindex);
+    }
+
+    /// Get `state.block_roots` for the state of a block, which holds SLOTS_PER_HISTORICAL_ROOT
+    /// roots under the builder's config.
+    pub fn beacon_get_block_roots(&mut self, block_root: Bytes32Variable) -> Vec<Bytes32Variable> {
+        let gindex = self.beacon_state_field_gindex(BLOCK_ROOTS_FIELD);
+        let depth = gindex.ilog2() as usize;
+        let length = self.beacon_config.slots_per_historical_root as usize;
+
+        let mut input = VariableStream::new();
+        input.write(&block_root);
+        let output = self.hint(
+            input,
+            BeaconBlockRootsHint {
+                depth,
+                length,
+                nodes: self.beacon_nodes(),
+            },
         );
-        let root = self.ssz_hash_leafs(block_roots.as_slice());
+        let block_roots_root = output.read::<Bytes32Variable>(self);
+        let proof = (0..depth)
+            .map(|_| output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
+        let block_roots = (0..length)
+            .map(|_| output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
+        self.ssz_verify_proof_const(block_root, block_roots_root, &proof, gindex);
+        let root = self.ssz_merkleize(&block_roots);
         self.assert_is_equal(root, block_roots_root);
         block_roots
     }
@@ -614,7 +1397,12 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     pub fn beacon_get_graffiti(&mut self, block_root: Bytes32Variable) -> Bytes32Variable {
         let mut input = VariableStream::new();
         input.write(&block_root);
-        let output = self.hint(input, BeaconGraffitiHint {});
+        let output = self.hint(
+            input,
+            BeaconGraffitiHint {
+                nodes: self.beacon_nodes(),
+            },
+        );
         let graffiti = output.read::<Bytes32Variable>(self);
         let proof = output.read::<ArrayVariable<Bytes32Variable, GRAFFITI_PROOF_DEPTH>>(self);
         self.ssz_verify_proof_const(
@@ -626,6 +1414,66 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         graffiti
     }
 
+    /// Get the execution payload header of the block at `block_root`, verified against the block
+    /// root with an SSZ proof into the block body.
+    pub fn beacon_get_execution_payload_header(
+        &mut self,
+        block_root: Bytes32Variable,
+    ) -> ExecutionPayloadHeaderVariable {
+        let mut input = VariableStream::new();
+        input.write(&block_root);
+        let output = self.hint(
+            input,
+            BeaconExecutionPayloadHeaderHint {
+                nodes: self.beacon_nodes(),
+            },
+        );
+        let header = output.read::<ExecutionPayloadHeaderVariable>(self);
+        let proof =
+            output.read::<ArrayVariable<Bytes32Variable, EXECUTION_PAYLOAD_PROOF_DEPTH>>(self);
+        let header_root = header.hash_tree_root(self);
+        self.ssz_verify_proof_const(
+            block_root,
+            header_root,
+            proof.as_slice(),
+            EXECUTION_PAYLOAD_GINDEX,
+        );
+        header
+    }
+
+    /// Get the randao mix at `state.randao_mixes[epoch % EPOCHS_PER_HISTORICAL_VECTOR]` from the
+    /// state of the given block root.
+    ///
+    /// The mix only belongs to `epoch` if it is within `EPOCHS_PER_HISTORICAL_VECTOR` epochs of the
+    /// state's current epoch, which the caller is responsible for checking.
+    pub fn beacon_get_randao_mix(
+        &mut self,
+        block_root: Bytes32Variable,
+        epoch: U64Variable,
+    ) -> Bytes32Variable {
+        let depth = self.beacon_state_field_gindex(RANDAO_MIXES_FIELD).ilog2() as usize
+            + self.beacon_config.randao_mixes_depth();
+
+        let mut input = VariableStream::new();
+        input.write(&block_root);
+        input.write(&epoch);
+        let output = self.hint(
+            input,
+            BeaconRandaoMixHint {
+                depth,
+                nodes: self.beacon_nodes(),
+            },
+        );
+        let randao_mix = output.read::<Bytes32Variable>(self);
+        let proof = (0..depth)
+            .map(|_| output.read::<Bytes32Variable>(self))
+            .collect::<Vec<_>>();
+
+        let gindex = self.beacon_randao_mixes_gindex(epoch);
+        self.ssz_verify_proof(block_root, randao_mix, &proof, gindex);
+        randao_mix
+    }
+
     pub fn beacon_witness_headers_from_offset_range<const B: usize>(
         &mut self,
         end_block_root: Bytes32Variable,
@@ -636,118 +1484,124 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input.write(&end_block_root);
         input.write(&start_offset);
         input.write(&end_offset);
-        let output = self.hint(input, BeaconHeadersFromOffsetRangeHint::<B> {});
+        let output = self.hint(
+            input,
+            BeaconHeadersFromOffsetRangeHint::<B> {
+                nodes: self.beacon_nodes(),
+            },
+        );
         output.read::<ArrayVariable<Bytes32Variable, B>>(self)
     }
+}
 
-    /// Verify a simple serialize (ssz) merkle proof with a dynamic index.
-    #[allow(unused_variables)]
-    pub fn ssz_verify_proof(
-        &mut self,
-        root: Bytes32Variable,
-        leaf: Bytes32Variable,
-        branch: &[Bytes32Variable],
-        gindex: U64Variable,
-    ) {
-        let expected_root = self.ssz_restore_merkle_root(leaf, branch, gindex);
-        self.assert_is_equal(root, expected_root);
-    }
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+    use std::env;
 
-    /// Verify a simple serialize (ssz) merkle proof with a constant index.
-    #[allow(unused_variables)]
-    pub fn ssz_verify_proof_const(
-        &mut self,
-        root: Bytes32Variable,
-        leaf: Bytes32Variable,
-        branch: &[Bytes32Variable],
-        gindex: u64,
-    ) {
-        let expected_root = self.ssz_restore_merkle_root_const(leaf, branch, gindex);
-        self.assert_is_equal(root, expected_root);
-    }
+    use array_macro::array;
+    use ethers::providers::{Http, Middleware, Provider};
+    use ethers::types::{H160, H256};
+    use log::debug;
+    use rand::rngs::OsRng;
+    use rand::Rng;
+    use tokio::runtime::Runtime;
+
+    use super::{
+        BALANCES_FIELD, BALANCE_BASE_GINDEX, BLOCK_ROOTS_FIELD, RANDAO_MIXES_FIELD,
+        VALIDATORS_AND_BALANCES_GINDEX, VALIDATOR_BASE_GINDEX, VALIDATOR_REGISTRY_LIMIT_LOG2,
+    };
+    use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::builder::CircuitBuilder;
+    use crate::frontend::eth::beacon::generators::{
+        VALIDATORS_AND_BALANCES_BRANCH_DEPTH, VALIDATORS_AND_BALANCES_SHARED_DEPTH,
+    };
+    use crate::frontend::eth::beacon::vars::{
+        BeaconValidatorVariable, GweiVariable, HistoricalSummaryVariable,
+    };
+    use crate::frontend::eth::vars::{AddressVariable, BLSPubkeyVariable};
+    use crate::frontend::uint::uint256::U256Variable;
+    use crate::frontend::uint::uint64::U64Variable;
+    use crate::frontend::vars::{ArrayVariable, BoolVariable, Bytes32Variable, U32Variable};
+    use crate::utils::eth::beacon::{
+        mock, BeaconClient, BeaconConfig, BeaconFork, BeaconStateId, BeaconValidator,
+    };
+    use crate::utils::hash::sha256;
+    use crate::utils::test_utils::assert_constraint_failure;
+    use crate::utils::{bytes, bytes32, hex};
 
-    /// Computes the expected merkle root given a leaf, branch, and dynamic index.
-    pub fn ssz_restore_merkle_root(
-        &mut self,
-        leaf: Bytes32Variable,
-        branch: &[Bytes32Variable],
-        gindex: U64Variable,
-    ) -> Bytes32Variable {
-        let bits = self.to_le_bits(gindex);
-        let mut hash = leaf;
-        for i in 0..branch.len() {
-            let left = branch[i].as_bytes();
-            let right = hash.as_bytes();
+    type L = DefaultParameters;
+    const D: usize = 2;
 
-            let mut data = [self.init_unsafe::<ByteVariable>(); 64];
-            data[..32].copy_from_slice(&left);
-            data[32..].copy_from_slice(&right);
-            let case1 = self.curta_sha256(&data);
+    /// The root of the header served by [`header_node`].
+    const HEADER_ROOT: &str = "0xa668524532c883d6adc0cfca6049980a55019497ebc738ec03ceee8cdfc13b5a";
+
+    /// Serves a header at slot 8000000 under its root, `finalized` and each of `slots`.
+    fn header_node(slots: &[u64]) -> BeaconClient {
+        let header = serde_json::json!({
+            "execution_optimistic": false,
+            "finalized": true,
+            "data": {
+                "root": HEADER_ROOT,
+                "canonical": true,
+                "header": {
+                    "message": {
+                        "slot": "8000000",
+                        "proposer_index": "1",
+                        "parent_root": format!("0x{}", "01".repeat(32)),
+                        "state_root": format!("0x{}", "03".repeat(32)),
+                        "body_root": format!("0x{}", "02".repeat(32)),
+                    }
+                }
+            }
+        })
+        .to_string();
+        let routes = [HEADER_ROOT.to_string(), "finalized".to_string()]
+            .into_iter()
+            .chain(slots.iter().map(|slot| slot.to_string()))
+            .map(|id| (format!("/eth/v1/beacon/headers/{}", id), header.clone()))
+            .collect::<HashMap<_, _>>();
+        BeaconClient::new(mock::serve_routes(routes))
+    }
 
-            data[..32].copy_from_slice(&right);
-            data[32..].copy_from_slice(&left);
-            let case2 = self.curta_sha256(&data);
+    #[test]
+    fn test_beacon_get_block_root() {
+        env_logger::try_init().unwrap_or_default();
 
-            hash = self.select(bits[i], case1, case2);
-        }
-        hash
-    }
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(header_node(&[8_000_000]));
 
-    /// Computes the expected merkle root given a leaf, branch, and deterministic index.
-    pub fn ssz_restore_merkle_root_const(
-        &mut self,
-        leaf: Bytes32Variable,
-        branch: &[Bytes32Variable],
-        gindex: u64,
-    ) -> Bytes32Variable {
-        let mut hash = leaf;
-        for i in 0..branch.len() {
-            let (first, second) = if (gindex >> i) & 1 == 1 {
-                (branch[i].as_bytes(), hash.as_bytes())
-            } else {
-                (hash.as_bytes(), branch[i].as_bytes())
-            };
-            let mut data = [ByteVariable::init_unsafe(self); 64];
-            data[..32].copy_from_slice(&first);
-            data[32..].copy_from_slice(&second);
-            hash = self.curta_sha256(&data);
-        }
-        hash
-    }
+        let finalized = builder.beacon_get_block_root(BeaconStateId::Finalized);
+        let at_slot = builder.beacon_get_block_root(BeaconStateId::Slot(8_000_000));
+        let header = builder.beacon_get_block_header(BeaconStateId::Finalized);
+        builder.write(finalized);
+        builder.write(at_slot);
+        builder.write(header.slot);
 
-    pub fn ssz_hash_tree_root<V: SSZVariable>(&mut self, variable: V) -> Bytes32Variable {
-        variable.hash_tree_root(self)
-    }
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
 
-    pub fn ssz_hash_leafs(&mut self, leafs: &[Bytes32Variable]) -> Bytes32Variable {
-        let mut leafs = leafs.to_vec();
-        while leafs.len() != 1 {
-            let mut tmp = Vec::new();
-            for i in 0..leafs.len() / 2 {
-                tmp.push(self.curta_sha256_pair(leafs[i * 2], leafs[i * 2 + 1]));
-            }
-            leafs = tmp;
-        }
-        leafs[0]
+        assert_eq!(output.read::<Bytes32Variable>(), bytes32!(HEADER_ROOT));
+        assert_eq!(output.read::<Bytes32Variable>(), bytes32!(HEADER_ROOT));
+        assert_eq!(output.read::<U64Variable>(), 8_000_000);
+        circuit.test_default_serializers();
     }
-}
-
-#[cfg(test)]
-pub(crate) mod tests {
-    use std::env;
-
-    use log::debug;
 
-    use crate::backend::circuit::DefaultParameters;
-    use crate::frontend::builder::CircuitBuilder;
-    use crate::frontend::eth::vars::BLSPubkeyVariable;
-    use crate::frontend::uint::uint64::U64Variable;
-    use crate::frontend::vars::Bytes32Variable;
-    use crate::utils::eth::beacon::BeaconClient;
-    use crate::utils::{bytes, bytes32};
+    #[test]
+    #[should_panic]
+    fn test_beacon_get_block_root_wrong_slot() {
+        // The node resolves slot 7999999 to the block at slot 8000000.
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(header_node(&[7_999_999]));
+        let block_root = builder.beacon_get_block_root(BeaconStateId::Slot(7_999_999));
+        builder.write(block_root);
 
-    type L = DefaultParameters;
-    const D: usize = 2;
+        let circuit = builder.build();
+        let input = circuit.input();
+        circuit.prove(&input);
+    }
 
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
@@ -797,6 +1651,35 @@ pub(crate) mod tests {
         circuit.test_default_serializers();
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_get_validator_count() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+        let latest_block_root = client.get_finalized_block_root().unwrap();
+        let list_node = client
+            .get_validators_list_node(latest_block_root.clone())
+            .unwrap();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+
+        let block_root = builder.constant::<Bytes32Variable>(bytes32!(latest_block_root));
+        let count = builder.beacon_get_validator_count(block_root);
+        builder.write(count);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U64Variable>(), list_node.length);
+        circuit.test_default_serializers();
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_beacon_get_validator() {
@@ -872,14 +1755,22 @@ pub(crate) mod tests {
             "0x933ad9491b62059dd065b560d256d8957a8c402cc6e8d8ee7290ae11e8f7329267a8811c397529dac52ae1342ba58c95"
         ));
         let validators = builder.beacon_get_validators(block_root);
-        let (_, validator) = builder.beacon_get_validator_by_pubkey(validators, pubkey);
+        let (index, validator) = builder.beacon_get_validator_by_pubkey(validators, pubkey);
         builder.assert_is_equal(validator.pubkey, pubkey);
 
+        // The returned index can be reused to look the validator up again.
+        let validator_at_index = builder.beacon_get_validator(validators, index);
+        builder.assert_is_equal(validator_at_index.pubkey, pubkey);
+        builder.write(index);
+
         let circuit = builder.build();
         let input = circuit.input();
-        let (proof, output) = circuit.prove(&input);
+        let (proof, mut output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
         circuit.test_default_serializers();
+
+        // The pubkey is that of the first validator.
+        assert_eq!(output.read::<U64Variable>(), 0);
     }
 
     #[test]
@@ -933,6 +1824,45 @@ pub(crate) mod tests {
         circuit.test_default_serializers();
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_get_validators_and_balances() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+        let block_root = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670"
+        ));
+
+        let start = builder.num_gates();
+        let (validators, balances) = builder.beacon_get_validators_and_balances(block_root);
+        let joint_gates = builder.num_gates() - start;
+
+        let start = builder.num_gates();
+        let expected_validators = builder.beacon_get_validators(block_root);
+        let expected_balances = builder.beacon_get_balances(block_root);
+        let separate_gates = builder.num_gates() - start;
+
+        debug!(
+            "validators and balances roots: joint={} gates, separate={} gates",
+            joint_gates, separate_gates
+        );
+        assert!(joint_gates < separate_gates);
+        builder.assert_is_equal(validators, expected_validators);
+        builder.assert_is_equal(balances, expected_balances);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_beacon_get_partial_balances_root() {
@@ -983,6 +1913,261 @@ pub(crate) mod tests {
         circuit.test_default_serializers();
     }
 
+    #[test]
+    fn test_beacon_total_balance_for_credential() {
+        env_logger::try_init().unwrap_or_default();
+
+        const MAX_VALIDATORS: usize = 4;
+
+        // Only the eth1 credentials of the address count: the second validator withdraws to
+        // another address and the third has a BLS credential that ends with the address.
+        let address = H160::repeat_byte(0xaa);
+        let credentials = |prefix: u8, address: H160| {
+            format!(
+                "0x{:02x}{}{}",
+                prefix,
+                "00".repeat(11),
+                hex::encode(address)
+            )
+        };
+        let validators = [
+            (1, address),
+            (1, H160::repeat_byte(0xbb)),
+            (0, address),
+            (1, address),
+        ]
+        .map(|(prefix, address)| BeaconValidator {
+            withdrawal_credentials: credentials(prefix, address),
+            ..validator(1, 32_000_000_000, u64::MAX)
+        });
+        let balances = [
+            32_001_000_000,
+            31_500_000_000,
+            32_000_000_000,
+            33_250_000_000,
+        ];
+
+        // The validators and the balances are both proven at the gindices of the builder's fork.
+        for fork in [BeaconFork::Deneb, BeaconFork::Electra] {
+            let (client, block_root) = validators_node_at(fork, &validators, &balances);
+
+            let mut builder = CircuitBuilder::<L, D>::new();
+            builder.set_beacon_client(client);
+            builder.set_beacon_config(BeaconConfig {
+                fork,
+                ..BeaconConfig::mainnet()
+            });
+
+            let block_root = builder.constant::<Bytes32Variable>(block_root);
+            let address = builder.constant::<AddressVariable>(address);
+            let total =
+                builder.beacon_total_balance_for_credential(block_root, address, MAX_VALIDATORS);
+            builder.write(total);
+
+            let circuit = builder.build();
+            let input = circuit.input();
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            assert_eq!(output.read::<U64Variable>(), 65_251_000_000, "{:?}", fork);
+        }
+    }
+
+    /// Validators that exited at epochs 100, 250 and 180, and one that has not exited.
+    fn exited_validators() -> Vec<BeaconValidator> {
+        [100, u64::MAX, 250, 180]
+            .into_iter()
+            .map(|exit_epoch: u64| BeaconValidator {
+                exit_epoch: exit_epoch.to_string(),
+                ..validator(1, 32_000_000_000, exit_epoch.saturating_add(256))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_beacon_count_exits_in_range() {
+        env_logger::try_init().unwrap_or_default();
+
+        const SCAN_LIMIT: usize = 4;
+
+        let validators = exited_validators();
+        let (client, block_root) = validators_node(&validators, &[32_000_000_000; 4]);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+
+        let block_root = builder.constant::<Bytes32Variable>(block_root);
+        let validators = builder.beacon_get_validators(block_root);
+        let epoch_lo = builder.read::<U64Variable>();
+        let epoch_hi = builder.read::<U64Variable>();
+        let count = builder.beacon_count_exits_in_range(validators, epoch_lo, epoch_hi, SCAN_LIMIT);
+        builder.write(count);
+
+        let circuit = builder.build();
+        // The validator that has not exited is never counted, even up to the far future epoch.
+        for (epoch_lo, epoch_hi, expected_count) in [(150, 260, 2), (0, u64::MAX, 3), (260, 300, 0)]
+        {
+            let mut input = circuit.input();
+            input.write::<U64Variable>(epoch_lo);
+            input.write::<U64Variable>(epoch_hi);
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<U32Variable>(), expected_count);
+        }
+    }
+
+    #[test]
+    fn test_beacon_count_exits_in_range_chunk() {
+        env_logger::try_init().unwrap_or_default();
+
+        let validators = exited_validators();
+        let (client, block_root) = validators_node(&validators, &[32_000_000_000; 4]);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+
+        let block_root = builder.constant::<Bytes32Variable>(block_root);
+        let validators = builder.beacon_get_validators(block_root);
+        let indices = builder.read::<ArrayVariable<U64Variable, 2>>();
+        let epoch_lo = builder.constant::<U64Variable>(150);
+        let epoch_hi = builder.constant::<U64Variable>(260);
+        let count =
+            builder.beacon_count_exits_in_range_chunk(validators, indices, epoch_lo, epoch_hi);
+        builder.write(count);
+
+        let circuit = builder.build();
+        // The partial counts of the two chunks add up to the count over the whole range.
+        for (indices, expected_count) in [([0, 1], 0), ([2, 3], 2), ([3, 0], 1)] {
+            let mut input = circuit.input();
+            input.write::<ArrayVariable<U64Variable, 2>>(indices.to_vec());
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<U32Variable>(), expected_count);
+        }
+    }
+
+    #[test]
+    fn test_beacon_exited_in_range() {
+        let exited_at = |exit_epoch: u64| {
+            let mut validator = validator(1, 32_000_000_000, exit_epoch.saturating_add(256));
+            validator.exit_epoch = exit_epoch.to_string();
+            validator
+        };
+        let cases = [
+            (exited_at(100), 100, 200, true),
+            (exited_at(200), 100, 200, true),
+            (exited_at(150), 100, 200, true),
+            (exited_at(99), 100, 200, false),
+            (exited_at(201), 100, 200, false),
+            (exited_at(u64::MAX), 100, u64::MAX, false),
+        ];
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        for _ in 0..cases.len() {
+            let validator = builder.read::<BeaconValidatorVariable>();
+            let epoch_lo = builder.read::<U64Variable>();
+            let epoch_hi = builder.read::<U64Variable>();
+            let exited = builder.beacon_exited_in_range(&validator, epoch_lo, epoch_hi);
+            builder.write(exited);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for (validator, epoch_lo, epoch_hi, _) in cases.iter() {
+            input.write::<BeaconValidatorVariable>(validator.clone());
+            input.write::<U64Variable>(*epoch_lo);
+            input.write::<U64Variable>(*epoch_hi);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for (i, (_, _, _, expected)) in cases.iter().enumerate() {
+            assert_eq!(output.read::<BoolVariable>(), *expected, "case {}", i);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_count_active_validators() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        const MAX_SCAN: usize = 4;
+
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+        let block_root = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+
+        let epoch = 200_000u64;
+        let mut expected_count = 0u64;
+        for i in 0..MAX_SCAN as u64 {
+            let validator = client
+                .get_validator(block_root.to_string(), i)
+                .unwrap()
+                .validator;
+            let activation_epoch = validator.activation_epoch.parse::<u64>().unwrap();
+            let exit_epoch = validator.exit_epoch.parse::<u64>().unwrap();
+            if activation_epoch <= epoch && epoch < exit_epoch {
+                expected_count += 1;
+            }
+        }
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+
+        let block_root = builder.constant::<Bytes32Variable>(bytes32!(block_root));
+        let validators = builder.beacon_get_validators(block_root);
+        let epoch = builder.constant::<U64Variable>(epoch);
+        let count = builder.beacon_count_active_validators(validators, epoch, MAX_SCAN);
+        builder.write(count);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U64Variable>(), expected_count);
+    }
+
+    #[test]
+    fn test_beacon_is_active_validator() {
+        let active_between = |activation_epoch: u64, exit_epoch: u64| {
+            let mut validator = validator(1, 32_000_000_000, exit_epoch.saturating_add(256));
+            validator.activation_epoch = activation_epoch.to_string();
+            validator.exit_epoch = exit_epoch.to_string();
+            validator
+        };
+        let cases = [
+            (active_between(100, 200), 100, true),
+            (active_between(100, 200), 199, true),
+            (active_between(100, 200), 99, false),
+            (active_between(100, 200), 200, false),
+            (active_between(0, u64::MAX), 150, true),
+            (active_between(u64::MAX, u64::MAX), 150, false),
+        ];
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        for _ in 0..cases.len() {
+            let validator = builder.read::<BeaconValidatorVariable>();
+            let epoch = builder.read::<U64Variable>();
+            let is_active = builder.beacon_is_active_validator(&validator, epoch);
+            builder.write(is_active);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for (validator, epoch, _) in cases.iter() {
+            input.write::<BeaconValidatorVariable>(validator.clone());
+            input.write::<U64Variable>(*epoch);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for (i, (_, _, expected)) in cases.iter().enumerate() {
+            assert_eq!(output.read::<BoolVariable>(), *expected, "case {}", i);
+        }
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_beacon_get_withdrawals() {
@@ -1061,6 +2246,61 @@ pub(crate) mod tests {
         circuit.test_default_serializers();
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_get_historical_summary() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+        let block_root = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+
+        // A slot in the period accumulated by the tenth historical summary.
+        let config = BeaconConfig::default();
+        let summary_index = 10;
+        let target_slot =
+            config.capella_fork_slot() + summary_index * config.slots_per_historical_root + 5;
+        let historical = Runtime::new()
+            .unwrap()
+            .block_on(client.get_historical_block(block_root.to_string(), target_slot))
+            .unwrap();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+
+        let block_root = builder.constant::<Bytes32Variable>(bytes32!(block_root));
+        let index = builder.constant::<U64Variable>(summary_index);
+        let summary = builder.beacon_get_historical_summary(block_root, index);
+        let summary_root = builder.ssz_hash_tree_root(summary);
+        let expected_summary_root = builder
+            .constant::<Bytes32Variable>(bytes32!(historical.far_slot_historical_summary_root));
+        builder.assert_is_equal(summary_root, expected_summary_root);
+
+        // The far slot proof runs from the block root to the summary, so all but its last sibling
+        // make up the proof to the block summary root.
+        let block_roots_index =
+            builder.constant::<U64Variable>(target_slot % config.slots_per_historical_root);
+        let target_block_root =
+            builder.constant::<Bytes32Variable>(bytes32!(historical.target_block_root));
+        let proof = historical.far_slot_block_root_proof[..config.block_roots_depth()]
+            .iter()
+            .map(|p| builder.constant::<Bytes32Variable>(bytes32!(p)))
+            .collect::<Vec<_>>();
+        builder.beacon_verify_historical_block_root(
+            summary,
+            block_roots_index,
+            target_block_root,
+            &proof,
+        );
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_beacon_get_block_roots() {
@@ -1076,7 +2316,8 @@ pub(crate) mod tests {
 
         let block_root = builder.constant::<Bytes32Variable>(bytes32!(latest_block_root));
         let block_roots = builder.beacon_get_block_roots(block_root);
-        builder.watch(&block_roots, "block_roots");
+        assert_eq!(block_roots.len(), 8192);
+        builder.watch(&block_roots[0], "block_roots[0]");
 
         let circuit = builder.build();
         let input = circuit.input();
@@ -1109,6 +2350,78 @@ pub(crate) mod tests {
         circuit.test_default_serializers();
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_get_execution_payload_header() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+        let block_root =
+            bytes32!("0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670");
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+
+        let block_root = builder.constant::<Bytes32Variable>(block_root);
+        let header = builder.beacon_get_execution_payload_header(block_root);
+        builder.write(header.block_number);
+        builder.write(header.block_hash);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+
+        let block_number = output.read::<U64Variable>();
+        let block_hash = output.read::<Bytes32Variable>();
+
+        let provider = Provider::<Http>::try_from(env::var("RPC_1").unwrap()).unwrap();
+        let block = Runtime::new()
+            .unwrap()
+            .block_on(provider.get_block(block_number))
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.hash.unwrap(), block_hash);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_get_randao_mix() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+        let latest_block_root = client.get_finalized_block_root().unwrap();
+        let slot = client.get_finalized_slot().unwrap().parse::<u64>().unwrap();
+        let epoch = slot / 32 - 2;
+        let expected_randao_mix = client
+            .get_state_randao(latest_block_root.clone(), epoch)
+            .unwrap();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+
+        let block_root = builder.constant::<Bytes32Variable>(bytes32!(latest_block_root));
+        let epoch = builder.constant::<U64Variable>(epoch);
+        let randao_mix = builder.beacon_get_randao_mix(block_root, epoch);
+        builder.write(randao_mix);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!(expected_randao_mix)
+        );
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_beacon_witness_headers_from_offset_range() {
@@ -1142,137 +2455,871 @@ pub(crate) mod tests {
     }
 
     #[test]
-    #[cfg_attr(feature = "ci", ignore)]
-    fn test_ssz_restore_merkle_root_equal() {
-        env_logger::try_init().unwrap_or_default();
-        dotenv::dotenv().ok();
+    fn test_pubkeys_equal() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<BLSPubkeyVariable>();
+        let b = builder.read::<BLSPubkeyVariable>();
+        let c = builder.read::<BLSPubkeyVariable>();
+        let a_eq_b = builder.pubkeys_equal(a, b);
+        let a_eq_c = builder.pubkeys_equal(a, c);
+        builder.write(a_eq_b);
+        builder.write(a_eq_c);
+
+        let circuit = builder.build();
+        let pubkey: [u8; 48] = bytes!(
+            "0x933ad9491b62059dd065b560d256d8957a8c402cc6e8d8ee7290ae11e8f7329267a8811c397529dac52ae1342ba58c95"
+        );
+        let mut other_pubkey = pubkey;
+        other_pubkey[47] ^= 1;
+
+        let mut input = circuit.input();
+        input.write::<BLSPubkeyVariable>(pubkey);
+        input.write::<BLSPubkeyVariable>(pubkey);
+        input.write::<BLSPubkeyVariable>(other_pubkey);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert!(output.read::<BoolVariable>());
+        assert!(!output.read::<BoolVariable>());
+    }
 
+    #[test]
+    fn test_pubkeys_equal_gate_count() {
         let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<BLSPubkeyVariable>();
+        let b = builder.read::<BLSPubkeyVariable>();
 
-        let leaf = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
-        ));
-        let index = builder.constant::<U64Variable>(2);
-        let branch = vec![
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
-            )),
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
-            )),
+        let start = builder.api.num_gates();
+        let _ = builder.pubkeys_equal(a, b);
+        let packed_gates = builder.api.num_gates() - start;
+
+        let start = builder.api.num_gates();
+        let _ = builder.is_equal(a, b);
+        let naive_gates = builder.api.num_gates() - start;
+
+        debug!(
+            "pubkeys_equal: {} gates, naive is_equal: {} gates",
+            packed_gates, naive_gates
+        );
+        assert!(packed_gates < naive_gates);
+    }
+
+    fn validator(
+        credentials_prefix: u8,
+        effective_balance: u64,
+        withdrawable_epoch: u64,
+    ) -> BeaconValidator {
+        BeaconValidator {
+            pubkey: format!("0x{}", "93".repeat(48)),
+            withdrawal_credentials: format!("0x{:02x}{}", credentials_prefix, "11".repeat(31)),
+            effective_balance,
+            slashed: false,
+            activation_eligibility_epoch: "0".to_string(),
+            activation_epoch: "0".to_string(),
+            exit_epoch: withdrawable_epoch.to_string(),
+            withdrawable_epoch: withdrawable_epoch.to_string(),
+        }
+    }
+
+    /// Restores the merkle root of `leaf` at `gindex` from its branch.
+    fn restore_merkle_root(leaf: H256, branch: &[H256], gindex: u64) -> H256 {
+        let mut hash = leaf;
+        for (i, sibling) in branch.iter().enumerate() {
+            hash = match (gindex >> i) & 1 {
+                1 => H256::from(sha256(&[sibling.0, hash.0].concat())),
+                _ => H256::from(sha256(&[hash.0, sibling.0].concat())),
+            };
+        }
+        hash
+    }
+
+    /// Returns the root of a tree of the given depth whose leaves past `leaves` are empty, along
+    /// with the branch of the leaf at `index`.
+    fn merkle_branch(leaves: &[H256], index: usize, depth: usize) -> (H256, Vec<H256>) {
+        let mut level = leaves.to_vec();
+        let mut index = index;
+        let mut zero = H256::zero();
+        let mut branch = Vec::new();
+        for _ in 0..depth {
+            if level.len() % 2 == 1 {
+                level.push(zero);
+            }
+            branch.push(level[index ^ 1]);
+            level = level
+                .chunks(2)
+                .map(|pair| H256::from(sha256(&[pair[0].0, pair[1].0].concat())))
+                .collect();
+            zero = H256::from(sha256(&[zero.0, zero.0].concat()));
+            index /= 2;
+        }
+        (level[0], branch)
+    }
+
+    fn uint64_leaf(value: u64) -> H256 {
+        let mut leaf = H256::zero();
+        leaf.0[..8].copy_from_slice(&value.to_le_bytes());
+        leaf
+    }
+
+    /// Serves a node whose Deneb state holds `validators` and `balances`, with a proof of each of
+    /// them and of the subtrees of the first validators. The other fields of the state and the
+    /// header hold arbitrary roots. Returns the client of the node and the block root of the state.
+    fn validators_node(validators: &[BeaconValidator], balances: &[u64]) -> (BeaconClient, H256) {
+        validators_node_at(BeaconFork::Deneb, validators, balances)
+    }
+
+    /// Like [`validators_node`], but for a state of the given fork.
+    fn validators_node_at(
+        fork: BeaconFork,
+        validators: &[BeaconValidator],
+        balances: &[u64],
+    ) -> (BeaconClient, H256) {
+        let to_hex = |roots: &[H256]| roots.iter().map(|r| format!("{:?}", r)).collect::<Vec<_>>();
+        let mix_in_length = |root: H256, length: usize| {
+            H256::from(sha256(&[root.0, uint64_leaf(length as u64).0].concat()))
+        };
+
+        let validator_json = |validator: &BeaconValidator| {
+            serde_json::json!({
+                "pubkey": validator.pubkey,
+                "withdrawalCredentials": validator.withdrawal_credentials,
+                "effectiveBalance": validator.effective_balance,
+                "slashed": validator.slashed,
+                "activationEligibilityEpoch": validator.activation_eligibility_epoch,
+                "activationEpoch": validator.activation_epoch,
+                "exitEpoch": validator.exit_epoch,
+                "withdrawableEpoch": validator.withdrawable_epoch,
+            })
+        };
+
+        let validator_leaves = validators
+            .iter()
+            .map(|v| v.ssz_hash_tree_root())
+            .collect::<Vec<_>>();
+        let (merkle_root, first_branch) =
+            merkle_branch(&validator_leaves, 0, VALIDATOR_REGISTRY_LIMIT_LOG2);
+        let validators_root = mix_in_length(merkle_root, validators.len());
+
+        // Balances are packed four to a leaf.
+        let balance_leaves = balances
+            .chunks(4)
+            .map(|chunk| {
+                let mut leaf = H256::zero();
+                for (i, balance) in chunk.iter().enumerate() {
+                    leaf.0[i * 8..(i + 1) * 8].copy_from_slice(&balance.to_le_bytes());
+                }
+                leaf
+            })
+            .collect::<Vec<_>>();
+        let balances_depth = VALIDATOR_REGISTRY_LIMIT_LOG2 - 2;
+        let (merkle_root, _) = merkle_branch(&balance_leaves, 0, balances_depth);
+        let balances_root = mix_in_length(merkle_root, balances.len());
+
+        let state_depth = fork.state_depth();
+        let num_fields = match fork {
+            BeaconFork::Deneb => 28,
+            BeaconFork::Electra => 37,
+        };
+        let mut state_fields = (0..num_fields)
+            .map(|i| H256::repeat_byte(i + 100))
+            .collect::<Vec<_>>();
+        state_fields[11] = validators_root;
+        state_fields[12] = balances_root;
+        let header_fields = |state_root: H256| {
+            [
+                uint64_leaf(8_000_000),
+                uint64_leaf(1),
+                H256::repeat_byte(1),
+                state_root,
+                H256::repeat_byte(2),
+            ]
+        };
+        let root_proof = |field: usize| {
+            let (state_root, mut branch) = merkle_branch(&state_fields, field, state_depth);
+            let (block_root, header_branch) = merkle_branch(&header_fields(state_root), 3, 3);
+            branch.extend(header_branch);
+            (block_root, branch)
+        };
+        let (block_root, validators_branch) = root_proof(11);
+        let (_, balances_branch) = root_proof(12);
+        let validators_root_gindex = (11u64 << state_depth) + 11;
+
+        let mut routes = HashMap::from([
+            (
+                format!("/api/beacon/proof/validator/{:?}", block_root),
+                serde_json::json!({
+                    "success": true,
+                    "result": {
+                        "validatorsRoot": format!("{:?}", validators_root),
+                        "gindex": validators_root_gindex.to_string(),
+                        "depth": validators_branch.len(),
+                        "proof": to_hex(&validators_branch),
+                    }
+                })
+                .to_string(),
+            ),
+            (
+                format!("/api/beacon/proof/balance/{:?}", block_root),
+                serde_json::json!({
+                    "success": true,
+                    "result": {
+                        "balancesRoot": format!("{:?}", balances_root),
+                        "gindex": (validators_root_gindex + 1).to_string(),
+                        "depth": balances_branch.len(),
+                        "proof": to_hex(&balances_branch),
+                    }
+                })
+                .to_string(),
+            ),
+        ]);
+        for (i, validator) in validators.iter().enumerate() {
+            let (_, mut branch) =
+                merkle_branch(&validator_leaves, i, VALIDATOR_REGISTRY_LIMIT_LOG2);
+            branch.push(uint64_leaf(validators.len() as u64));
+            let body = serde_json::json!({
+                "success": true,
+                "result": {
+                    "validatorRoot": format!("{:?}", validator_leaves[i]),
+                    "validatorsRoot": format!("{:?}", validators_root),
+                    "validatorIdx": i,
+                    "validator": validator_json(validator),
+                    "gindex": (VALIDATOR_BASE_GINDEX + i as u64).to_string(),
+                    "depth": branch.len(),
+                    "proof": to_hex(&branch),
+                }
+            });
+            routes.insert(
+                format!("/api/beacon/proof/validator/{:?}/{}", block_root, i),
+                body.to_string(),
+            );
+        }
+        // The root of the first 2^b_log2 validators and the batch of them, padded with the last
+        // validator past the end of the list.
+        for b_log2 in 0..=4 {
+            let b = 1usize << b_log2;
+            let (subtree_root, _) = merkle_branch(&validator_leaves, 0, b_log2);
+            let mut branch = first_branch[b_log2..].to_vec();
+            branch.push(uint64_leaf(validators.len() as u64));
+            branch.extend(validators_branch.iter());
+            routes.insert(
+                format!("/api/beacon/proof/partialValidator/{:?}/{}", block_root, b),
+                serde_json::json!({
+                    "success": true,
+                    "result": {
+                        "partialValidatorsRoot": format!("{:?}", subtree_root),
+                        "gindex": (validators_root_gindex << (41 - b_log2)).to_string(),
+                        "depth": branch.len(),
+                        "proof": to_hex(&branch),
+                    }
+                })
+                .to_string(),
+            );
+            let batch = (0..b)
+                .map(|i| validator_json(&validators[i.min(validators.len() - 1)]))
+                .collect::<Vec<_>>();
+            routes.insert(
+                format!("/api/beacon/validator/{:?}/0,{}", block_root, b),
+                serde_json::json!({ "validators": batch }).to_string(),
+            );
+        }
+        for (i, balance) in balances.iter().enumerate() {
+            let (_, mut branch) = merkle_branch(&balance_leaves, i / 4, balances_depth);
+            branch.push(uint64_leaf(balances.len() as u64));
+            let body = serde_json::json!({
+                "success": true,
+                "result": {
+                    "balance": balance,
+                    "balanceLeaf": format!("{:?}", balance_leaves[i / 4]),
+                    "balancesRoot": format!("{:?}", balances_root),
+                    "proof": to_hex(&branch),
+                    "depth": branch.len(),
+                    "gindex": (BALANCE_BASE_GINDEX + i as u64 / 4).to_string(),
+                }
+            });
+            routes.insert(
+                format!("/api/beacon/proof/balance/{:?}/{}", block_root, i),
+                body.to_string(),
+            );
+        }
+        (BeaconClient::new(mock::serve_routes(routes)), block_root)
+    }
+
+    fn verify_validators_root(block_root: H256, validators_root: H256, branch: &[H256]) {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let block_root_var = builder.read::<Bytes32Variable>();
+        let validators_root_var = builder.read::<Bytes32Variable>();
+        let branch_vars = branch
+            .iter()
+            .map(|_| builder.read::<Bytes32Variable>())
+            .collect::<Vec<_>>();
+        builder.beacon_verify_validators_root(block_root_var, validators_root_var, &branch_vars);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(block_root);
+        input.write::<Bytes32Variable>(validators_root);
+        for sibling in branch.iter() {
+            input.write::<Bytes32Variable>(*sibling);
+        }
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_beacon_verify_validators_root() {
+        let validators_root = H256::from(OsRng.gen::<[u8; 32]>());
+        for (depth, gindex) in [(8, 363), (9, 715)] {
+            let branch = (0..depth)
+                .map(|_| H256::from(OsRng.gen::<[u8; 32]>()))
+                .collect::<Vec<_>>();
+            let block_root = restore_merkle_root(validators_root, &branch, gindex);
+            verify_validators_root(block_root, validators_root, &branch);
+        }
+    }
+
+    #[test]
+    fn test_beacon_validators_root_proof_depth_follows_fork() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        assert_eq!(builder.beacon_validators_root_proof_depth(), 8);
+
+        builder.set_beacon_config(BeaconConfig {
+            fork: BeaconFork::Electra,
+            ..BeaconConfig::mainnet()
+        });
+        assert_eq!(builder.beacon_validators_root_proof_depth(), 9);
+    }
+
+    #[test]
+    fn test_beacon_validators_and_balances_gindices_follow_fork() {
+        for fork in [BeaconFork::Deneb, BeaconFork::Electra] {
+            let mut builder = CircuitBuilder::<L, D>::new();
+            builder.set_beacon_config(BeaconConfig {
+                fork,
+                ..BeaconConfig::mainnet()
+            });
+            let (validators, balances, ancestor) =
+                builder.beacon_validators_and_balances_gindices();
+            let depth = builder.beacon_validators_root_proof_depth();
+
+            // Both roots sit right below their common ancestor, whose proof is the shared one.
+            let below_ancestor = VALIDATORS_AND_BALANCES_BRANCH_DEPTH as u64 + 1;
+            assert_eq!(validators >> below_ancestor, ancestor, "{:?}", fork);
+            assert_eq!(balances >> below_ancestor, ancestor, "{:?}", fork);
+            assert_eq!(balances, validators + 1, "{:?}", fork);
+            assert_eq!(
+                ancestor.ilog2() as usize,
+                depth - VALIDATORS_AND_BALANCES_BRANCH_DEPTH - 1,
+                "{:?}",
+                fork
+            );
+        }
+        assert_eq!(
+            VALIDATORS_AND_BALANCES_GINDEX.ilog2() as usize,
+            VALIDATORS_AND_BALANCES_SHARED_DEPTH
+        );
+    }
+
+    #[test]
+    fn test_beacon_state_field_gindices_follow_fork() {
+        let expected = [
+            (BeaconFork::Deneb, [357, 364, 365], 12717129728),
+            (BeaconFork::Electra, [709, 716, 717], 24528289792),
         ];
-        let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xac0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
-        ));
+        for (fork, fields, historical_summaries) in expected {
+            let mut builder = CircuitBuilder::<L, D>::new();
+            builder.set_beacon_config(BeaconConfig {
+                fork,
+                ..BeaconConfig::mainnet()
+            });
+            let gindices = [BLOCK_ROOTS_FIELD, BALANCES_FIELD, RANDAO_MIXES_FIELD]
+                .map(|field| builder.beacon_state_field_gindex(field));
+            assert_eq!(gindices, fields, "{:?}", fork);
+            assert_eq!(
+                builder.beacon_historical_summaries_base_gindex(),
+                historical_summaries,
+                "{:?}",
+                fork
+            );
+        }
+    }
+
+    #[test]
+    fn test_beacon_verify_validators_root_wrong_branch() {
+        let validators_root = H256::from(OsRng.gen::<[u8; 32]>());
+        let branch = (0..8)
+            .map(|_| H256::from(OsRng.gen::<[u8; 32]>()))
+            .collect::<Vec<_>>();
+        let block_root = restore_merkle_root(validators_root, &branch, 363);
+        let mut wrong_branch = branch.clone();
+        wrong_branch[3] = H256::from(OsRng.gen::<[u8; 32]>());
+
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let block_root = builder.read::<Bytes32Variable>();
+                let validators_root = builder.read::<Bytes32Variable>();
+                let branch = (0..8)
+                    .map(|_| builder.read::<Bytes32Variable>())
+                    .collect::<Vec<_>>();
+                builder.beacon_verify_validators_root(block_root, validators_root, &branch);
+            },
+            |input| {
+                input.write::<Bytes32Variable>(block_root);
+                input.write::<Bytes32Variable>(validators_root);
+                for sibling in wrong_branch.iter() {
+                    input.write::<Bytes32Variable>(*sibling);
+                }
+            },
+            None,
+        );
+    }
+
+    /// Computes the root of a subtree of validators whose leaves past `length` are empty.
+    fn validator_subtree_root(validators: &[BeaconValidator], length: usize) -> H256 {
+        let mut leaves = validators
+            .iter()
+            .enumerate()
+            .map(|(i, v)| match i < length {
+                true => v.ssz_hash_tree_root(),
+                false => H256::zero(),
+            })
+            .collect::<Vec<_>>();
+        while leaves.len() > 1 {
+            leaves = leaves
+                .chunks(2)
+                .map(|pair| H256::from(sha256(&[pair[0].0, pair[1].0].concat())))
+                .collect();
+        }
+        leaves[0]
+    }
+
+    fn prove_pubkey_absent_from_subtree(
+        validators: &[BeaconValidator],
+        length: usize,
+        pubkey: [u8; 48],
+    ) {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let subtree_root = builder.read::<Bytes32Variable>();
+        let length_var = builder.read::<U64Variable>();
+        let validator_vars = validators
+            .iter()
+            .map(|_| builder.read::<BeaconValidatorVariable>())
+            .collect::<Vec<_>>();
+        let pubkey_var = builder.read::<BLSPubkeyVariable>();
+        builder.beacon_assert_pubkey_absent_from_subtree(
+            subtree_root,
+            length_var,
+            &validator_vars,
+            pubkey_var,
+        );
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(validator_subtree_root(validators, length));
+        input.write::<U64Variable>(length as u64);
+        for validator in validators.iter() {
+            input.write::<BeaconValidatorVariable>(validator.clone());
+        }
+        input.write::<BLSPubkeyVariable>(pubkey);
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    fn registered_validators() -> Vec<BeaconValidator> {
+        (0..4u8)
+            .map(|i| BeaconValidator {
+                pubkey: format!("0x{}", hex::encode([i + 1; 48])),
+                ..validator(1, 32_000_000_000, u64::MAX)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_beacon_assert_pubkey_absent_from_subtree() {
+        let mut rng = OsRng;
+        let pubkey: [u8; 48] = array![_ => rng.gen(); 48];
+
+        // The slot past the length is empty, so the validator witnessed there is ignored even if it
+        // has the pubkey.
+        let mut validators = registered_validators();
+        validators[3].pubkey = format!("0x{}", hex::encode(pubkey));
+        prove_pubkey_absent_from_subtree(&validators, 3, pubkey);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_beacon_assert_pubkey_absent_from_subtree_registered() {
+        let validators = registered_validators();
+        prove_pubkey_absent_from_subtree(&validators, 3, [2; 48]);
+    }
+
+    fn prove_validator_absent(fork: BeaconFork, validators: &[BeaconValidator], pubkey: [u8; 48]) {
+        let balances = vec![32_000_000_000; validators.len()];
+        let (client, block_root) = validators_node_at(fork, validators, &balances);
 
-        let computed_root = builder.ssz_restore_merkle_root(leaf, &branch, index);
-        builder.assert_is_equal(expected_root, computed_root);
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+        builder.set_beacon_config(BeaconConfig {
+            fork,
+            ..BeaconConfig::mainnet()
+        });
+        let block_root = builder.constant::<Bytes32Variable>(block_root);
+        let validators = builder.beacon_get_validators(block_root);
+        let pubkey = builder.constant::<BLSPubkeyVariable>(pubkey);
+        builder.beacon_prove_validator_absent::<4>(validators, pubkey);
 
         let circuit = builder.build();
         let input = circuit.input();
         let (proof, output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
-        circuit.test_default_serializers();
+    }
+
+    #[test]
+    fn test_beacon_prove_validator_absent() {
+        // The list has three validators, so the last slot of the scanned subtree is empty.
+        let validators = registered_validators();
+        for fork in [BeaconFork::Deneb, BeaconFork::Electra] {
+            prove_validator_absent(fork, &validators[..3], [9; 48]);
+        }
     }
 
     #[test]
     #[should_panic]
-    #[cfg_attr(feature = "ci", ignore)]
-    fn test_ssz_restore_merkle_root_unequal() {
-        env_logger::try_init().unwrap_or_default();
-        dotenv::dotenv().ok();
+    fn test_beacon_prove_validator_absent_registered() {
+        let validators = registered_validators();
+        prove_validator_absent(BeaconFork::Deneb, &validators[..3], [2; 48]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_beacon_prove_validator_absent_registry_exceeds_scan() {
+        // The fifth validator is past the scanned subtree, so absence cannot be proven.
+        let mut validators = registered_validators();
+        validators.push(BeaconValidator {
+            pubkey: format!("0x{}", hex::encode([5; 48])),
+            ..validator(1, 32_000_000_000, u64::MAX)
+        });
+        prove_validator_absent(BeaconFork::Deneb, &validators, [9; 48]);
+    }
+
+    #[test]
+    fn test_beacon_is_fully_withdrawable() {
+        const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+        let cases = [
+            // Eligible once the withdrawable epoch is reached, including the boundary.
+            (validator(1, 32_000_000_000, 1000), 1, 1000, true),
+            (validator(1, 32_000_000_000, 1000), 1, 1001, true),
+            // The withdrawable epoch is not reached yet.
+            (validator(1, 32_000_000_000, 1000), 1, 999, false),
+            // 0x00 credentials cannot be withdrawn to.
+            (validator(0, 32_000_000_000, 1000), 1, 1000, false),
+            // Nothing to withdraw.
+            (validator(1, 0, 1000), 0, 1000, false),
+            // The validator has not exited.
+            (
+                validator(1, 32_000_000_000, FAR_FUTURE_EPOCH),
+                1,
+                FAR_FUTURE_EPOCH,
+                false,
+            ),
+        ];
 
         let mut builder = CircuitBuilder::<L, D>::new();
+        for _ in 0..cases.len() {
+            let validator = builder.read::<BeaconValidatorVariable>();
+            let balance = builder.read::<GweiVariable>();
+            let epoch = builder.read::<U256Variable>();
+            let result = builder.beacon_is_fully_withdrawable(validator, balance, epoch);
+            builder.write(result);
+        }
 
-        let leaf = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
-        ));
-        let index = builder.constant::<U64Variable>(2);
-        let branch = vec![
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
-            )),
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
-            )),
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for (validator, balance, epoch, _) in cases.iter() {
+            input.write::<BeaconValidatorVariable>(validator.clone());
+            input.write::<GweiVariable>(*balance);
+            input.write::<U256Variable>((*epoch).into());
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for (i, (_, _, _, expected)) in cases.iter().enumerate() {
+            assert_eq!(output.read::<BoolVariable>(), *expected, "case {}", i);
+        }
+    }
+
+    #[test]
+    fn test_beacon_is_partially_withdrawable() {
+        let cases = [
+            // Eligible with a balance above the maximum effective balance.
+            (validator(1, 32_000_000_000, u64::MAX), 32_000_000_001, true),
+            // No excess balance.
+            (
+                validator(1, 32_000_000_000, u64::MAX),
+                32_000_000_000,
+                false,
+            ),
+            // The effective balance is below the maximum.
+            (
+                validator(1, 31_000_000_000, u64::MAX),
+                32_000_000_001,
+                false,
+            ),
+            // 0x00 credentials cannot be withdrawn to.
+            (
+                validator(0, 32_000_000_000, u64::MAX),
+                32_000_000_001,
+                false,
+            ),
         ];
-        let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xbd0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
-        ));
-        let computed_root = builder.ssz_restore_merkle_root(leaf, &branch, index);
-        builder.assert_is_equal(expected_root, computed_root);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        for _ in 0..cases.len() {
+            let validator = builder.read::<BeaconValidatorVariable>();
+            let balance = builder.read::<GweiVariable>();
+            let result = builder.beacon_is_partially_withdrawable(validator, balance);
+            builder.write(result);
+        }
 
         let circuit = builder.build();
-        let input = circuit.input();
-        let (proof, output) = circuit.prove(&input);
+        let mut input = circuit.input();
+        for (validator, balance, _) in cases.iter() {
+            input.write::<BeaconValidatorVariable>(validator.clone());
+            input.write::<GweiVariable>(*balance);
+        }
+        let (proof, mut output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
-        circuit.test_default_serializers();
+
+        for (i, (_, _, expected)) in cases.iter().enumerate() {
+            assert_eq!(output.read::<BoolVariable>(), *expected, "case {}", i);
+        }
     }
 
     #[test]
-    #[cfg_attr(feature = "ci", ignore)]
-    fn test_ssz_restore_merkle_root_const_equal() {
+    fn test_beacon_config_slot_math() {
         env_logger::try_init().unwrap_or_default();
-        dotenv::dotenv().ok();
 
+        // The historical vector lengths of the minimal preset.
+        let config = BeaconConfig {
+            slots_per_epoch: 16,
+            slots_per_historical_root: 64,
+            epochs_per_historical_vector: 64,
+            seconds_per_slot: 5,
+            genesis_time: 1638993340,
+            capella_fork_epoch: 100,
+            ..BeaconConfig::mainnet()
+        };
         let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_config(config);
+        let slot = builder.read::<U64Variable>();
+        let epoch = builder.beacon_slot_to_epoch(slot);
+        let epoch_start = builder.beacon_epoch_to_slot(epoch);
+        let timestamp = builder.beacon_slot_to_timestamp(slot);
+        let block_roots_gindex = builder.beacon_block_roots_gindex(slot);
+        let randao_mixes_gindex = builder.beacon_randao_mixes_gindex(epoch);
+        builder.write(epoch);
+        builder.write(epoch_start);
+        builder.write(timestamp);
+        builder.write(block_roots_gindex);
+        builder.write(randao_mixes_gindex);
 
-        let leaf = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
-        ));
-        let index = 2;
-        let branch = vec![
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
-            )),
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
-            )),
-        ];
-        let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xac0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
-        ));
-        let computed_root = builder.ssz_restore_merkle_root_const(leaf, &branch, index);
-        builder.assert_is_equal(expected_root, computed_root);
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U64Variable>(1000);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U64Variable>(), 62);
+        assert_eq!(output.read::<U64Variable>(), 992);
+        assert_eq!(output.read::<U64Variable>(), 1638993340 + 5000);
+        // block_roots is the field at gindex 357, and 1000 % 64 = 40.
+        assert_eq!(output.read::<U64Variable>(), 357 * 64 + 40);
+        // randao_mixes is the field at gindex 365, and epoch 62 % 64 = 62.
+        assert_eq!(output.read::<U64Variable>(), 365 * 64 + 62);
+    }
+
+    #[test]
+    fn test_beacon_verify_historical_block_root_minimal_preset() {
+        env_logger::try_init().unwrap_or_default();
+
+        // A block_roots vector of 64 roots has a tree of depth 6.
+        let config = BeaconConfig {
+            slots_per_historical_root: 64,
+            epochs_per_historical_vector: 64,
+            ..BeaconConfig::mainnet()
+        };
+        assert_eq!(config.block_roots_depth(), 6);
+        assert_eq!(config.randao_mixes_depth(), 6);
+
+        let index = 37;
+        let block_root = H256::from(OsRng.gen::<[u8; 32]>());
+        let proof = (0..6)
+            .map(|_| H256::from(OsRng.gen::<[u8; 32]>()))
+            .collect::<Vec<_>>();
+        let block_summary_root = restore_merkle_root(block_root, &proof, 64 + index);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_config(config);
+        let summary = HistoricalSummaryVariable {
+            block_summary_root: builder.constant::<Bytes32Variable>(block_summary_root),
+            state_summary_root: builder.constant::<Bytes32Variable>(H256::zero()),
+        };
+        let index = builder.constant::<U64Variable>(index);
+        let block_root = builder.constant::<Bytes32Variable>(block_root);
+        let proof = proof
+            .iter()
+            .map(|sibling| builder.constant::<Bytes32Variable>(*sibling))
+            .collect::<Vec<_>>();
+        builder.beacon_verify_historical_block_root(summary, index, block_root, &proof);
 
         let circuit = builder.build();
         let input = circuit.input();
         let (proof, output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
-        circuit.test_default_serializers();
+    }
+
+    fn hash_pubkey_list(nb_pubkeys: usize) -> H256 {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let pubkeys = (0..nb_pubkeys)
+            .map(|i| {
+                let pubkey = array![j => ((i * 48 + j) % 256) as u8; 48];
+                builder.constant::<BLSPubkeyVariable>(pubkey)
+            })
+            .collect::<Vec<_>>();
+        let root = builder.hash_pubkey_list(&pubkeys);
+        builder.write(root);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        output.read::<Bytes32Variable>()
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hash_pubkey_list() {
+        env_logger::try_init().unwrap_or_default();
+
+        // The roots of Vector[BLSPubkey, N] over the pubkeys with bytes (48 * i + j) % 256, as
+        // merkleized by the consensus specs. 512 is the size of a sync committee.
+        assert_eq!(
+            hash_pubkey_list(3),
+            bytes32!("0x521fab760d76e6fe52a0dcce2121330e90064110fad00b687abf442bf1d1b92f")
+        );
+        assert_eq!(
+            hash_pubkey_list(512),
+            bytes32!("0xd27011faf0b9041abf3a18491c3e3e911a1cdde418d41df130a93555d29ccc9a")
+        );
     }
 
     #[test]
-    #[should_panic]
     #[cfg_attr(feature = "ci", ignore)]
-    fn test_ssz_restore_merkle_root_const_unequal() {
+    fn test_beacon_assert_none_slashed() {
         env_logger::try_init().unwrap_or_default();
         dotenv::dotenv().ok();
 
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+        let block_root =
+            bytes32!("0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670");
+        let pubkeys = [0, 1000, 2000]
+            .iter()
+            .map(|i| {
+                let witness = client
+                    .get_validator_witness(hex!(block_root.as_bytes()), *i)
+                    .unwrap();
+                assert!(!witness.validator.slashed);
+                witness.validator.pubkey
+            })
+            .collect::<Vec<_>>();
+
         let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+        let block_root = builder.constant::<Bytes32Variable>(block_root);
+        let validators = builder.beacon_get_validators(block_root);
+        let pubkeys = pubkeys
+            .iter()
+            .map(|pubkey| builder.constant::<BLSPubkeyVariable>(bytes!(pubkey)))
+            .collect::<Vec<_>>();
+        builder.beacon_assert_none_slashed(validators, &pubkeys);
 
-        let leaf = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
-        ));
-        let index = 2;
-        let branch = vec![
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
-            )),
-            builder.constant::<Bytes32Variable>(bytes32!(
-                "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
-            )),
-        ];
-        let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
-            "0xbd0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
-        ));
-        let computed_root = builder.ssz_restore_merkle_root_const(leaf, &branch, index);
-        builder.assert_is_equal(expected_root, computed_root);
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_beacon_assert_validators_not_slashed() {
+        env_logger::try_init().unwrap_or_default();
 
+        let clean = [validator(1, 32_000_000_000, 1000), validator(0, 0, 1000)];
+        let mut slashed = clean.clone();
+        slashed[1].slashed = true;
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let set = clean
+            .iter()
+            .map(|v| builder.constant::<BeaconValidatorVariable>(v.clone()))
+            .collect::<Vec<_>>();
+        builder.beacon_assert_validators_not_slashed(&set);
         let circuit = builder.build();
         let input = circuit.input();
         let (proof, output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
-        circuit.test_default_serializers();
+
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let set = slashed
+                    .iter()
+                    .map(|_| builder.read::<BeaconValidatorVariable>())
+                    .collect::<Vec<_>>();
+                builder.beacon_assert_validators_not_slashed(&set);
+            },
+            |input| {
+                for v in slashed.iter() {
+                    input.write::<BeaconValidatorVariable>(v.clone());
+                }
+            },
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "consensus mismatch: the validators root of")]
+    fn test_beacon_clients_agreement_fails_witness() {
+        let block_root = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+        let node = |validators_root: &str| {
+            let body = serde_json::json!({
+                "success": true,
+                "result": {
+                    "validatorsRoot": validators_root,
+                    "gindex": "363",
+                    "depth": 8,
+                    "proof": vec![format!("0x{}", "00".repeat(32)); 8],
+                }
+            });
+            let routes = HashMap::from([(
+                format!("/api/beacon/proof/validator/{}", block_root),
+                body.to_string(),
+            )]);
+            BeaconClient::new(mock::serve_routes(routes))
+        };
+        let primary = node("0x4bba6e5bf53bd1ef1d9b6c9d8a6b6f0e1f2d1c8a58d4f4c9e0b2a7b3c6d5e4f3");
+        let secondary = node("0x5c0b2e29c4f2d8a1e7b6c3d9f0a8e4b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7");
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_clients(primary, secondary.clone(), true);
+
+        // The hints only record that agreement is required, not the urls of the nodes.
+        let nodes = bincode::serialize(&builder.beacon_nodes()).unwrap();
+        let url = secondary.rpc_url().as_bytes();
+        assert!(!nodes.windows(url.len()).any(|w| w == url));
+
+        let block_root = builder.constant::<Bytes32Variable>(bytes32!(block_root));
+        builder.beacon_state(block_root);
+        let circuit = builder.build();
+        let input = circuit.input();
+        circuit.prove(&input);
     }
 }
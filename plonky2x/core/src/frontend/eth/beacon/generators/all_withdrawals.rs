@@ -1,15 +1,13 @@
-use std::env;
-
 use async_trait::async_trait;
 use ethers::types::{H160, U256};
 use log::debug;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::frontend::eth::beacon::vars::{BeaconWithdrawalValue, BeaconWithdrawalVariable};
 use crate::frontend::hint::asynchronous::hint::AsyncHint;
 use crate::frontend::vars::ValueStream;
 use crate::prelude::{ArrayVariable, Bytes32Variable, PlonkParameters};
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::hex;
 
 const MAX_WITHDRAWALS_PER_PAYLOAD: usize = 16;
@@ -17,16 +15,20 @@ const MAX_WITHDRAWALS_PER_PAYLOAD: usize = 16;
 /// Input: (block_root: bytes32)
 /// Output: (withdrawals: ArrayVariable<BeaconWithdrawalVariable, MAX_WITHDRAWALS_PER_PAYLOAD>)
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconAllWithdrawalsHint {}
+pub struct BeaconAllWithdrawalsHint {
+    pub nodes: BeaconNodes,
+}
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconAllWithdrawalsHint {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
         output_stream: &mut ValueStream<L, D>,
     ) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
 
         let block_root = input_stream.read_value::<Bytes32Variable>();
         let withdrawals_res = client
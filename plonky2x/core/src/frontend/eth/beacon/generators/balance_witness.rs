@@ -1,20 +1,22 @@
-use std::env;
-
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::ValueStream;
 use crate::prelude::{ArrayVariable, Bytes32Variable, PlonkParameters};
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::hex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconBalanceWitnessHint {}
+pub struct BeaconBalanceWitnessHint {
+    pub nodes: BeaconNodes,
+}
 
 impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconBalanceWitnessHint {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let validator_index = input_stream.read_value::<U64Variable>();
 
@@ -27,13 +29,17 @@ impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconBalanceWitnessH
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconBalanceBatchWitnessHint<const B: usize> {}
+pub struct BeaconBalanceBatchWitnessHint<const B: usize> {
+    pub nodes: BeaconNodes,
+}
 
 impl<L: PlonkParameters<D>, const D: usize, const B: usize> Hint<L, D>
     for BeaconBalanceBatchWitnessHint<B>
 {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let start_idx = input_stream.read_value::<U64Variable>();
         let response = client
@@ -1,7 +1,6 @@
 use core::marker::PhantomData;
 use std::env;
 
-use array_macro::array;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::PartitionWitness;
@@ -14,14 +13,13 @@ use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
 use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
-const DEPTH: usize = 8;
-
 #[derive(Debug, Clone)]
 pub struct BeaconBalancesGenerator<L: PlonkParameters<D>, const D: usize> {
     client: BeaconClient,
     block_root: Bytes32Variable,
     pub balances_root: Bytes32Variable,
-    pub proof: [Bytes32Variable; DEPTH],
+    /// The proof of the balances root, whose depth depends on the fork.
+    pub proof: Vec<Bytes32Variable>,
     _phantom: PhantomData<L>,
 }
 
@@ -30,12 +28,15 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconBalancesGenerator<L, D> {
         builder: &mut CircuitBuilder<L, D>,
         client: BeaconClient,
         block_root: Bytes32Variable,
+        depth: usize,
     ) -> Self {
         Self {
             client,
             block_root,
             balances_root: builder.init_unsafe::<Bytes32Variable>(),
-            proof: array![_ => builder.init_unsafe::<Bytes32Variable>(); DEPTH],
+            proof: (0..depth)
+                .map(|_| builder.init_unsafe::<Bytes32Variable>())
+                .collect(),
             _phantom: Default::default(),
         }
     }
@@ -70,8 +71,13 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
 
         self.balances_root
             .set(out_buffer, bytes32!(result.balances_root));
-        for i in 0..DEPTH {
-            self.proof[i].set(out_buffer, bytes32!(result.proof[i]));
+        assert_eq!(
+            result.proof.len(),
+            self.proof.len(),
+            "balances root proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+        for (proof, value) in self.proof.iter().zip(result.proof.iter()) {
+            proof.set(out_buffer, bytes32!(value));
         }
     }
 
@@ -83,8 +89,9 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     ) -> IoResult<()> {
         dst.write_target_vec(&self.block_root.targets())?;
         dst.write_target_vec(&self.balances_root.targets())?;
-        for i in 0..DEPTH {
-            dst.write_target_vec(&self.proof[i].targets())?;
+        dst.write_usize(self.proof.len())?;
+        for proof in self.proof.iter() {
+            dst.write_target_vec(&proof.targets())?;
         }
         Ok(())
     }
@@ -96,8 +103,9 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     ) -> IoResult<Self> {
         let block_root = Bytes32Variable::from_targets(&src.read_target_vec()?);
         let balances_root = Bytes32Variable::from_targets(&src.read_target_vec()?);
+        let depth = src.read_usize()?;
         let mut proof = Vec::new();
-        for i in 0..DEPTH {
+        for _ in 0..depth {
             proof.push(Bytes32Variable::from_targets(&src.read_target_vec()?));
         }
         let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
@@ -106,7 +114,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
             client,
             block_root,
             balances_root,
-            proof: proof.try_into().unwrap(),
+            proof,
             _phantom: Default::default(),
         })
     }
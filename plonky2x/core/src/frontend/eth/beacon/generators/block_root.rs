@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use super::BeaconNodes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{Bytes32Variable, ValueStream};
+use crate::utils::bytes32;
+use crate::utils::eth::beacon::BeaconStateId;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconBlockRootHint {
+    pub state_id: BeaconStateId,
+    /// The nodes to resolve the state id with.
+    pub nodes: BeaconNodes,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconBlockRootHint {
+    const NETWORK: bool = true;
+
+    fn hint(&self, _: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let block_root = self.nodes.client().get_block_root(self.state_id).unwrap();
+        output_stream.write_value::<Bytes32Variable>(bytes32!(block_root));
+    }
+}
@@ -1,36 +1,43 @@
-use std::env;
-
-use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::vars::{Bytes32Variable, ValueStream};
-use crate::prelude::ArrayVariable;
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
-const NB_BLOCK_ROOTS: usize = 8192;
-const DEPTH: usize = 8;
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconBlockRootsHint;
+pub struct BeaconBlockRootsHint {
+    /// The depth of the proof from blockRoot -> blockRoots, which depends on the fork.
+    pub depth: usize,
+    /// The length of the block roots vector, SLOTS_PER_HISTORICAL_ROOT.
+    pub length: usize,
+    pub nodes: BeaconNodes,
+}
 
 impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconBlockRootsHint {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let response = client.get_block_roots(hex!(header_root)).unwrap();
         output_stream.write_value::<Bytes32Variable>(bytes32!(response.block_roots_root));
-        output_stream.write_value::<ArrayVariable<Bytes32Variable, DEPTH>>(
-            response.proof.iter().map(|p| bytes32!(p)).collect_vec(),
+        assert_eq!(
+            response.proof.len(),
+            self.depth,
+            "block roots proof has an unexpected depth, is the builder's fork set correctly?"
         );
-        output_stream.write_value::<ArrayVariable<Bytes32Variable, NB_BLOCK_ROOTS>>(
-            response
-                .block_roots
-                .iter()
-                .map(|p| bytes32!(p))
-                .collect_vec(),
+        for proof in response.proof.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(proof));
+        }
+        assert_eq!(
+            response.block_roots.len(),
+            self.length,
+            "block roots vector has an unexpected length, is the builder's config set correctly?"
         );
+        for block_root in response.block_roots.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(block_root));
+        }
     }
 }
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use ethers::types::U256;
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use super::BeaconNodes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::eth::beacon::vars::{
+    ExecutionPayloadHeaderValue, ExecutionPayloadHeaderVariable,
+};
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{Bytes32Variable, ValueStream};
+use crate::prelude::ArrayVariable;
+use crate::utils::eth::beacon::BeaconExecutionPayloadHeader;
+use crate::utils::{address, bytes32, hex};
+
+/// The depth of the proof from blockRoot -> body.executionPayload.
+pub const EXECUTION_PAYLOAD_PROOF_DEPTH: usize = 7;
+
+fn parse_header<F: RichField>(
+    header: &BeaconExecutionPayloadHeader,
+) -> ExecutionPayloadHeaderValue<F> {
+    ExecutionPayloadHeaderValue {
+        parent_hash: bytes32!(header.parent_hash),
+        fee_recipient: address!(header.fee_recipient),
+        state_root: bytes32!(header.state_root),
+        receipts_root: bytes32!(header.receipts_root),
+        logs_bloom_root: header.logs_bloom_root().unwrap(),
+        prev_randao: bytes32!(header.prev_randao),
+        block_number: header.block_number.parse().unwrap(),
+        gas_limit: header.gas_limit.parse().unwrap(),
+        gas_used: header.gas_used.parse().unwrap(),
+        timestamp: header.timestamp.parse().unwrap(),
+        extra_data_root: header.extra_data_root().unwrap(),
+        base_fee_per_gas: U256::from_dec_str(&header.base_fee_per_gas).unwrap(),
+        block_hash: bytes32!(header.block_hash),
+        transactions_root: bytes32!(header.transactions_root),
+        withdrawals_root: bytes32!(header.withdrawals_root),
+        blob_gas_used: header.blob_gas_used.parse().unwrap(),
+        excess_blob_gas: header.excess_blob_gas.parse().unwrap(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconExecutionPayloadHeaderHint {
+    pub nodes: BeaconNodes,
+}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconExecutionPayloadHeaderHint {
+    const NETWORK: bool = true;
+
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = self.nodes.client();
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let response = client
+            .get_execution_payload_header(hex!(block_root))
+            .unwrap();
+        output_stream.write_value::<ExecutionPayloadHeaderVariable>(parse_header(&response.header));
+        output_stream.write_value::<ArrayVariable<Bytes32Variable, EXECUTION_PAYLOAD_PROOF_DEPTH>>(
+            response.proof,
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use super::BeaconNodes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{Bytes32Variable, ValueStream};
+use crate::utils::hex;
+
+/// Input: (block_root: bytes32)
+/// Output: (genesis_validators_root: bytes32, proof: [bytes32; depth])
+///
+/// The proof is from the state root of the block to the genesis validators root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconGenesisValidatorsRootHint {
+    /// The depth of the state tree, which is one more from Electra on.
+    pub depth: usize,
+    pub nodes: BeaconNodes,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconGenesisValidatorsRootHint {
+    const NETWORK: bool = true;
+
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = self.nodes.client();
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let response = client
+            .get_genesis_validators_root(hex!(block_root.as_bytes()))
+            .expect("failed to get genesis validators root");
+        assert_eq!(
+            response.proof.len(),
+            self.depth,
+            "genesis validators root proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+        output_stream.write_value::<Bytes32Variable>(response.genesis_validators_root);
+        for sibling in response.proof {
+            output_stream.write_value::<Bytes32Variable>(sibling);
+        }
+    }
+}
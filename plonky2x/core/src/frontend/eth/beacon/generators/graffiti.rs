@@ -1,25 +1,27 @@
-use std::env;
-
 use async_trait::async_trait;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::vars::{Bytes32Variable, ValueStream};
 use crate::prelude::ArrayVariable;
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 const DEPTH: usize = 7;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconGraffitiHint;
+pub struct BeaconGraffitiHint {
+    pub nodes: BeaconNodes,
+}
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconGraffitiHint {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let response = client.get_graffiti(hex!(header_root)).unwrap();
         output_stream.write_value::<Bytes32Variable>(bytes32!(response.graffiti));
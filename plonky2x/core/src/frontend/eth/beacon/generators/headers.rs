@@ -1,24 +1,26 @@
-use std::env;
-
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{Bytes32Variable, ValueStream};
 use crate::prelude::ArrayVariable;
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconHeadersFromOffsetRangeHint<const B: usize>;
+pub struct BeaconHeadersFromOffsetRangeHint<const B: usize> {
+    pub nodes: BeaconNodes,
+}
 
 impl<L: PlonkParameters<D>, const D: usize, const B: usize> Hint<L, D>
     for BeaconHeadersFromOffsetRangeHint<B>
 {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let start_offset = input_stream.read_value::<U64Variable>();
         let end_offset = input_stream.read_value::<U64Variable>() + 1;
@@ -1,25 +1,32 @@
-use std::env;
-
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::hint::asynchronous::hint::AsyncHint;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::Bytes32Variable;
-use crate::prelude::{ArrayVariable, ValueStream};
-use crate::utils::eth::beacon::BeaconClient;
+use crate::prelude::ValueStream;
 use crate::utils::{bytes32, hex};
 
-pub const CLOSE_SLOT_BLOCK_ROOT_DEPTH: usize = 21;
-pub const FAR_SLOT_HISTORICAL_SUMMARY_DEPTH: usize = 33;
-pub const FAR_SLOT_BLOCK_ROOT_DEPTH: usize = 14;
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconHistoricalBlockHint {}
+pub struct BeaconHistoricalBlockHint {
+    /// The depth of the proof from blockRoot -> state.block_roots[i], which depends on the fork
+    /// and on SLOTS_PER_HISTORICAL_ROOT.
+    pub close_slot_block_root_depth: usize,
+    /// The depth of the proof from historicalSummary -> block_summary_root.block_roots[i], which
+    /// depends on SLOTS_PER_HISTORICAL_ROOT.
+    pub far_slot_block_root_depth: usize,
+    /// The depth of the proof from blockRoot -> state.historical_summaries[i], which depends on
+    /// the fork.
+    pub far_slot_historical_summary_depth: usize,
+    pub nodes: BeaconNodes,
+}
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconHistoricalBlockHint {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
@@ -28,36 +35,38 @@ impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconHistorical
         let block_root = input_stream.read_value::<Bytes32Variable>();
         let target_slot = input_stream.read_value::<U64Variable>();
 
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let result = client
             .get_historical_block(hex!(block_root.as_bytes()).to_string(), target_slot)
             .await
             .expect("failed to get historical block root");
 
         output_stream.write_value::<Bytes32Variable>(bytes32!(result.target_block_root));
-        output_stream.write_value::<ArrayVariable<Bytes32Variable, CLOSE_SLOT_BLOCK_ROOT_DEPTH>>(
-            result
-                .close_slot_block_root_proof
-                .iter()
-                .map(|x| bytes32!(*x))
-                .collect(),
+        assert_eq!(
+            result.close_slot_block_root_proof.len(),
+            self.close_slot_block_root_depth,
+            "block roots proof has an unexpected depth, is the builder's config set correctly?"
         );
-        output_stream.write_value::<ArrayVariable<Bytes32Variable, FAR_SLOT_BLOCK_ROOT_DEPTH>>(
-            result
-                .far_slot_block_root_proof
-                .iter()
-                .map(|x| bytes32!(*x))
-                .collect(),
+        for proof in result.close_slot_block_root_proof.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(*proof));
+        }
+        assert_eq!(
+            result.far_slot_block_root_proof.len(),
+            self.far_slot_block_root_depth,
+            "far slot block root proof has an unexpected depth, is the builder's config set correctly?"
         );
+        for proof in result.far_slot_block_root_proof.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(*proof));
+        }
         output_stream
             .write_value::<Bytes32Variable>(bytes32!(result.far_slot_historical_summary_root));
-        output_stream
-            .write_value::<ArrayVariable<Bytes32Variable, FAR_SLOT_HISTORICAL_SUMMARY_DEPTH>>(
-                result
-                    .far_slot_historical_summary_proof
-                    .iter()
-                    .map(|x| bytes32!(*x))
-                    .collect(),
-            );
+        assert_eq!(
+            result.far_slot_historical_summary_proof.len(),
+            self.far_slot_historical_summary_depth,
+            "historical summary proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+        for proof in result.far_slot_historical_summary_proof.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(*proof));
+        }
     }
 }
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::BeaconNodes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::eth::beacon::vars::{HistoricalSummaryValue, HistoricalSummaryVariable};
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, ValueStream};
+use crate::utils::eth::beacon::BeaconConfig;
+use crate::utils::hex;
+
+/// Fetches the historical summary at an index, which covers the period starting at
+/// `capella_fork_slot + index * SLOTS_PER_HISTORICAL_ROOT` under the builder's config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconHistoricalSummaryHint {
+    pub config: BeaconConfig,
+    /// The depth of the proof from blockRoot -> state.historical_summaries[i], which depends on
+    /// the fork.
+    pub depth: usize,
+    pub nodes: BeaconNodes,
+}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconHistoricalSummaryHint {
+    const NETWORK: bool = true;
+
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = self.nodes.client();
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let index = input_stream.read_value::<U64Variable>();
+        let response = client
+            .get_historical_summary(hex!(block_root), index, &self.config)
+            .unwrap();
+        let summary = response.historical_summary;
+        output_stream.write_value::<HistoricalSummaryVariable>(HistoricalSummaryValue {
+            block_summary_root: summary.block_summary_root,
+            state_summary_root: summary.state_summary_root,
+        });
+        assert_eq!(
+            response.proof.len(),
+            self.depth,
+            "historical summary proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+        for proof in response.proof {
+            output_stream.write_value::<Bytes32Variable>(proof);
+        }
+    }
+}
@@ -2,33 +2,47 @@ mod all_withdrawals;
 mod balance;
 mod balance_witness;
 mod balances;
+mod block_root;
 mod block_roots;
+mod execution_payload_header;
+mod genesis_validators_root;
 mod graffiti;
 mod header;
 mod headers;
 mod historical;
+mod historical_summary;
+mod nodes;
 mod partial_balances;
 mod partial_validators;
+mod randao_mix;
+mod sync_committee;
 mod validator;
 mod validator_subtree;
 mod validator_witness;
 mod validators;
+mod validators_and_balances;
 mod withdrawal;
 mod withdrawals;
 pub use all_withdrawals::BeaconAllWithdrawalsHint;
 pub use balance::BeaconBalanceGenerator;
 pub use balance_witness::{BeaconBalanceBatchWitnessHint, BeaconBalanceWitnessHint};
 pub use balances::BeaconBalancesGenerator;
+pub use block_root::BeaconBlockRootHint;
 pub use block_roots::BeaconBlockRootsHint;
+pub use execution_payload_header::{
+    BeaconExecutionPayloadHeaderHint, EXECUTION_PAYLOAD_PROOF_DEPTH,
+};
+pub use genesis_validators_root::BeaconGenesisValidatorsRootHint;
 pub use graffiti::BeaconGraffitiHint;
 pub use header::BeaconHeaderHint;
 pub use headers::BeaconHeadersFromOffsetRangeHint;
-pub use historical::{
-    BeaconHistoricalBlockHint, CLOSE_SLOT_BLOCK_ROOT_DEPTH, FAR_SLOT_BLOCK_ROOT_DEPTH,
-    FAR_SLOT_HISTORICAL_SUMMARY_DEPTH,
-};
+pub use historical::BeaconHistoricalBlockHint;
+pub use historical_summary::BeaconHistoricalSummaryHint;
+pub use nodes::{BeaconNodes, CONSENSUS_AGREEMENT_RPC_URL};
 pub use partial_balances::BeaconPartialBalancesHint;
 pub use partial_validators::BeaconPartialValidatorsHint;
+pub use randao_mix::BeaconRandaoMixHint;
+pub use sync_committee::BeaconSyncCommitteeHint;
 pub use validator::BeaconValidatorGenerator;
 pub use validator_subtree::{
     BeaconValidatorSubtreeHint, BeaconValidatorSubtreePoseidonHint, BeaconValidatorSubtreesHint,
@@ -37,5 +51,9 @@ pub use validator_witness::{
     BeaconValidatorBatchHint, BeaconValidatorHint, CompressedBeaconValidatorBatchHint,
 };
 pub use validators::{BeaconValidatorsGenerator, BeaconValidatorsHint};
+pub use validators_and_balances::{
+    BeaconValidatorsAndBalancesHint, VALIDATORS_AND_BALANCES_BRANCH_DEPTH,
+    VALIDATORS_AND_BALANCES_SHARED_DEPTH,
+};
 pub use withdrawal::BeaconWithdrawalGenerator;
 pub use withdrawals::BeaconWithdrawalsGenerator;
@@ -0,0 +1,90 @@
+use std::env;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::eth::beacon::{BeaconClient, BeaconHeader, GetBeaconValidatorsRoot};
+
+/// The env var of the second node that a circuit read back from disk checks its leaves against.
+pub const CONSENSUS_AGREEMENT_RPC_URL: &str = "CONSENSUS_AGREEMENT_RPC_URL";
+
+/// The beacon nodes that the network hints fetch their responses from.
+///
+/// The clients are taken from the builder, so they keep their auth header, timeout and retries.
+/// They are never serialized since their urls may carry credentials: a circuit read back from
+/// disk only records whether agreement is required, and connects to `CONSENSUS_RPC_URL` and
+/// [`CONSENSUS_AGREEMENT_RPC_URL`] instead.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BeaconNodes {
+    #[serde(skip)]
+    primary: Option<BeaconClient>,
+    #[serde(skip)]
+    agreement: Option<BeaconClient>,
+    require_agreement: bool,
+}
+
+impl BeaconNodes {
+    pub fn new(primary: Option<BeaconClient>, agreement: Option<BeaconClient>) -> Self {
+        Self {
+            primary,
+            require_agreement: agreement.is_some(),
+            agreement,
+        }
+    }
+
+    /// The nodes of a circuit read back from disk, which are configured through the env.
+    pub fn from_env(require_agreement: bool) -> Self {
+        Self {
+            primary: None,
+            agreement: None,
+            require_agreement,
+        }
+    }
+
+    /// Whether the leaves must be confirmed by a second node.
+    pub fn require_agreement(&self) -> bool {
+        self.require_agreement
+    }
+
+    /// The client of the primary node, which serves every leaf that is not cross-checked.
+    pub fn client(&self) -> BeaconClient {
+        self.primary
+            .clone()
+            .unwrap_or_else(|| BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap()))
+    }
+
+    fn agreement(&self) -> Option<BeaconClient> {
+        if !self.require_agreement {
+            return None;
+        }
+        let client = self.agreement.clone().unwrap_or_else(|| {
+            let rpc_url = env::var(CONSENSUS_AGREEMENT_RPC_URL).unwrap_or_else(|_| {
+                panic!(
+                    "the circuit requires beacon node agreement but {} is not set",
+                    CONSENSUS_AGREEMENT_RPC_URL
+                )
+            });
+            BeaconClient::new(rpc_url)
+        });
+        Some(client)
+    }
+
+    /// Gets the validators root, checked against the second node if agreement is required.
+    pub fn get_validators_root(&self, beacon_id: String) -> Result<GetBeaconValidatorsRoot> {
+        let client = self.client();
+        match self.agreement() {
+            Some(other) => client.get_validators_root_agreed(&other, beacon_id),
+            None => client.get_validators_root(beacon_id),
+        }
+    }
+
+    /// Gets the header, whose state root is checked against the second node if agreement is
+    /// required.
+    pub async fn get_header(&self, beacon_id: String) -> Result<BeaconHeader> {
+        let client = self.client();
+        match self.agreement() {
+            Some(other) => client.get_header_agreed(&other, beacon_id).await,
+            None => client.get_header(beacon_id).await,
+        }
+    }
+}
@@ -1,41 +1,45 @@
-use std::env;
-
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::hint::asynchronous::hint::AsyncHint;
 use crate::frontend::vars::{Bytes32Variable, ValueStream};
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
-const VALIDATOR_REGISTRY_LIMIT_LOG2: usize = 40;
-const DEPTH: usize = 8;
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconPartialValidatorsHint<const B: usize> {}
+pub struct BeaconPartialValidatorsHint<const B: usize> {
+    /// The depth of the proof of the root of the first B validators, which depends on the fork.
+    pub depth: usize,
+    /// The nodes to fetch the root of the first B validators from.
+    pub nodes: BeaconNodes,
+}
 
 #[async_trait]
 
 impl<L: PlonkParameters<D>, const D: usize, const B: usize> AsyncHint<L, D>
     for BeaconPartialValidatorsHint<B>
 {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
         output_stream: &mut ValueStream<L, D>,
     ) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let response = client
             .get_partial_validators_root(hex!(header_root), B)
             .await
             .unwrap();
         output_stream.write_value::<Bytes32Variable>(bytes32!(response.partial_validators_root));
-        let nb_branches =
-            DEPTH + (VALIDATOR_REGISTRY_LIMIT_LOG2 + 1 - ((B as f64).log2().ceil() as usize));
-        assert_eq!(response.proof.len(), nb_branches);
-        for i in 0..nb_branches {
+        assert_eq!(
+            response.proof.len(),
+            self.depth,
+            "partial validators proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+        for i in 0..self.depth {
             output_stream.write_value::<Bytes32Variable>(bytes32!(response.proof[i]));
         }
     }
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::BeaconNodes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, ValueStream};
+use crate::utils::{bytes32, hex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconRandaoMixHint {
+    /// The depth of the proof from blockRoot -> randaoMixes[i], which depends on the fork.
+    pub depth: usize,
+    pub nodes: BeaconNodes,
+}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconRandaoMixHint {
+    const NETWORK: bool = true;
+
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = self.nodes.client();
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let epoch = input_stream.read_value::<U64Variable>();
+        let response = client.get_randao_mix(hex!(block_root), epoch).unwrap();
+        output_stream.write_value::<Bytes32Variable>(bytes32!(response.randao_mix));
+        assert_eq!(
+            response.proof.len(),
+            self.depth,
+            "randao mix proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+        for proof in response.proof.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(proof));
+        }
+    }
+}
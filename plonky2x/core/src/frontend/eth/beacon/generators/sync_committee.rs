@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use super::BeaconNodes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::eth::beacon::vars::{BeaconSyncCommitteeValue, BeaconSyncCommitteeVariable};
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{Bytes32Variable, ValueStream};
+use crate::utils::{bytes, bytes32, hex};
+
+/// Input: (block_root: bytes32)
+/// Output: (current_sync_committee: SyncCommittee, proof: [bytes32; depth])
+///
+/// The proof is from the state root of the block to the current sync committee, as served by the
+/// light client bootstrap of the block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconSyncCommitteeHint {
+    /// The depth of the state tree, which is one more from Electra on.
+    pub depth: usize,
+    pub nodes: BeaconNodes,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconSyncCommitteeHint {
+    const NETWORK: bool = true;
+
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = self.nodes.client();
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let bootstrap = client
+            .get_light_client_bootstrap(hex!(block_root.as_bytes()))
+            .expect("failed to get light client bootstrap");
+        assert_eq!(
+            bootstrap.current_sync_committee_branch.len(),
+            self.depth,
+            "sync committee proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+
+        let committee = bootstrap.current_sync_committee;
+        let pubkeys: Vec<[u8; 48]> = committee
+            .pubkeys
+            .iter()
+            .map(|p| bytes!(p.as_str()))
+            .collect();
+        output_stream.write_value::<BeaconSyncCommitteeVariable>(BeaconSyncCommitteeValue {
+            pubkeys,
+            aggregate_pubkey: bytes!(committee.aggregate_pubkey.as_str()),
+        });
+        for sibling in bootstrap.current_sync_committee_branch.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(sibling));
+        }
+    }
+}
@@ -1,30 +1,32 @@
-use std::env;
-
 use async_trait::async_trait;
 use ethers::types::U256;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::frontend::eth::beacon::vars::BeaconValidatorVariable;
 use crate::frontend::hint::asynchronous::hint::AsyncHint;
 use crate::prelude::{
     BoolVariable, Bytes32Variable, PlonkParameters, U256Variable, U64Variable, ValueStream,
 };
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconValidatorSubtreesHint<const B: usize, const N: usize> {}
+pub struct BeaconValidatorSubtreesHint<const B: usize, const N: usize> {
+    pub nodes: BeaconNodes,
+}
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize, const B: usize, const N: usize> AsyncHint<L, D>
     for BeaconValidatorSubtreesHint<B, N>
 {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
         output_stream: &mut ValueStream<L, D>,
     ) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let block_root = input_stream.read_value::<Bytes32Variable>();
 
         let response = client
@@ -39,18 +41,22 @@ impl<L: PlonkParameters<D>, const D: usize, const B: usize, const N: usize> Asyn
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconValidatorSubtreeHint<const B: usize, const N: usize> {}
+pub struct BeaconValidatorSubtreeHint<const B: usize, const N: usize> {
+    pub nodes: BeaconNodes,
+}
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize, const B: usize, const N: usize> AsyncHint<L, D>
     for BeaconValidatorSubtreeHint<B, N>
 {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
         output_stream: &mut ValueStream<L, D>,
     ) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let block_root = input_stream.read_value::<Bytes32Variable>();
 
         let response = client
@@ -65,18 +71,22 @@ impl<L: PlonkParameters<D>, const D: usize, const B: usize, const N: usize> Asyn
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconValidatorSubtreePoseidonHint<const B: usize> {}
+pub struct BeaconValidatorSubtreePoseidonHint<const B: usize> {
+    pub nodes: BeaconNodes,
+}
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize, const B: usize> AsyncHint<L, D>
     for BeaconValidatorSubtreePoseidonHint<B>
 {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
         output_stream: &mut ValueStream<L, D>,
     ) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let block_root = input_stream.read_value::<Bytes32Variable>();
         let withdrawal_credentials = input_stream.read_value::<Bytes32Variable>();
         let start_index = input_stream.read_value::<U64Variable>();
@@ -1,8 +1,7 @@
-use std::env;
-
 use ethers::types::U256;
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::frontend::eth::beacon::vars::{
     BeaconValidatorVariable, CompressedBeaconValidatorValue, CompressedBeaconValidatorVariable,
 };
@@ -10,15 +9,18 @@ use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::ValueStream;
 use crate::prelude::{ArrayVariable, Bytes32Variable, PlonkParameters};
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconValidatorHint {}
+pub struct BeaconValidatorHint {
+    pub nodes: BeaconNodes,
+}
 
 impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconValidatorHint {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let validator_index = input_stream.read_value::<U64Variable>();
 
@@ -31,13 +33,18 @@ impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconValidatorHint {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconValidatorBatchHint<const B: usize> {}
+pub struct BeaconValidatorBatchHint<const B: usize> {
+    /// The nodes to fetch the validators from.
+    pub nodes: BeaconNodes,
+}
 
 impl<L: PlonkParameters<D>, const D: usize, const B: usize> Hint<L, D>
     for BeaconValidatorBatchHint<B>
 {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let start_idx = input_stream.read_value::<U64Variable>();
         let response = client
@@ -51,13 +58,17 @@ impl<L: PlonkParameters<D>, const D: usize, const B: usize> Hint<L, D>
 const ZERO_VALIDATOR_PUBKEY: &str = "0x111111000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CompressedBeaconValidatorBatchHint<const B: usize>;
+pub struct CompressedBeaconValidatorBatchHint<const B: usize> {
+    pub nodes: BeaconNodes,
+}
 
 impl<L: PlonkParameters<D>, const D: usize, const B: usize> Hint<L, D>
     for CompressedBeaconValidatorBatchHint<B>
 {
+    const NETWORK: bool = true;
+
     fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
-        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let client = self.nodes.client();
         let header_root = input_stream.read_value::<Bytes32Variable>();
         let start_idx = input_stream.read_value::<U64Variable>();
         let response = client
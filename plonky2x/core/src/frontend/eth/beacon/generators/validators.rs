@@ -1,5 +1,4 @@
 use core::marker::PhantomData;
-use std::env;
 
 use async_trait::async_trait;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
@@ -9,32 +8,54 @@ use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 use serde::{Deserialize, Serialize};
 
+use super::BeaconNodes;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::hint::asynchronous::hint::AsyncHint;
+use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{Bytes32Variable, CircuitVariable, ValueStream};
-use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 pub(crate) const DEPTH: usize = 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaconValidatorsHint {}
+pub struct BeaconValidatorsHint {
+    /// The depth of the proof of the validators root, which is one more from Electra on.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+    /// The nodes to fetch the validators root from, which must agree on it if required.
+    pub nodes: BeaconNodes,
+}
+
+fn default_depth() -> usize {
+    DEPTH
+}
 
 impl BeaconValidatorsHint {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            nodes: BeaconNodes::default(),
+        }
+    }
+
+    /// Fetches the validators root from the given nodes.
+    pub fn with_nodes(mut self, nodes: BeaconNodes) -> Self {
+        self.nodes = nodes;
+        self
     }
 }
 
 impl Default for BeaconValidatorsHint {
     fn default() -> Self {
-        Self::new()
+        Self::new(DEPTH)
     }
 }
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconValidatorsHint {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
@@ -42,7 +63,8 @@ impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconValidators
     ) {
         let block_root = input_stream.read_value::<Bytes32Variable>();
 
-        let result = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap())
+        let result = self
+            .nodes
             .get_validators_root(hex!(block_root.as_bytes()).to_string())
             .expect("failed to get validators root");
 
@@ -50,28 +72,49 @@ impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconValidators
         output_stream.write_value::<Bytes32Variable>(bytes32!(result.validators_root));
 
         // write proof
-        for i in 0..DEPTH {
+        assert_eq!(
+            result.proof.len(),
+            self.depth,
+            "validators root proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+        for i in 0..self.depth {
             output_stream.write_value::<Bytes32Variable>(bytes32!(result.proof[i]));
         }
+
+        let list_node = self
+            .nodes
+            .client()
+            .get_validators_list_node(hex!(block_root.as_bytes()).to_string())
+            .expect("failed to get validators list length");
+
+        // write merkle root and list length
+        output_stream.write_value::<Bytes32Variable>(list_node.merkle_root);
+        output_stream.write_value::<U64Variable>(list_node.length);
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct BeaconValidatorsGenerator<L: PlonkParameters<D>, const D: usize> {
+    nodes: BeaconNodes,
     block_root: Bytes32Variable,
     pub validators_root: Bytes32Variable,
     pub proof: Vec<Bytes32Variable>,
+    pub merkle_root: Bytes32Variable,
+    pub length: U64Variable,
     _phantom: PhantomData<L>,
 }
 
 impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorsGenerator<L, D> {
     pub fn new(builder: &mut CircuitBuilder<L, D>, block_root: Bytes32Variable) -> Self {
         Self {
+            nodes: builder.beacon_nodes(),
             block_root,
             validators_root: builder.init::<Bytes32Variable>(),
             proof: (0..DEPTH)
                 .map(|_| builder.init::<Bytes32Variable>())
                 .collect::<Vec<_>>(),
+            merkle_root: builder.init::<Bytes32Variable>(),
+            length: builder.init::<U64Variable>(),
             _phantom: Default::default(),
         }
     }
@@ -99,15 +142,23 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     ) {
         let block_root = self.block_root.get(witness);
 
-        let result = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap())
+        let result = self
+            .nodes
             .get_validators_root(hex!(block_root.as_bytes()).to_string())
             .expect("failed to get validators root");
+        let list_node = self
+            .nodes
+            .client()
+            .get_validators_list_node(hex!(block_root.as_bytes()).to_string())
+            .expect("failed to get validators list length");
 
         self.validators_root
             .set(out_buffer, bytes32!(result.validators_root));
         for i in 0..DEPTH {
             self.proof[i].set(out_buffer, bytes32!(result.proof[i]));
         }
+        self.merkle_root.set(out_buffer, list_node.merkle_root);
+        self.length.set(out_buffer, list_node.length);
     }
 
     #[allow(unused_variables)]
@@ -121,6 +172,9 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         for i in 0..DEPTH {
             dst.write_target_vec(&self.proof[i].targets())?;
         }
+        dst.write_target_vec(&self.merkle_root.targets())?;
+        dst.write_target_vec(&self.length.targets())?;
+        dst.write_bool(self.nodes.require_agreement())?;
         Ok(())
     }
 
@@ -135,10 +189,16 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         for i in 0..DEPTH {
             proof.push(Bytes32Variable::from_targets(&src.read_target_vec()?));
         }
+        let merkle_root = Bytes32Variable::from_targets(&src.read_target_vec()?);
+        let length = U64Variable::from_targets(&src.read_target_vec()?);
+        let nodes = BeaconNodes::from_env(src.read_bool()?);
         Ok(Self {
+            nodes,
             block_root,
             validators_root,
             proof,
+            merkle_root,
+            length,
             _phantom: Default::default(),
         })
     }
@@ -168,7 +228,7 @@ pub(crate) mod tests {
             "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670"
         ));
         let generator = BeaconValidatorsGenerator::<L, D>::new(&mut builder, block_root);
-        builder.add_simple_generator(generator);
+        builder.add_network_simple_generator(generator);
 
         let circuit = builder.build();
         let pw = PartialWitness::new();
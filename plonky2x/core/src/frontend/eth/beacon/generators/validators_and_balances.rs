@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::BeaconNodes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, ValueStream};
+use crate::prelude::ArrayVariable;
+use crate::utils::{bytes32, hex};
+
+/// The depth of the proofs from the common ancestor of validatorsRoot and balancesRoot to each
+/// of them, which is the same in every fork.
+pub const VALIDATORS_AND_BALANCES_BRANCH_DEPTH: usize = 2;
+
+/// The depth of the proof from blockRoot -> the common ancestor of validatorsRoot and
+/// balancesRoot up to Deneb. It is one more from Electra on.
+pub const VALIDATORS_AND_BALANCES_SHARED_DEPTH: usize = 5;
+
+/// Witnesses the validators root and the balances root of a block together with a multiproof of
+/// both against the block root, followed by the merkle root and the length of the validators
+/// list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconValidatorsAndBalancesHint {
+    /// The depth of the proof from the block root to the common ancestor of both roots.
+    pub shared_depth: usize,
+    /// The nodes to fetch the roots from, which must agree on the validators root if required.
+    pub nodes: BeaconNodes,
+}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconValidatorsAndBalancesHint {
+    const NETWORK: bool = true;
+
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = self.nodes.client();
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let validators = self
+            .nodes
+            .get_validators_root(hex!(block_root.as_bytes()))
+            .expect("failed to get validators root");
+        let balances = client
+            .get_balances_root(hex!(block_root.as_bytes()))
+            .expect("failed to get balances root");
+        let list_node = client
+            .get_validators_list_node(hex!(block_root.as_bytes()))
+            .expect("failed to get validators list length");
+
+        // The proofs of both roots only differ below their common ancestor. The next sibling in
+        // the validators proof is the subtree of the balances root, which the circuit recomputes.
+        let validators_branch = &validators.proof[..VALIDATORS_AND_BALANCES_BRANCH_DEPTH];
+        let balances_branch = &balances.proof[..VALIDATORS_AND_BALANCES_BRANCH_DEPTH];
+        let shared_branch = &validators.proof[VALIDATORS_AND_BALANCES_BRANCH_DEPTH + 1..];
+        assert_eq!(
+            shared_branch.len(),
+            self.shared_depth,
+            "validators root proof has an unexpected depth, is the builder's fork set correctly?"
+        );
+
+        output_stream.write_value::<Bytes32Variable>(bytes32!(validators.validators_root));
+        output_stream.write_value::<Bytes32Variable>(bytes32!(balances.balances_root));
+        output_stream
+            .write_value::<ArrayVariable<Bytes32Variable, VALIDATORS_AND_BALANCES_BRANCH_DEPTH>>(
+                validators_branch.iter().map(|p| bytes32!(p)).collect_vec(),
+            );
+        output_stream
+            .write_value::<ArrayVariable<Bytes32Variable, VALIDATORS_AND_BALANCES_BRANCH_DEPTH>>(
+                balances_branch.iter().map(|p| bytes32!(p)).collect_vec(),
+            );
+        for p in shared_branch.iter() {
+            output_stream.write_value::<Bytes32Variable>(bytes32!(p));
+        }
+        output_stream.write_value::<Bytes32Variable>(list_node.merkle_root);
+        output_stream.write_value::<U64Variable>(list_node.length);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod builder;
 pub mod generators;
+pub mod state;
 pub mod vars;
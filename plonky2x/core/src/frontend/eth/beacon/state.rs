@@ -0,0 +1,263 @@
+use super::generators::{BeaconGenesisValidatorsRootHint, BeaconSyncCommitteeHint};
+use super::vars::{
+    BeaconBalancesVariable, BeaconSyncCommitteeVariable, BeaconValidatorVariable,
+    BeaconValidatorsVariable,
+};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, SSZVariable, VariableStream};
+
+/// The index of genesisValidatorsRoot among the fields of the state.
+const GENESIS_VALIDATORS_ROOT_FIELD: u64 = 1;
+
+/// The index of currentSyncCommittee among the fields of the state.
+const CURRENT_SYNC_COMMITTEE_FIELD: u64 = 22;
+
+/// A handle to the beacon state at a block root.
+///
+/// The roots of the state that the accessors are proven against are verified once, when the
+/// handle is created or on first use, and every query through the handle reuses them instead of
+/// verifying its own path from the block root. Obtain one with [`CircuitBuilder::beacon_state`].
+///
+/// The fields are located in the state tree of the builder's `BeaconConfig::fork`.
+#[derive(Debug, Clone)]
+pub struct BeaconState {
+    block_root: Bytes32Variable,
+    validators: BeaconValidatorsVariable,
+    balances: BeaconBalancesVariable,
+    state_root: Option<Bytes32Variable>,
+    genesis_validators_root: Option<Bytes32Variable>,
+    sync_committee: Option<BeaconSyncCommitteeVariable>,
+}
+
+impl BeaconState {
+    pub(crate) fn new(
+        block_root: Bytes32Variable,
+        validators: BeaconValidatorsVariable,
+        balances: BeaconBalancesVariable,
+    ) -> Self {
+        Self {
+            block_root,
+            validators,
+            balances,
+            state_root: None,
+            genesis_validators_root: None,
+            sync_committee: None,
+        }
+    }
+
+    pub fn block_root(&self) -> Bytes32Variable {
+        self.block_root
+    }
+
+    /// The validators of the state, with a verified validators root.
+    pub fn validators(&self) -> BeaconValidatorsVariable {
+        self.validators
+    }
+
+    /// The balances of the state, with a verified balances root.
+    pub fn balances(&self) -> BeaconBalancesVariable {
+        self.balances
+    }
+
+    /// The state root, verified against the block header the first time it is requested.
+    pub fn state_root<L: PlonkParameters<D>, const D: usize>(
+        &mut self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let block_root = self.block_root;
+        *self
+            .state_root
+            .get_or_insert_with(|| builder.beacon_get_block_header(block_root).state_root)
+    }
+
+    /// The genesis validators root, verified against the state root the first time it is
+    /// requested.
+    pub fn genesis_validators_root<L: PlonkParameters<D>, const D: usize>(
+        &mut self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        if let Some(genesis_validators_root) = self.genesis_validators_root {
+            return genesis_validators_root;
+        }
+        let state_root = self.state_root(builder);
+        let depth = builder.beacon_config.fork.state_depth();
+
+        let mut input = VariableStream::new();
+        input.write(&self.block_root);
+        let output = builder.hint(
+            input,
+            BeaconGenesisValidatorsRootHint {
+                depth,
+                nodes: builder.beacon_nodes(),
+            },
+        );
+        let genesis_validators_root = output.read::<Bytes32Variable>(builder);
+        let proof = (0..depth)
+            .map(|_| output.read::<Bytes32Variable>(builder))
+            .collect::<Vec<_>>();
+        let gindex = (1 << depth) + GENESIS_VALIDATORS_ROOT_FIELD;
+        builder.ssz_verify_proof_const(state_root, genesis_validators_root, &proof, gindex);
+
+        self.genesis_validators_root = Some(genesis_validators_root);
+        genesis_validators_root
+    }
+
+    /// The current sync committee, verified against the state root the first time it is
+    /// requested.
+    ///
+    /// The committee is witnessed from the light client bootstrap of the block, which nodes
+    /// usually only serve for finalized blocks at the start of an epoch.
+    pub fn sync_committee<L: PlonkParameters<D>, const D: usize>(
+        &mut self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> BeaconSyncCommitteeVariable {
+        if let Some(sync_committee) = &self.sync_committee {
+            return sync_committee.clone();
+        }
+        let state_root = self.state_root(builder);
+        let depth = builder.beacon_config.fork.state_depth();
+
+        let mut input = VariableStream::new();
+        input.write(&self.block_root);
+        let output = builder.hint(
+            input,
+            BeaconSyncCommitteeHint {
+                depth,
+                nodes: builder.beacon_nodes(),
+            },
+        );
+        let sync_committee = output.read::<BeaconSyncCommitteeVariable>(builder);
+        let proof = (0..depth)
+            .map(|_| output.read::<Bytes32Variable>(builder))
+            .collect::<Vec<_>>();
+        let root = sync_committee.hash_tree_root(builder);
+        let gindex = (1 << depth) + CURRENT_SYNC_COMMITTEE_FIELD;
+        builder.ssz_verify_proof_const(state_root, root, &proof, gindex);
+
+        self.sync_committee = Some(sync_committee.clone());
+        sync_committee
+    }
+
+    /// Get a validator from a given dynamic index.
+    pub fn get_validator<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+        index: U64Variable,
+    ) -> BeaconValidatorVariable {
+        builder.beacon_get_validator(self.validators, index)
+    }
+
+    /// Get a validator from a given deterministic index.
+    pub fn get_validator_const<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+        index: u64,
+    ) -> BeaconValidatorVariable {
+        builder.beacon_get_validator_const(self.validators, index)
+    }
+
+    /// Get a validator balance from a given dynamic index.
+    pub fn get_balance<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+        index: U64Variable,
+    ) -> U64Variable {
+        builder.beacon_get_balance(self.balances, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::debug;
+
+    use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::builder::CircuitBuilder;
+    use crate::frontend::eth::beacon::generators::VALIDATORS_AND_BALANCES_BRANCH_DEPTH;
+    use crate::frontend::uint::uint64::U64Variable;
+    use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
+    use crate::utils::eth::beacon::{BeaconClient, BeaconConfig, BeaconFork};
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    /// The accessors only need a client when proving, so building works without a node.
+    fn offline_builder() -> CircuitBuilder<L, D> {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(BeaconClient::new("http://localhost:5052".to_string()));
+        builder
+    }
+
+    /// The number of sha256 hashes requested by the builder so far.
+    fn nb_sha256(builder: &CircuitBuilder<L, D>) -> usize {
+        builder
+            .sha256_accelerator
+            .as_ref()
+            .map_or(0, |accelerator| accelerator.hash_requests.len())
+    }
+
+    #[test]
+    fn test_beacon_state_shares_root_proofs() {
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = offline_builder();
+        let block_root = builder.read::<Bytes32Variable>();
+        let index = builder.read::<U64Variable>();
+        let (start_gates, start_hashes) = (builder.num_gates(), nb_sha256(&builder));
+        let state = builder.beacon_state(block_root);
+        state.get_validator(&mut builder, index);
+        state.get_balance(&mut builder, index);
+        let handle_gates = builder.num_gates() - start_gates;
+        let handle_hashes = nb_sha256(&builder) - start_hashes;
+
+        // Both sides prove the length of the validators list, so they only differ in the path
+        // from the common ancestor of the validators and balances roots to the block root.
+        let mut builder = offline_builder();
+        let block_root = builder.read::<Bytes32Variable>();
+        let index = builder.read::<U64Variable>();
+        let (start_gates, start_hashes) = (builder.num_gates(), nb_sha256(&builder));
+        let validators = builder.beacon_get_validators(block_root);
+        let balances = builder.beacon_get_balances(block_root);
+        builder.beacon_get_validator(validators, index);
+        builder.beacon_get_balance(balances, index);
+        let independent_gates = builder.num_gates() - start_gates;
+        let independent_hashes = nb_sha256(&builder) - start_hashes;
+
+        debug!(
+            "validator and balance: handle={} gates {} hashes, independent={} gates {} hashes",
+            handle_gates, handle_hashes, independent_gates, independent_hashes
+        );
+        assert_eq!(
+            independent_hashes - handle_hashes,
+            8 - VALIDATORS_AND_BALANCES_BRANCH_DEPTH
+        );
+        assert!(handle_gates < independent_gates);
+    }
+
+    #[test]
+    fn test_beacon_state_caches_roots() {
+        for fork in [BeaconFork::Deneb, BeaconFork::Electra] {
+            let mut builder = offline_builder();
+            builder.set_beacon_config(BeaconConfig {
+                fork,
+                ..BeaconConfig::mainnet()
+            });
+            let block_root = builder.read::<Bytes32Variable>();
+            let mut state = builder.beacon_state(block_root);
+
+            let genesis_validators_root = state.genesis_validators_root(&mut builder);
+            let sync_committee = state.sync_committee(&mut builder);
+            let start = nb_sha256(&builder);
+            assert_eq!(
+                state.genesis_validators_root(&mut builder).variables(),
+                genesis_validators_root.variables()
+            );
+            assert_eq!(
+                state.sync_committee(&mut builder).variables(),
+                sync_committee.variables()
+            );
+            assert_eq!(nb_sha256(&builder), start, "{:?}", fork);
+        }
+    }
+}
@@ -5,9 +5,13 @@ use plonky2x_derive::CircuitVariable;
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
 use crate::prelude::Variable;
 
+/// An amount of Gwei, as stored in the balances of the beacon state.
+pub type GweiVariable = U64Variable;
+
 #[derive(Debug, Clone, Copy, CircuitVariable)]
 pub struct BeaconBalancesVariable {
     pub block_root: Bytes32Variable,
@@ -0,0 +1,39 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::{CircuitVariable, Ssz};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::uint::uint256::U256Variable;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, CircuitVariable, SSZVariable, Ssz};
+use crate::prelude::Variable;
+
+/// The execution payload header of a beacon block body, in the field order of the Deneb spec.
+/// Reference: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/beacon-chain.md#executionpayloadheader
+///
+/// The variable length `logs_bloom` and `extra_data` fields are represented by their hash tree
+/// roots, which is all that the hash tree root of the header depends on.
+#[derive(Debug, Copy, Clone, CircuitVariable, Ssz)]
+#[value_name(ExecutionPayloadHeaderValue)]
+pub struct ExecutionPayloadHeaderVariable {
+    pub parent_hash: Bytes32Variable,
+    pub fee_recipient: AddressVariable,
+    pub state_root: Bytes32Variable,
+    pub receipts_root: Bytes32Variable,
+    pub logs_bloom_root: Bytes32Variable,
+    pub prev_randao: Bytes32Variable,
+    pub block_number: U64Variable,
+    pub gas_limit: U64Variable,
+    pub gas_used: U64Variable,
+    pub timestamp: U64Variable,
+    pub extra_data_root: Bytes32Variable,
+    pub base_fee_per_gas: U256Variable,
+    pub block_hash: Bytes32Variable,
+    pub transactions_root: Bytes32Variable,
+    pub withdrawals_root: Bytes32Variable,
+    pub blob_gas_used: U64Variable,
+    pub excess_blob_gas: U64Variable,
+}
@@ -1,16 +1,15 @@
 use std::fmt::Debug;
 
 use plonky2::hash::hash_types::RichField;
-use plonky2x_derive::CircuitVariable;
+use plonky2x_derive::{CircuitVariable, Ssz};
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::uint::uint64::U64Variable;
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable, SSZVariable};
-use crate::prelude::{ByteVariable, Variable};
-use crate::utils::bytes32;
+use crate::frontend::vars::{Bytes32Variable, CircuitVariable, SSZVariable, Ssz};
+use crate::prelude::Variable;
 
-#[derive(Debug, Copy, Clone, CircuitVariable)]
+#[derive(Debug, Copy, Clone, CircuitVariable, Ssz)]
 #[value_name(BeaconHeaderValue)]
 pub struct BeaconHeaderVariable {
     pub slot: U64Variable,
@@ -20,60 +19,6 @@ pub struct BeaconHeaderVariable {
     pub body_root: Bytes32Variable,
 }
 
-impl SSZVariable for BeaconHeaderVariable {
-    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
-        &self,
-        builder: &mut CircuitBuilder<L, D>,
-    ) -> Bytes32Variable {
-        let zero = builder.constant::<ByteVariable>(0);
-
-        let slot_leaf = self.slot.hash_tree_root(builder);
-        let proposer_index_leaf = self.proposer_index.hash_tree_root(builder);
-        let parent_root_leaf = self.parent_root.hash_tree_root(builder);
-        let state_root_leaf = self.state_root.hash_tree_root(builder);
-        let body_root_leaf = self.body_root.hash_tree_root(builder);
-
-        // ab, cd, ef, gh (f/g/h are zero)
-        let zero_bytes = vec![zero; 32];
-
-        let mut ab_input = Vec::new();
-        ab_input.extend(slot_leaf.0 .0);
-        ab_input.extend(proposer_index_leaf.0 .0);
-        let ab = builder.curta_sha256(&ab_input);
-
-        let mut cd_input = Vec::new();
-        cd_input.extend(parent_root_leaf.0 .0);
-        cd_input.extend(state_root_leaf.0 .0);
-        let cd = builder.curta_sha256(&cd_input);
-
-        let mut ef_input = Vec::new();
-        ef_input.extend(body_root_leaf.0 .0);
-        ef_input.extend(zero_bytes);
-        let ef = builder.curta_sha256(&ef_input);
-
-        // sha256(00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000)
-        let gh = Bytes32Variable::constant(
-            builder,
-            bytes32!("f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"),
-        );
-
-        let mut abcd_input = Vec::new();
-        abcd_input.extend(ab.0 .0);
-        abcd_input.extend(cd.0 .0);
-        let abcd = builder.curta_sha256(&abcd_input);
-
-        let mut efgh_input = Vec::new();
-        efgh_input.extend(ef.0 .0);
-        efgh_input.extend(gh.0 .0);
-        let efgh = builder.curta_sha256(&efgh_input);
-
-        let mut full_input = Vec::new();
-        full_input.extend(abcd.0 .0);
-        full_input.extend(efgh.0 .0);
-        builder.curta_sha256(&full_input)
-    }
-}
-
 #[cfg(test)]
 mod test {
     use std::env;
@@ -82,6 +27,7 @@ mod test {
 
     use super::*;
     use crate::prelude::DefaultParameters;
+    use crate::utils::bytes32;
     use crate::utils::eth::beacon::BeaconClient;
 
     type L = DefaultParameters;
@@ -0,0 +1,19 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::{CircuitVariable, Ssz};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{Bytes32Variable, CircuitVariable, SSZVariable, Ssz};
+use crate::prelude::Variable;
+
+/// An entry of `state.historical_summaries`, which from Capella on accumulates the roots of the
+/// `block_roots` and `state_roots` vectors of every `SLOTS_PER_HISTORICAL_ROOT` slots.
+/// Reference: https://github.com/ethereum/consensus-specs/blob/dev/specs/capella/beacon-chain.md#historicalsummary
+#[derive(Debug, Copy, Clone, CircuitVariable, Ssz)]
+#[value_name(HistoricalSummaryValue)]
+pub struct HistoricalSummaryVariable {
+    pub block_summary_root: Bytes32Variable,
+    pub state_summary_root: Bytes32Variable,
+}
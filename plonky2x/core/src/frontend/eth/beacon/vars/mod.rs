@@ -1,6 +1,9 @@
 mod balances;
 mod compressed_validator;
+mod execution_payload_header;
 mod header;
+mod historical_summary;
+mod sync_committee;
 mod validator;
 mod validators;
 mod withdrawal;
@@ -8,7 +11,10 @@ mod withdrawals;
 
 pub use balances::*;
 pub use compressed_validator::*;
+pub use execution_payload_header::*;
 pub use header::*;
+pub use historical_summary::*;
+pub use sync_committee::*;
 pub use validator::*;
 pub use validators::*;
 pub use withdrawal::*;
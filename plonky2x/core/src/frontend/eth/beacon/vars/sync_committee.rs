@@ -0,0 +1,33 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::vars::{Bytes32Variable, CircuitVariable, SSZVariable};
+use crate::prelude::{ArrayVariable, Variable};
+
+/// The number of validators in a sync committee.
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+
+/// The sync committee struct according to the consensus spec.
+/// Reference: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/beacon-chain.md#synccommittee
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(BeaconSyncCommitteeValue)]
+pub struct BeaconSyncCommitteeVariable {
+    pub pubkeys: ArrayVariable<BLSPubkeyVariable, SYNC_COMMITTEE_SIZE>,
+    pub aggregate_pubkey: BLSPubkeyVariable,
+}
+
+impl SSZVariable for BeaconSyncCommitteeVariable {
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let pubkeys_root = builder.hash_pubkey_list(self.pubkeys.as_slice());
+        let aggregate_pubkey_root = self.aggregate_pubkey.hash_tree_root(builder);
+        builder.ssz_hash_leafs(&[pubkeys_root, aggregate_pubkey_root])
+    }
+}
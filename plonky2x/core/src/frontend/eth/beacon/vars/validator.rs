@@ -1,21 +1,26 @@
 use std::fmt::Debug;
 
+use anyhow::{ensure, Result};
+use ethers::types::{H256, U256};
 use plonky2::hash::hash_types::RichField;
+use plonky2::iop::witness::WitnessWrite;
+use plonky2x_derive::Ssz;
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::eth::vars::BLSPubkeyVariable;
 use crate::frontend::vars::{
-    BoolVariable, Bytes32Variable, CircuitVariable, EvmVariable, SSZVariable, U256Variable,
+    BoolVariable, Bytes32Variable, CircuitVariable, SSZVariable, Ssz, U256Variable,
+    ValueSerializable,
 };
-use crate::prelude::{ByteVariable, Variable};
-use crate::utils::eth::beacon::BeaconValidator;
+use crate::prelude::Variable;
+use crate::utils::eth::beacon::{BeaconValidator, ValidationError};
 use crate::utils::{bytes, bytes32, hex};
 
 const ZERO_BYTE32: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
 const ZERO_VALIDATOR_PUBKEY: &str = "0x111111000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Ssz)]
 pub struct BeaconValidatorVariable {
     pub pubkey: BLSPubkeyVariable,
     pub withdrawal_credentials: Bytes32Variable,
@@ -65,23 +70,15 @@ impl CircuitVariable for BeaconValidatorVariable {
     }
 
     fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
-        let pubkey = BLSPubkeyVariable::elements(bytes!(value.pubkey));
-        let withdrawal_credentials =
-            Bytes32Variable::elements(bytes32!(value.withdrawal_credentials));
+        let (pubkey, withdrawal_credentials, epochs) = decode(&value);
+        let pubkey = BLSPubkeyVariable::elements(pubkey);
+        let withdrawal_credentials = Bytes32Variable::elements(H256::from(withdrawal_credentials));
         let effective_balance = U256Variable::elements(value.effective_balance.into());
         let slashed = BoolVariable::elements(value.slashed);
-        let activation_eligibility_epoch = U256Variable::elements(
-            value
-                .activation_eligibility_epoch
-                .parse::<u64>()
-                .unwrap()
-                .into(),
-        );
-        let activation_epoch =
-            U256Variable::elements(value.activation_epoch.parse::<u64>().unwrap().into());
-        let exit_epoch = U256Variable::elements(value.exit_epoch.parse::<u64>().unwrap().into());
-        let withdrawable_epoch =
-            U256Variable::elements(value.withdrawable_epoch.parse::<u64>().unwrap().into());
+        let activation_eligibility_epoch = U256Variable::elements(epochs[0].into());
+        let activation_epoch = U256Variable::elements(epochs[1].into());
+        let exit_epoch = U256Variable::elements(epochs[2].into());
+        let withdrawable_epoch = U256Variable::elements(epochs[3].into());
         pubkey
             .into_iter()
             .chain(withdrawal_credentials)
@@ -165,70 +162,175 @@ impl CircuitVariable for BeaconValidatorVariable {
     }
 }
 
-impl SSZVariable for BeaconValidatorVariable {
-    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+impl BeaconValidatorVariable {
+    /// The leaf of the validator in a validator subtree served by the beacon node.
+    ///
+    /// The server pads subtrees past the end of the registry with placeholder validators whose
+    /// pubkey is `ZERO_VALIDATOR_PUBKEY`, and their leaves are empty. Every other validator's leaf
+    /// is its hash tree root.
+    pub fn subtree_leaf<L: PlonkParameters<D>, const D: usize>(
         &self,
         builder: &mut CircuitBuilder<L, D>,
     ) -> Bytes32Variable {
-        let zero = builder.constant::<ByteVariable>(0);
-        let one = builder.constant::<ByteVariable>(1);
-
-        let mut pubkey_serialized = self.pubkey.0 .0.to_vec();
-        pubkey_serialized.extend([zero; 16]);
+        let leaf = self.hash_tree_root(builder);
+        let zero_leaf = builder.constant::<Bytes32Variable>(bytes32!(ZERO_BYTE32));
+        let zero_validator_pubkey =
+            builder.constant::<BLSPubkeyVariable>(bytes!(ZERO_VALIDATOR_PUBKEY));
+        let is_zero_validator = builder.is_equal(self.pubkey, zero_validator_pubkey);
+        builder.select(is_zero_validator, zero_leaf, leaf)
+    }
 
-        let tmp = builder.curta_sha256(&pubkey_serialized);
-        let mut a1 = tmp.0 .0.to_vec();
-        a1.extend(self.withdrawal_credentials.0 .0.to_vec());
+    /// Like [`CircuitBuilder::constant`], but returns the invalid field of `value` as an error
+    /// instead of panicking.
+    pub fn try_constant<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        value: BeaconValidator,
+    ) -> Result<Self, ValidationError> {
+        value.validate()?;
+        Ok(Self::constant(builder, value))
+    }
 
-        let mut a2 = self.effective_balance.encode(builder);
-        a2.reverse();
-        let mut slashed = vec![builder.select(self.slashed, one, zero)];
-        slashed.extend([zero; 31]);
-        a2.extend(slashed);
+    /// Like [`CircuitVariable::set`], but returns the invalid field of `value` as an error instead
+    /// of panicking.
+    pub fn try_set<F: RichField, W: WitnessWrite<F>>(
+        &self,
+        witness: &mut W,
+        value: BeaconValidator,
+    ) -> Result<(), ValidationError> {
+        value.validate()?;
+        self.set(witness, value);
+        Ok(())
+    }
+}
 
-        let mut a3 = self.activation_eligibility_epoch.encode(builder);
-        a3.reverse();
-        let mut tmp = self.activation_epoch.encode(builder);
-        tmp.reverse();
-        a3.extend(&tmp);
+/// Decodes the pubkey, the withdrawal credentials and the epochs of a validator, returning the
+/// invalid field as an error if it does not hold a valid value.
+fn try_decode(
+    value: &BeaconValidator,
+) -> core::result::Result<([u8; 48], [u8; 32], [u64; 4]), ValidationError> {
+    Ok((
+        value.pubkey_bytes()?,
+        value.withdrawal_credentials_bytes()?,
+        value.epochs()?,
+    ))
+}
 
-        let mut a4 = self.exit_epoch.encode(builder);
-        a4.reverse();
-        let mut tmp = self.withdrawable_epoch.encode(builder);
-        tmp.reverse();
-        a4.extend(&tmp);
+/// Like [`try_decode`], but panics with the invalid field.
+fn decode(value: &BeaconValidator) -> ([u8; 48], [u8; 32], [u64; 4]) {
+    try_decode(value).unwrap_or_else(|e| panic!("{}", e))
+}
 
-        let mut b1 = builder.curta_sha256(&a1).0 .0.to_vec();
-        b1.extend(builder.curta_sha256(&a2).0 .0.to_vec());
+/// The fields of a validator, in the order of their canonical encoding.
+type BeaconValidatorFields = (
+    BLSPubkeyVariable,
+    Bytes32Variable,
+    U256Variable,
+    BoolVariable,
+    U256Variable,
+    U256Variable,
+    U256Variable,
+    U256Variable,
+);
 
-        let mut b2 = builder.curta_sha256(&a3).0 .0.to_vec();
-        b2.extend(builder.curta_sha256(&a4).0 .0.to_vec());
+/// Encoded as the concatenation of the encodings of its fields, 241 bytes in total:
+/// - `pubkey`: the raw 48 bytes,
+/// - `withdrawal_credentials`: the raw 32 bytes,
+/// - `effective_balance`: 32 bytes, big-endian,
+/// - `slashed`: a single `0` or `1` byte,
+/// - the activation eligibility, activation, exit and withdrawable epochs: 32 bytes each,
+///   big-endian.
+impl ValueSerializable for BeaconValidatorVariable {
+    fn nb_value_bytes() -> usize {
+        BeaconValidatorFields::nb_value_bytes()
+    }
 
-        let mut c1 = builder.curta_sha256(&b1).0 .0.to_vec();
-        c1.extend(builder.curta_sha256(&b2).0 .0.to_vec());
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        let (pubkey, withdrawal_credentials, epochs) = try_decode(&value)?;
+        BeaconValidatorFields::to_bytes::<F>((
+            pubkey,
+            H256::from(withdrawal_credentials),
+            value.effective_balance.into(),
+            value.slashed,
+            epochs[0].into(),
+            epochs[1].into(),
+            epochs[2].into(),
+            epochs[3].into(),
+        ))
+    }
 
-        let leaf = builder.curta_sha256(&c1);
-        let zero_leaf = builder.constant::<Bytes32Variable>(bytes32!(ZERO_BYTE32));
-        let zero_validator_pubkey =
-            builder.constant::<BLSPubkeyVariable>(bytes!(ZERO_VALIDATOR_PUBKEY));
-        let is_zero_validator = builder.is_equal(self.pubkey, zero_validator_pubkey);
-        builder.select(is_zero_validator, zero_leaf, leaf)
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        let (
+            pubkey,
+            withdrawal_credentials,
+            effective_balance,
+            slashed,
+            activation_eligibility_epoch,
+            activation_epoch,
+            exit_epoch,
+            withdrawable_epoch,
+        ) = BeaconValidatorFields::from_bytes::<F>(bytes)?;
+        let as_u64 = |value: U256| {
+            ensure!(value.bits() <= 64, "{} does not fit in a u64", value);
+            Ok(value.as_u64())
+        };
+        Ok(BeaconValidator {
+            pubkey: hex!(pubkey),
+            withdrawal_credentials: hex!(withdrawal_credentials),
+            effective_balance: as_u64(effective_balance)?,
+            slashed,
+            activation_eligibility_epoch: as_u64(activation_eligibility_epoch)?.to_string(),
+            activation_epoch: as_u64(activation_epoch)?.to_string(),
+            exit_epoch: as_u64(exit_epoch)?.to_string(),
+            withdrawable_epoch: as_u64(withdrawable_epoch)?.to_string(),
+        })
     }
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::env;
+
+    use array_macro::array;
+    use ethers::types::{H256, U256};
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     use crate::backend::circuit::DefaultParameters;
     use crate::frontend::builder::CircuitBuilder;
-    use crate::frontend::eth::beacon::vars::BeaconValidatorVariable;
-    use crate::frontend::vars::{Bytes32Variable, SSZVariable};
-    use crate::utils::bytes32;
-    use crate::utils::eth::beacon::BeaconValidator;
+    use crate::frontend::eth::beacon::vars::{BeaconHeaderVariable, BeaconValidatorVariable};
+    use crate::frontend::vars::{
+        BoolVariable, Bytes32Variable, SSZVariable, Ssz, U256Variable, ValueSerializable,
+    };
+    use crate::utils::eth::beacon::{BeaconClient, BeaconValidator, ValidationError};
+    use crate::utils::test_utils::{
+        assert_constraint_failure, assert_variable_roundtrip, ROUNDTRIP_SEED,
+    };
+    use crate::utils::{bytes32, hex};
 
     type L = DefaultParameters;
     const D: usize = 2;
 
+    #[test]
+    fn test_beacon_validator_variable_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(ROUNDTRIP_SEED);
+        for _ in 0..8 {
+            let pubkey: [u8; 48] = array![_ => rng.gen(); 48];
+            let withdrawal_credentials: [u8; 32] = rng.gen();
+            let validator = BeaconValidator {
+                pubkey: hex!(pubkey),
+                withdrawal_credentials: hex!(withdrawal_credentials),
+                effective_balance: rng.gen(),
+                slashed: rng.gen(),
+                activation_eligibility_epoch: rng.gen::<u64>().to_string(),
+                activation_epoch: rng.gen::<u64>().to_string(),
+                exit_epoch: rng.gen::<u64>().to_string(),
+                withdrawable_epoch: rng.gen::<u64>().to_string(),
+            };
+            assert_variable_roundtrip::<L, D, BeaconValidatorVariable>(validator);
+        }
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_validator_hash_tree_root_1() {
@@ -259,6 +361,51 @@ pub(crate) mod tests {
         circuit.verify(&proof, &input, &output);
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_verify_validator_slashed_field() {
+        env_logger::try_init().unwrap_or_default();
+
+        let validator = BeaconValidator {
+            pubkey: "0x1d7d6a239c32e1a82c53f9f5506d0e2bb5e4be75b5046ecb5c685544c2346a2e659203c77f9896d0783dca8c2bc7345f".to_string(),
+            withdrawal_credentials: "0xc2f56d5e99cd47e06d5a7a449ed9317c843ed5056982a15fac1972eb7b1b6048".to_string(),
+            effective_balance: 5,
+            slashed: true,
+            activation_eligibility_epoch: "3".to_string(),
+            activation_epoch: "6".to_string(),
+            exit_epoch: "2".to_string(),
+            withdrawable_epoch: "2".to_string(),
+        };
+        let root = bytes32!("0xaf7cc4e01fcb4a0620a1c842c6040a02275438e933dc9b3280b2ec7e0b7adc9f");
+        const SLASHED_FIELD: usize = 3;
+
+        // Verifies `slashed` against the validator root with the branch computed from the other
+        // field roots.
+        let verify_slashed = |builder: &mut CircuitBuilder<L, D>, slashed: bool| {
+            let v = builder.constant::<BeaconValidatorVariable>(validator.clone());
+            let leaves = v.field_roots(builder);
+            let left = builder.curta_sha256_pair(leaves[0], leaves[1]);
+            let right_left = builder.curta_sha256_pair(leaves[4], leaves[5]);
+            let right_right = builder.curta_sha256_pair(leaves[6], leaves[7]);
+            let right = builder.curta_sha256_pair(right_left, right_right);
+            let branch = [leaves[2], left, right];
+
+            let slashed = builder.constant::<BoolVariable>(slashed);
+            let leaf = slashed.hash_tree_root(builder);
+            let root = builder.constant::<Bytes32Variable>(root);
+            builder.ssz_verify_field::<BeaconValidatorVariable>(root, SLASHED_FIELD, leaf, &branch);
+        };
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        verify_slashed(&mut builder, true);
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_constraint_failure::<L, D>(|builder| verify_slashed(builder, false), |_| {}, None);
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_validator_hash_tree_root_2() {
@@ -288,4 +435,212 @@ pub(crate) mod tests {
         let (proof, output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
     }
+
+    #[test]
+    fn test_validator_try_constant() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let validator = BeaconValidator {
+            pubkey: "0x933ad9491b62059dd065b560d256d8957a8c402cc6e8d8ee7290ae11e8f7329267a8811c397529dac52ae1342ba58c".to_string(),
+            withdrawal_credentials: "0x0100000000000000000000000d369bb49efa5100fd3b86a9f828c55da04d2d50".to_string(),
+            effective_balance: 32000000000,
+            slashed: false,
+            activation_eligibility_epoch: "0".to_string(),
+            activation_epoch: "0".to_string(),
+            exit_epoch: "18446744073709551615".to_string(),
+            withdrawable_epoch: "18446744073709551615".to_string(),
+        };
+        let err = BeaconValidatorVariable::try_constant(&mut builder, validator).unwrap_err();
+        assert_eq!(err.field(), "pubkey");
+    }
+
+    #[test]
+    fn test_validator_value_bytes() {
+        type F = GoldilocksField;
+
+        let pubkey = [0x93; 48];
+        let withdrawal_credentials = [0x01; 32];
+        let validator = BeaconValidator {
+            pubkey: hex!(pubkey),
+            withdrawal_credentials: hex!(withdrawal_credentials),
+            effective_balance: 32000000000,
+            slashed: true,
+            activation_eligibility_epoch: "1".to_string(),
+            activation_epoch: "2".to_string(),
+            exit_epoch: "18446744073709551615".to_string(),
+            withdrawable_epoch: "3".to_string(),
+        };
+
+        let word = |value: u64| {
+            let mut bytes = [0u8; 32];
+            bytes[24..].copy_from_slice(&value.to_be_bytes());
+            bytes
+        };
+        let mut expected = Vec::new();
+        expected.extend(pubkey);
+        expected.extend(withdrawal_credentials);
+        expected.extend(word(32000000000));
+        expected.push(1);
+        expected.extend(word(1));
+        expected.extend(word(2));
+        expected.extend(word(u64::MAX));
+        expected.extend(word(3));
+
+        let bytes = BeaconValidatorVariable::to_bytes::<F>(validator.clone()).unwrap();
+        assert_eq!(bytes, expected);
+        assert_eq!(BeaconValidatorVariable::nb_value_bytes(), 241);
+
+        let decoded = BeaconValidatorVariable::from_bytes::<F>(&bytes).unwrap();
+        assert_eq!(decoded.ssz_hash_tree_root(), validator.ssz_hash_tree_root());
+
+        let malformed = BeaconValidator {
+            exit_epoch: "not an epoch".to_string(),
+            ..validator
+        };
+        let err = BeaconValidatorVariable::to_bytes::<F>(malformed).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ValidationError>().unwrap().field(),
+            "exit_epoch"
+        );
+    }
+
+    #[test]
+    fn test_container_field_gindex() {
+        // Validator fields are leaves 8..16 of a depth 3 tree, e.g. slashed is at gindex 11.
+        assert_eq!(BeaconValidatorVariable::chunk_count(), 8);
+        assert_eq!(BeaconValidatorVariable::field_gindex(0), 8);
+        assert_eq!(BeaconValidatorVariable::field_gindex(3), 11);
+        assert_eq!(BeaconValidatorVariable::field_gindex(7), 15);
+
+        // The five header fields are padded to eight leaves, so state_root is at gindex 11.
+        assert_eq!(BeaconHeaderVariable::chunk_count(), 5);
+        assert_eq!(BeaconHeaderVariable::field_gindex(3), 11);
+        assert_eq!(BeaconHeaderVariable::field_gindex(4), 12);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_validator_hash_tree_root_matches_client() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let block_root =
+            bytes32!("0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670");
+        let validator = client
+            .get_validator_witness(hex!(block_root.as_bytes()), 1000)
+            .unwrap()
+            .validator;
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client);
+        let block_root = builder.constant::<Bytes32Variable>(block_root);
+        let validators = builder.beacon_get_validators(block_root);
+        // The accessor verifies the hash tree root of the validator against the client's proof.
+        let proven = builder.beacon_get_validator_const(validators, 1000);
+        let proven_root = proven.hash_tree_root(&mut builder);
+        let constant = builder.constant::<BeaconValidatorVariable>(validator.clone());
+        let constant_root = constant.hash_tree_root(&mut builder);
+        builder.assert_is_equal(proven_root, constant_root);
+        builder.write(constant_root);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            validator.ssz_hash_tree_root()
+        );
+    }
+
+    #[test]
+    fn test_validator_subtree_leaf() {
+        let validator = |pubkey: &str| BeaconValidator {
+            pubkey: pubkey.to_string(),
+            withdrawal_credentials: hex!([0x01; 32]),
+            effective_balance: 32000000000,
+            slashed: false,
+            activation_eligibility_epoch: "0".to_string(),
+            activation_epoch: "0".to_string(),
+            exit_epoch: "18446744073709551615".to_string(),
+            withdrawable_epoch: "18446744073709551615".to_string(),
+        };
+        let registered = validator(&hex!([0x93; 48]));
+        let placeholder = validator(super::ZERO_VALIDATOR_PUBKEY);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        for value in [registered.clone(), placeholder.clone()] {
+            let v = builder.constant::<BeaconValidatorVariable>(value);
+            let root = v.hash_tree_root(&mut builder);
+            let leaf = v.subtree_leaf(&mut builder);
+            builder.write(root);
+            builder.write(leaf);
+        }
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        // The derived root follows the spec for every validator, and only the placeholder has an
+        // empty subtree leaf.
+        let registered_root = registered.ssz_hash_tree_root();
+        assert_eq!(output.read::<Bytes32Variable>(), registered_root);
+        assert_eq!(output.read::<Bytes32Variable>(), registered_root);
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            placeholder.ssz_hash_tree_root()
+        );
+        assert_eq!(output.read::<Bytes32Variable>(), H256::zero());
+    }
+
+    #[test]
+    fn test_if_then_else_validator_and_balance() {
+        let validator = |byte: u8, effective_balance: u64| BeaconValidator {
+            pubkey: hex!([byte; 48]),
+            withdrawal_credentials: hex!([byte; 32]),
+            effective_balance,
+            slashed: false,
+            activation_eligibility_epoch: "0".to_string(),
+            activation_epoch: "0".to_string(),
+            exit_epoch: "18446744073709551615".to_string(),
+            withdrawable_epoch: "18446744073709551615".to_string(),
+        };
+        let first = validator(0x93, 32000000000);
+        let second = validator(0xa1, 31000000000);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let condition = builder.read::<BoolVariable>();
+        let (selected, balance) = builder.if_then_else(
+            condition,
+            |builder| {
+                (
+                    builder.constant::<BeaconValidatorVariable>(first.clone()),
+                    builder.constant::<U256Variable>(U256::from(32000000123u64)),
+                )
+            },
+            |builder| {
+                (
+                    builder.constant::<BeaconValidatorVariable>(second.clone()),
+                    builder.constant::<U256Variable>(U256::from(31000000456u64)),
+                )
+            },
+        );
+        builder.write(selected);
+        builder.write(balance);
+        let circuit = builder.build();
+
+        for (condition, validator, balance) in [
+            (true, &first, 32000000123u64),
+            (false, &second, 31000000456u64),
+        ] {
+            let mut input = circuit.input();
+            input.write::<BoolVariable>(condition);
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<BeaconValidatorVariable>(), *validator);
+            assert_eq!(output.read::<U256Variable>(), U256::from(balance));
+        }
+    }
 }
@@ -1,6 +1,10 @@
+#[cfg(feature = "beacon")]
 pub mod beacon;
+#[cfg(feature = "mpt")]
 pub mod mpt;
+#[cfg(feature = "mpt")]
 pub mod rlp;
+#[cfg(feature = "mpt")]
 pub mod storage;
 pub mod utils;
 pub mod vars;
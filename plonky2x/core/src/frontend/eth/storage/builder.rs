@@ -45,7 +45,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> EthHeaderVariable {
         let generator = EthBlockGenerator::new(self, block_hash);
         let value = generator.value;
-        self.add_simple_generator(generator);
+        self.add_network_simple_generator(generator);
         value
     }
 
@@ -65,7 +65,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> EthLogVariable {
         let generator = EthLogGenerator::new(self, transaction_hash, block_hash, log_index);
         let value = generator.clone().value;
-        self.add_simple_generator(generator);
+        self.add_network_simple_generator(generator);
         value
     }
 }
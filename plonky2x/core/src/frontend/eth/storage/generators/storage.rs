@@ -44,6 +44,8 @@ impl<L: PlonkParameters<D>, const D: usize> EthStorageProofHint<L, D> {
 
 #[async_trait]
 impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for EthStorageProofHint<L, D> {
+    const NETWORK: bool = true;
+
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
@@ -1,12 +1,14 @@
 use std::fmt::Debug;
 
+use anyhow::Result;
 use ethers::types::H160;
 use plonky2::hash::hash_types::RichField;
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::vars::{
-    ByteVariable, BytesVariable, CircuitVariable, EvmVariable, SSZVariable,
+    ensure_nb_value_bytes, ByteVariable, BytesVariable, CircuitVariable, EvmVariable, SSZVariable,
+    ValueSerializable,
 };
 use crate::prelude::{Bytes32Variable, Variable};
 
@@ -50,6 +52,36 @@ impl CircuitVariable for BLSPubkeyVariable {
     }
 }
 
+/// Encoded as the raw 48 bytes.
+impl ValueSerializable for BLSPubkeyVariable {
+    fn nb_value_bytes() -> usize {
+        48
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(value.to_vec())
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        Ok(bytes.try_into().unwrap())
+    }
+}
+
+/// A pubkey is a 48 byte vector, which SSZ packs into two chunks by padding it with zeros to 64
+/// bytes.
+impl SSZVariable for BLSPubkeyVariable {
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let zero = builder.constant::<ByteVariable>(0);
+        let mut chunks = self.0 .0.to_vec();
+        chunks.extend([zero; 16]);
+        builder.curta_sha256(&chunks)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct AddressVariable(pub BytesVariable<20>);
 
@@ -104,25 +136,67 @@ impl EvmVariable for AddressVariable {
     ) -> Self {
         Self(BytesVariable::decode(builder, bytes))
     }
+}
+
+/// Encoded as the raw 20 bytes.
+impl ValueSerializable for AddressVariable {
+    fn nb_value_bytes() -> usize {
+        20
+    }
 
-    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
-        value.as_bytes().to_vec()
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(value.as_bytes().to_vec())
     }
 
-    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
-        H160::from_slice(bytes)
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        Ok(H160::from_slice(bytes))
     }
 }
 
+/// The SSZ leaf of a `Bytes20` is the raw bytes right-padded to 32 bytes.
 impl SSZVariable for AddressVariable {
     fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
         &self,
         builder: &mut CircuitBuilder<L, D>,
     ) -> Bytes32Variable {
         let mut bytes = self.encode(builder);
-        bytes.reverse();
         let zero = builder.constant::<ByteVariable>(0);
         bytes.extend([zero; 12]);
         Bytes32Variable(BytesVariable::<32>(bytes.try_into().unwrap()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::utils::bytes32;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_address_hash_tree_root() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let address = builder.read::<AddressVariable>();
+        let root = address.hash_tree_root(&mut builder);
+        builder.write(root);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<AddressVariable>(
+            "0x4675c7e5baafbffbca748158becba61ef3b0a263"
+                .parse()
+                .unwrap(),
+        );
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        // The SSZ leaf of a `Bytes20` keeps the bytes in order and pads them on the right.
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x4675c7e5baafbffbca748158becba61ef3b0a263000000000000000000000000")
+        );
+    }
+}
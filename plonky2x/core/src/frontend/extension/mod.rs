@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 
+use anyhow::Result;
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::Target;
 use starkyx::math::prelude::cubic::element::CubicElement;
 use starkyx::plonky2::cubic::builder::CubicCircuitBuilder;
 
 use crate::prelude::{
-    Add, CircuitBuilder, CircuitVariable, Mul, One, PlonkParameters, Sub, Variable, Zero,
+    Add, CircuitBuilder, CircuitVariable, Mul, One, PlonkParameters, Sub, ValueSerializable,
+    Variable, Zero,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -65,6 +67,21 @@ impl CircuitVariable for CubicExtensionVariable {
     }
 }
 
+/// Encoded as the concatenation of the encodings of its coefficients.
+impl ValueSerializable for CubicExtensionVariable {
+    fn nb_value_bytes() -> usize {
+        <[Variable; 3]>::nb_value_bytes()
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        <[Variable; 3]>::to_bytes(value.0)
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        Ok(CubicElement(<[Variable; 3]>::from_bytes(bytes)?))
+    }
+}
+
 impl Variable {
     pub fn as_cubic_extension<L: PlonkParameters<D>, const D: usize>(
         &self,
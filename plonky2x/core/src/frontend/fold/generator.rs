@@ -122,7 +122,8 @@ where
         )
     };
     input.proof_write(proof);
-    circuit.prove(&input)
+    let (proof, output) = circuit.prove(&input);
+    (proof, output.into())
 }
 
 impl<Definition, Ctx, Element, Accumulator, Serializer, L, const D: usize>
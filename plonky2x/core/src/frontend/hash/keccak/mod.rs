@@ -3,11 +3,16 @@
 use core::marker::PhantomData;
 
 use self::keccak256::Keccak256Generator;
+use self::pad::KECCAK256_RATE_BYTES;
+use self::permutation::KeccakLane;
 use crate::backend::circuit::PlonkParameters;
-use crate::frontend::vars::Bytes32Variable;
-use crate::prelude::{ByteVariable, CircuitBuilder, Variable};
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, BytesVariable};
+use crate::prelude::{BoolVariable, ByteVariable, CircuitBuilder, Variable};
 
 pub mod keccak256;
+pub mod pad;
+pub mod permutation;
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// WARNING: DO NOT USE IN PRODUCTION, this is unconstrained!
@@ -40,11 +45,87 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.add_simple_generator(generator.clone());
         generator.output
     }
+
+    /// Computes the keccak256 hash of `data`, whose length is fixed when the circuit is built.
+    ///
+    /// The padding is made of constants, so this only costs the `data.len() / 136 + 1`
+    /// permutations of the padded message, without the selection of the last block done by
+    /// `keccak256_variable`.
+    pub fn keccak256(&mut self, data: &[ByteVariable]) -> Bytes32Variable {
+        let padded = self.pad_keccak256_fixed_length(data);
+
+        let mut state: [KeccakLane; 25] = [[self._false(); 64]; 25];
+        for block in padded.chunks_exact(KECCAK256_RATE_BYTES) {
+            self.keccak256_absorb(&mut state, block);
+            self.keccak_f(&mut state);
+        }
+
+        Bytes32Variable(BytesVariable(self.keccak256_squeeze(&state)))
+    }
+
+    /// Computes the keccak256 hash of the first `length` bytes of `data`, constraining both the
+    /// padding and the permutation in-circuit.
+    ///
+    /// The cost is that of hashing `data.len() / 136 + 1` blocks, whatever the value of `length`.
+    /// The state after each block is computed, and the digest is read from the state after the
+    /// last block of the padded message.
+    pub fn keccak256_variable(
+        &mut self,
+        data: &[ByteVariable],
+        length: U64Variable,
+    ) -> Bytes32Variable {
+        let padded = self.pad_keccak256_variable_length(data, length);
+        let last_block = self.compute_keccak256_last_block(length);
+
+        let mut state: [KeccakLane; 25] = [[self._false(); 64]; 25];
+        let mut digest = [self.zero::<ByteVariable>(); 32];
+        for (i, block) in padded.chunks_exact(KECCAK256_RATE_BYTES).enumerate() {
+            self.keccak256_absorb(&mut state, block);
+            self.keccak_f(&mut state);
+
+            // The digest is the first 32 bytes of the state after the last block.
+            let block_digest = self.keccak256_squeeze(&state);
+            let curr_block = self.constant::<U64Variable>(i as u64);
+            let is_last_block = self.is_equal(curr_block, last_block);
+            for (byte, block_byte) in digest.iter_mut().zip(block_digest) {
+                *byte = self.select(is_last_block, block_byte, *byte);
+            }
+        }
+
+        Bytes32Variable(BytesVariable(digest))
+    }
+
+    /// Absorbs a block into the first 17 lanes, as little-endian 64-bit words.
+    fn keccak256_absorb(&mut self, state: &mut [KeccakLane; 25], block: &[ByteVariable]) {
+        for (lane, word) in block.chunks_exact(8).enumerate() {
+            for (k, byte) in word.iter().enumerate() {
+                for (b, bit) in byte.as_le_bits().iter().enumerate() {
+                    state[lane][8 * k + b] = self.xor(state[lane][8 * k + b], *bit);
+                }
+            }
+        }
+    }
+
+    /// Reads the first 32 bytes of the keccak state.
+    fn keccak256_squeeze(&mut self, state: &[KeccakLane; 25]) -> [ByteVariable; 32] {
+        let mut bytes = [self.zero::<ByteVariable>(); 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let le_bits = &state[i / 8][8 * (i % 8)..8 * (i % 8) + 8];
+            let mut be_bits: [BoolVariable; 8] = le_bits.try_into().unwrap();
+            be_bits.reverse();
+            *byte = ByteVariable(be_bits);
+        }
+        bytes
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use ethers::types::H256;
+    use ethers::utils::keccak256;
+    use rand::{thread_rng, Rng};
+
     use super::*;
     use crate::backend::circuit::DefaultParameters;
     use crate::utils::bytes32;
@@ -67,4 +148,71 @@ mod tests {
         let input = circuit.input();
         let (_, _) = circuit.prove(&input);
     }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_keccak256_fixed_length() {
+        env_logger::try_init().unwrap_or_default();
+
+        // 0, 135, 136 and 137 bytes cover an empty message, a single padding byte and the lengths
+        // at and above the rate.
+        let mut rng = thread_rng();
+        let data = (0..137).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let data_var = builder.read::<BytesVariable<137>>();
+        for length in [0, 135, 136, 137] {
+            let hash = builder.keccak256(&data_var.0[..length]);
+            builder.write(hash);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BytesVariable<137>>(data.clone().try_into().unwrap());
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for length in [0, 135, 136, 137] {
+            assert_eq!(
+                output.read::<Bytes32Variable>(),
+                H256::from(keccak256(&data[..length]))
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_keccak256_variable() {
+        env_logger::try_init().unwrap_or_default();
+
+        // 135, 136 and 137 bytes are the lengths right below, at and above the rate, which pad to
+        // one, two and two blocks.
+        let lengths = [135, 136, 137];
+        let mut rng = thread_rng();
+        let data = (0..200).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let data_var = builder.read::<BytesVariable<200>>();
+        for _ in lengths.iter() {
+            let length = builder.read::<U64Variable>();
+            let hash = builder.keccak256_variable(&data_var.0, length);
+            builder.write(hash);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BytesVariable<200>>(data.clone().try_into().unwrap());
+        for length in lengths.iter() {
+            input.write::<U64Variable>(*length as u64);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for length in lengths.iter() {
+            assert_eq!(
+                output.read::<Bytes32Variable>(),
+                H256::from(keccak256(&data[..*length]))
+            );
+        }
+    }
 }
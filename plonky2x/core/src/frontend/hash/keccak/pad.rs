@@ -0,0 +1,114 @@
+use crate::prelude::*;
+
+/// The rate of the keccak256 sponge in bytes.
+pub const KECCAK256_RATE_BYTES: usize = 136;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Calculates the index of the last keccak256 block of an input_byte_length long message.
+    /// The padding always adds at least one byte, so this is `input_byte_length / 136`.
+    pub(crate) fn compute_keccak256_last_block(
+        &mut self,
+        input_byte_length: U64Variable,
+    ) -> U64Variable {
+        let rate = self.constant::<U64Variable>(KECCAK256_RATE_BYTES as u64);
+        self.div(input_byte_length, rate)
+    }
+
+    /// Pad the given fixed length input with the multi-rate padding of keccak256. Since the length
+    /// is known, the padding bytes are constants.
+    pub(crate) fn pad_keccak256_fixed_length(
+        &mut self,
+        input: &[ByteVariable],
+    ) -> Vec<ByteVariable> {
+        let num_blocks = input.len() / KECCAK256_RATE_BYTES + 1;
+        let padded_len = num_blocks * KECCAK256_RATE_BYTES;
+
+        let mut padded_input = input.to_vec();
+        if padded_len - input.len() == 1 {
+            padded_input.push(self.constant::<ByteVariable>(0x81));
+        } else {
+            padded_input.push(self.constant::<ByteVariable>(0x01));
+            padded_input.resize(padded_len - 1, self.constant::<ByteVariable>(0x00));
+            padded_input.push(self.constant::<ByteVariable>(0x80));
+        }
+        padded_input
+    }
+
+    /// Pad the given variable length input with the multi-rate padding of keccak256, which
+    /// appends `0x01`, zero bytes and a final `0x80` to the end of the last block.
+    /// input_byte_length gives the real length of the input in bytes.
+    pub(crate) fn pad_keccak256_variable_length(
+        &mut self,
+        input: &[ByteVariable],
+        input_byte_length: U64Variable,
+    ) -> Vec<ByteVariable> {
+        let true_t = self._true();
+        let false_t = self._false();
+
+        let last_block = self.compute_keccak256_last_block(input_byte_length);
+
+        // The padding adds at least one byte, so an input of input.len() bytes needs one more
+        // block than it fills completely.
+        let max_num_blocks = input.len() / KECCAK256_RATE_BYTES + 1;
+
+        // Extend input to size max_num_blocks * 136 before padding.
+        let mut padded_input = input.to_vec();
+        padded_input.resize(max_num_blocks * KECCAK256_RATE_BYTES, self.zero());
+
+        let zero_byte = self.constant::<ByteVariable>(0x00);
+        let padding_start_byte = self.constant::<ByteVariable>(0x01);
+        let padding_end_byte = self.constant::<ByteVariable>(0x80);
+        let padding_single_byte = self.constant::<ByteVariable>(0x81);
+
+        let mut padded_bytes = Vec::new();
+
+        // Set to true if the last block has been reached. This is used to verify that
+        // input_byte_length is <= input.len().
+        let mut reached_last_block = false_t;
+
+        let mut message_byte_selector = true_t;
+        for i in 0..max_num_blocks {
+            let block_offset = KECCAK256_RATE_BYTES * i;
+            let curr_block = self.constant::<U64Variable>(i as u64);
+
+            let is_last_block = self.is_equal(curr_block, last_block);
+            reached_last_block = self.or(reached_last_block, is_last_block);
+
+            for j in 0..KECCAK256_RATE_BYTES {
+                let idx = block_offset + j;
+                let idx_t = self.constant::<U64Variable>(idx as u64);
+                let is_last_msg_byte = self.is_equal(idx_t, input_byte_length);
+                let not_last_msg_byte = self.not(is_last_msg_byte);
+
+                message_byte_selector = self.select(
+                    message_byte_selector,
+                    not_last_msg_byte,
+                    message_byte_selector,
+                );
+
+                // If message_byte_selector is true, select the message byte.
+                let mut byte = self.select(message_byte_selector, padded_input[idx], zero_byte);
+                // If idx == input_byte_length, select the padding start byte.
+                byte = self.select(is_last_msg_byte, padding_start_byte, byte);
+
+                if j == KECCAK256_RATE_BYTES - 1 {
+                    // The last byte of the last block ends the padding. It is never a message
+                    // byte, and it is also the padding start byte if the message ends right
+                    // before it.
+                    let end_byte =
+                        self.select(is_last_msg_byte, padding_single_byte, padding_end_byte);
+                    byte = self.select(is_last_block, end_byte, byte);
+                }
+
+                padded_bytes.push(byte);
+            }
+        }
+        // These checks verify input_byte_length <= input.len().
+        let max_length = self.constant::<U64Variable>(input.len() as u64);
+        let is_in_range = self.lte(input_byte_length, max_length);
+        self.assert_is_equal(is_in_range, true_t);
+        self.assert_is_equal(reached_last_block, true_t);
+
+        padded_bytes
+    }
+}
@@ -0,0 +1,113 @@
+/// Implementation of the keccak-f[1600] permutation
+/// reference: https://keccak.team/keccak_specs_summary.html
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::BoolVariable;
+
+/// A 64-bit lane of the keccak state, as little-endian bits.
+pub type KeccakLane = [BoolVariable; 64];
+
+/// The round constants of the iota step.
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets of the rho step, indexed by `[x][y]`.
+const ROTATION_OFFSETS: [[usize; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+fn rotate_left(lane: KeccakLane, bits: usize) -> KeccakLane {
+    let mut res = lane;
+    for i in 0..64 {
+        res[i] = lane[(i + 64 - bits) % 64];
+    }
+    res
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Applies keccak-f[1600] to a state of 25 lanes, where lane `x + 5 * y` is `A[x, y]`.
+    pub(crate) fn keccak_f(&mut self, state: &mut [KeccakLane; 25]) {
+        for round_constant in ROUND_CONSTANTS {
+            // θ step.
+            let mut c = [[self._false(); 64]; 5];
+            for x in 0..5 {
+                for z in 0..64 {
+                    let t = self.xor3(
+                        state[x][z].variable,
+                        state[x + 5][z].variable,
+                        state[x + 10][z].variable,
+                    );
+                    c[x][z] = self.xor3(
+                        t.variable,
+                        state[x + 15][z].variable,
+                        state[x + 20][z].variable,
+                    );
+                }
+            }
+            for x in 0..5 {
+                let rotated = rotate_left(c[(x + 1) % 5], 1);
+                for z in 0..64 {
+                    let d = self.xor(c[(x + 4) % 5][z], rotated[z]);
+                    for y in 0..5 {
+                        state[x + 5 * y][z] = self.xor(state[x + 5 * y][z], d);
+                    }
+                }
+            }
+
+            // ρ and π steps.
+            let mut b = *state;
+            for x in 0..5 {
+                for y in 0..5 {
+                    b[y + 5 * ((2 * x + 3 * y) % 5)] =
+                        rotate_left(state[x + 5 * y], ROTATION_OFFSETS[x][y]);
+                }
+            }
+
+            // χ step.
+            for x in 0..5 {
+                for y in 0..5 {
+                    for z in 0..64 {
+                        let not_next = self.not(b[(x + 1) % 5 + 5 * y][z]);
+                        let and = self.and(not_next, b[(x + 2) % 5 + 5 * y][z]);
+                        state[x + 5 * y][z] = self.xor(b[x + 5 * y][z], and);
+                    }
+                }
+            }
+
+            // ι step.
+            for z in 0..64 {
+                if (round_constant >> z) & 1 == 1 {
+                    state[0][z] = self.not(state[0][z]);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 pub mod blake2;
 pub mod common;
 pub mod curta;
+#[cfg(feature = "keccak")]
 pub mod keccak;
 pub mod poseidon;
 pub mod sha;
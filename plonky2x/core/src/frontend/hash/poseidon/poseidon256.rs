@@ -1,3 +1,4 @@
+use anyhow::Result;
 use array_macro::array;
 use plonky2::hash::hash_types::{HashOut, HashOutTarget, RichField, NUM_HASH_OUT_ELTS};
 use plonky2::iop::target::BoolTarget;
@@ -5,7 +6,7 @@ use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
-use crate::frontend::vars::{ArrayVariable, Bytes32Variable};
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, ValueSerializable};
 use crate::prelude::{BoolVariable, ByteVariable, BytesVariable, CircuitVariable, Variable};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -55,6 +56,24 @@ impl CircuitVariable for PoseidonHashOutVariable {
     }
 }
 
+/// Encoded as the concatenation of the encodings of its field elements.
+impl ValueSerializable for PoseidonHashOutVariable {
+    fn nb_value_bytes() -> usize {
+        ArrayVariable::<Variable, NUM_HASH_OUT_ELTS>::nb_value_bytes()
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        ArrayVariable::<Variable, NUM_HASH_OUT_ELTS>::to_bytes(value.elements.to_vec())
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        let elements = ArrayVariable::<Variable, NUM_HASH_OUT_ELTS>::from_bytes(bytes)?;
+        Ok(HashOut {
+            elements: elements.try_into().unwrap(),
+        })
+    }
+}
+
 /// Implements the Poseidon hash for CircuitBuilder.
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// Computes the Poseidon hash of the given variables with no padding.
@@ -1,4 +1,4 @@
-use super::generator::{AsyncHintData, AsyncHintDataRef};
+use super::generator::{AsyncHintData, AsyncHintDataRef, AsyncHintRef};
 use super::hint::AsyncHint;
 use crate::frontend::vars::{OutputVariableStream, VariableStream};
 use crate::prelude::{CircuitBuilder, PlonkParameters};
@@ -12,6 +12,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> OutputVariableStream<L, D> {
         let output_stream = VariableStream::new();
         let hint_data = AsyncHintData::new(hint, input_stream, output_stream.clone());
+        if H::NETWORK {
+            self.network_generator_ids
+                .insert(AsyncHintRef::<L, D>::id(H::id()));
+        }
         let hint_id = self.hints.len();
         self.hints.push(Box::new(hint_data.clone()));
 
@@ -67,6 +67,11 @@ pub trait AsyncHint<L: PlonkParameters<D>, const D: usize>:
     fn id() -> String {
         std::any::type_name::<Self>().to_string()
     }
+
+    /// Whether this hint fetches its values from the network.
+    ///
+    /// See [`Hint::NETWORK`](crate::frontend::hint::simple::hint::Hint::NETWORK).
+    const NETWORK: bool = false;
 }
 
 /// A version of `AsyncHint` that that is [object safe][1] and can be used as a trait object.
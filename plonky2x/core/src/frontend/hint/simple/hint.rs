@@ -49,6 +49,12 @@ pub trait Hint<L: PlonkParameters<D>, const D: usize>:
     fn id() -> String {
         std::any::type_name::<Self>().to_string()
     }
+
+    /// Whether this hint fetches its values from the network.
+    ///
+    /// The values written by such hints are captured when preparing the inputs of a proof, so
+    /// that the proof can later be generated without network access.
+    const NETWORK: bool = false;
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
@@ -60,6 +66,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let output_stream = VariableStream::new();
 
         let generator = HintSimpleGenerator::new(input_stream, output_stream.clone(), hint);
+        if H::NETWORK {
+            self.network_generator_ids.insert(H::id());
+        }
         let hint_id = self.hints.len();
         self.hints.push(Box::new(generator));
 
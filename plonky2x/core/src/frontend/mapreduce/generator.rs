@@ -1,6 +1,8 @@
 use core::marker::PhantomData;
+use std::path::PathBuf;
 
 use array_macro::array;
+use itertools::Itertools;
 use log::debug;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::Target;
@@ -10,11 +12,70 @@ use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 use plonky2::plonk::proof::ProofWithPublicInputsTarget;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 
+use super::store::MapReduceProofStore;
 use super::{MapReduceInputVariable, MapReduceInputVariableValue};
-use crate::backend::circuit::{CircuitSerializer, PublicInput};
+use crate::backend::circuit::{CircuitSerializer, PublicInput, PublicOutput};
 use crate::backend::prover::{EnvProver, ProverOutputs};
 use crate::prelude::{CircuitVariable, PlonkParameters};
 
+/// Generates the proofs for one layer of the proof tree.
+///
+/// If a proof store is given, the proofs that were already persisted for the inputs of this layer
+/// are reused, and the missing positions are proven in one batch whose proofs are then persisted.
+fn prove_layer<L, S, const D: usize>(
+    prover: &EnvProver,
+    store: Option<&MapReduceProofStore>,
+    layer: usize,
+    circuit_id: &str,
+    inputs: &[PublicInput<L, D>],
+) -> ProverOutputs<L, D>
+where
+    L: PlonkParameters<D>,
+    S: CircuitSerializer,
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+{
+    let store = match store {
+        Some(store) => store,
+        None => return prover.batch_prove::<L, S, D>(circuit_id, inputs).unwrap(),
+    };
+
+    let mut proofs = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| store.load::<L, D>(layer, i, input))
+        .collect_vec();
+    let missing = proofs
+        .iter()
+        .positions(|proof| proof.is_none())
+        .collect_vec();
+    debug!(
+        "layer {}: reused {} of {} persisted proofs",
+        layer,
+        inputs.len() - missing.len(),
+        inputs.len()
+    );
+
+    if !missing.is_empty() {
+        let missing_inputs = missing.iter().map(|&i| inputs[i].clone()).collect_vec();
+        let (missing_outputs, metrics) = prover
+            .batch_prove_with_metrics::<L, S, D>(circuit_id, &missing_inputs)
+            .unwrap();
+        let (missing_proofs, _) = missing_outputs.materialize().unwrap();
+        let metrics = match metrics {
+            Some(metrics) => metrics.into_iter().map(Some).collect_vec(),
+            None => vec![None; missing.len()],
+        };
+        for ((i, proof), metrics) in missing.into_iter().zip(missing_proofs).zip(metrics) {
+            store.save::<L, D>(layer, i, &inputs[i], &proof, metrics);
+            proofs[i] = Some(proof);
+        }
+    }
+
+    let proofs = proofs.into_iter().map(Option::unwrap).collect_vec();
+    let outputs = proofs.iter().map(|_| PublicOutput::None()).collect_vec();
+    ProverOutputs::Local(proofs, outputs)
+}
+
 #[derive(Debug, Clone)]
 pub struct MapReduceGenerator<L, Ctx, Input, Output, Serializer, const B: usize, const D: usize>
 where
@@ -41,6 +102,9 @@ where
     /// The proof target for the final circuit proof.
     pub proof: ProofWithPublicInputsTarget<D>,
 
+    /// The directory that the intermediate proofs are persisted to, if any.
+    pub proof_dir: Option<PathBuf>,
+
     /// Phantom data.
     pub _phantom: PhantomData<(L, Output, Serializer)>,
 }
@@ -106,9 +170,17 @@ where
         }
 
         // Generate the proofs for the map layer.
-        let mut outputs = prover
-            .batch_prove::<L, Serializer, D>(&self.map_circuit_id, &map_inputs)
-            .unwrap();
+        let store = self
+            .proof_dir
+            .as_ref()
+            .map(|dir| MapReduceProofStore::new(dir, &self.map_circuit_id, &map_inputs));
+        let mut outputs = prove_layer::<L, Serializer, D>(
+            &prover,
+            store.as_ref(),
+            0,
+            &self.map_circuit_id,
+            &map_inputs,
+        );
 
         // Process each reduce layer.
         let nb_reduce_layers = ((self.inputs.len() / B) as f64).log2().ceil() as usize;
@@ -140,9 +212,13 @@ where
 
             // Generate the proofs for the reduce layer and update the proofs buffer.
             debug!("reduce batch proofs");
-            outputs = prover
-                .batch_prove::<L, Serializer, D>(&self.reduce_circuit_ids[i], &reduce_inputs)
-                .unwrap();
+            outputs = prove_layer::<L, Serializer, D>(
+                &prover,
+                store.as_ref(),
+                i + 1,
+                &self.reduce_circuit_ids[i],
+                &reduce_inputs,
+            );
         }
 
         // Set the proof target with the final proof.
@@ -172,7 +248,16 @@ where
         }
 
         // Write proof target.
-        dst.write_target_proof_with_public_inputs(&self.proof)
+        dst.write_target_proof_with_public_inputs(&self.proof)?;
+
+        // Write proof directory.
+        dst.write_bool(self.proof_dir.is_some())?;
+        if let Some(proof_dir) = &self.proof_dir {
+            let proof_dir = proof_dir.to_string_lossy();
+            dst.write_usize(proof_dir.len())?;
+            dst.write_all(proof_dir.as_bytes())?;
+        }
+        Ok(())
     }
 
     fn deserialize(src: &mut Buffer, _: &CommonCircuitData<L::Field, D>) -> IoResult<Self> {
@@ -205,12 +290,23 @@ where
         // Read proof.
         let proof = src.read_target_proof_with_public_inputs()?;
 
+        // Read proof directory.
+        let proof_dir = if src.read_bool()? {
+            let proof_dir_length = src.read_usize()?;
+            let mut proof_dir = vec![0u8; proof_dir_length];
+            src.read_exact(&mut proof_dir)?;
+            Some(PathBuf::from(String::from_utf8(proof_dir).unwrap()))
+        } else {
+            None
+        };
+
         Ok(Self {
             map_circuit_id: String::from_utf8(map_circuit_id).unwrap(),
             reduce_circuit_ids,
             ctx,
             inputs,
             proof,
+            proof_dir,
             _phantom: PhantomData,
         })
     }
@@ -249,6 +345,9 @@ pub struct MapReduceDynamicGenerator<
     /// The proof target for the final circuit proof.
     pub proof: ProofWithPublicInputsTarget<D>,
 
+    /// The directory that the intermediate proofs are persisted to, if any.
+    pub proof_dir: Option<PathBuf>,
+
     /// Phantom data.
     pub _phantom: PhantomData<(L, Output, Serializer)>,
 }
@@ -317,9 +416,17 @@ where
         }
 
         // Generate the proofs for the map layer.
-        let mut outputs = prover
-            .batch_prove::<L, Serializer, D>(&self.map_circuit_id, &map_inputs)
-            .unwrap();
+        let store = self
+            .proof_dir
+            .as_ref()
+            .map(|dir| MapReduceProofStore::new(dir, &self.map_circuit_id, &map_inputs));
+        let mut outputs = prove_layer::<L, Serializer, D>(
+            &prover,
+            store.as_ref(),
+            0,
+            &self.map_circuit_id,
+            &map_inputs,
+        );
 
         // Process each reduce layer.
         let nb_reduce_layers = ((self.inputs.len() / B) as f64).log2().ceil() as usize;
@@ -351,9 +458,13 @@ where
 
             // Generate the proofs for the reduce layer and update the proofs buffer.
             debug!("reduce batch proofs");
-            outputs = prover
-                .batch_prove::<L, Serializer, D>(&self.reduce_circuit_ids[i], &reduce_inputs)
-                .unwrap();
+            outputs = prove_layer::<L, Serializer, D>(
+                &prover,
+                store.as_ref(),
+                i + 1,
+                &self.reduce_circuit_ids[i],
+                &reduce_inputs,
+            );
         }
 
         // Set the proof target with the final proof.
@@ -383,7 +494,16 @@ where
         }
 
         // Write proof target.
-        dst.write_target_proof_with_public_inputs(&self.proof)
+        dst.write_target_proof_with_public_inputs(&self.proof)?;
+
+        // Write proof directory.
+        dst.write_bool(self.proof_dir.is_some())?;
+        if let Some(proof_dir) = &self.proof_dir {
+            let proof_dir = proof_dir.to_string_lossy();
+            dst.write_usize(proof_dir.len())?;
+            dst.write_all(proof_dir.as_bytes())?;
+        }
+        Ok(())
     }
 
     fn deserialize(src: &mut Buffer, _: &CommonCircuitData<L::Field, D>) -> IoResult<Self> {
@@ -415,12 +535,23 @@ where
         // Read proof.
         let proof = src.read_target_proof_with_public_inputs()?;
 
+        // Read proof directory.
+        let proof_dir = if src.read_bool()? {
+            let proof_dir_length = src.read_usize()?;
+            let mut proof_dir = vec![0u8; proof_dir_length];
+            src.read_exact(&mut proof_dir)?;
+            Some(PathBuf::from(String::from_utf8(proof_dir).unwrap()))
+        } else {
+            None
+        };
+
         Ok(Self {
             map_circuit_id: String::from_utf8(map_circuit_id).unwrap(),
             reduce_circuit_ids,
             ctx,
             inputs,
             proof,
+            proof_dir,
             _phantom: PhantomData,
         })
     }
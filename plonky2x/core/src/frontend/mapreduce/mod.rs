@@ -14,11 +14,18 @@
 //!
 //! Under the hood, we compute each map in a seperate proof and perform the reductions by generating
 //! a proof for each reduction between two proofs until we have a single proof.
+//!
+//! Long jobs can be made resumable with [`CircuitBuilder::set_mapreduce_proof_dir`]: each
+//! intermediate proof is then persisted to that directory keyed by its position in the proof tree,
+//! and proofs that already exist there for the same input are reused instead of being generated
+//! again.
 
 pub mod generator;
+pub mod store;
 
 use core::fmt::Debug;
 use core::marker::PhantomData;
+use std::path::PathBuf;
 
 use itertools::Itertools;
 use log::debug;
@@ -79,7 +86,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
     {
         let mut builder = CircuitBuilder::<L, D>::new();
-        builder.beacon_client = self.beacon_client.clone();
+        #[cfg(feature = "beacon")]
+        {
+            builder.beacon_client = self.beacon_client.clone();
+        }
         builder.execution_client = self.execution_client.clone();
 
         // Read the inputs.
@@ -156,6 +166,12 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         builder.build()
     }
 
+    /// Persists the intermediate proofs of the map/reduce jobs added after this call to `dir`, so
+    /// that an interrupted job can be resumed. See [`store::MapReduceProofStore`].
+    pub fn set_mapreduce_proof_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.mapreduce_proof_dir = Some(dir.into());
+    }
+
     pub fn mapreduce<Ctx, Input, Output, Serializer, const B: usize, MapFn, ReduceFn>(
         &mut self,
         ctx: Ctx,
@@ -234,6 +250,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             ctx: ctx.clone(),
             inputs: inputs.clone(),
             proof: final_proof.clone(),
+            proof_dir: self.mapreduce_proof_dir.clone(),
             _phantom: PhantomData,
         };
         self.add_simple_generator(generator);
@@ -257,6 +274,38 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         output.output
     }
 
+    /// Same as [`CircuitBuilder::mapreduce`], but each map task receives its batch of `B` inputs
+    /// as a slice and emits a single aggregated output for the batch.
+    ///
+    /// Batching generates `inputs.len() / B` map proofs instead of one per input, and
+    /// correspondingly fewer reduce proofs, which amortizes the recursion overhead over the batch.
+    /// The batch size is the const `B` because the map circuit is built for a fixed batch.
+    pub fn mapreduce_batched<Ctx, Input, Output, Serializer, const B: usize, MapBatchFn, ReduceFn>(
+        &mut self,
+        ctx: Ctx,
+        inputs: Vec<Input::ValueType<L::Field>>,
+        map_batch_fn: MapBatchFn,
+        reduce_fn: ReduceFn,
+    ) -> Output
+    where
+        Ctx: CircuitVariable,
+        Input: CircuitVariable,
+        Output: CircuitVariable,
+        Serializer: CircuitSerializer,
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+        <Input as CircuitVariable>::ValueType<<L as PlonkParameters<D>>::Field>: Sync + Send,
+        MapBatchFn: Fn(Ctx, &[Input], &mut CircuitBuilder<L, D>) -> Output,
+        ReduceFn: Fn(Ctx, Output, Output, &mut CircuitBuilder<L, D>) -> Output,
+    {
+        self.mapreduce::<Ctx, Input, Output, Serializer, B, _, _>(
+            ctx,
+            inputs,
+            |ctx, batch, builder| map_batch_fn(ctx, batch.as_slice(), builder),
+            reduce_fn,
+        )
+    }
+
     pub fn mapreduce_dynamic<Ctx, Input, Output, Serializer, const B: usize, MapFn, ReduceFn>(
         &mut self,
         ctx: Ctx,
@@ -331,6 +380,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             ctx: ctx.clone(),
             inputs: inputs.clone(),
             proof: final_proof.clone(),
+            proof_dir: self.mapreduce_proof_dir.clone(),
             _phantom: PhantomData,
         };
         self.add_simple_generator(generator);
@@ -357,6 +407,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::fs;
+
     use plonky2::field::goldilocks_field::GoldilocksField;
     use plonky2::field::types::Field;
 
@@ -405,4 +457,130 @@ pub(crate) mod tests {
         let result = output.read::<Variable>();
         println!("{}", result);
     }
+
+    #[test]
+    fn test_mapreduce_batched() {
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let ctx = builder.constant::<Variable>(F::from_canonical_u64(3));
+        let inputs = (0..16).map(F::from_canonical_u64).collect::<Vec<_>>();
+
+        // Scales each input by the context and sums the results.
+        let map_batch_fn =
+            |ctx: Variable, batch: &[Variable], builder: &mut CircuitBuilder<L, D>| {
+                let mut sum = builder.zero::<Variable>();
+                for input in batch.iter() {
+                    let scaled = builder.mul(*input, ctx);
+                    sum = builder.add(sum, scaled);
+                }
+                sum
+            };
+        let reduce_fn =
+            |_: Variable, left: Variable, right: Variable, builder: &mut CircuitBuilder<L, D>| {
+                builder.add(left, right)
+            };
+
+        let batched = builder
+            .mapreduce_batched::<Variable, Variable, Variable, DefaultSerializer, 4, _, _>(
+                ctx,
+                inputs.clone(),
+                map_batch_fn,
+                reduce_fn,
+            );
+        let unbatched = builder
+            .mapreduce_batched::<Variable, Variable, Variable, DefaultSerializer, 1, _, _>(
+                ctx,
+                inputs,
+                map_batch_fn,
+                reduce_fn,
+            );
+        builder.assert_is_equal(batched, unbatched);
+        builder.write(batched);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<Variable>(), F::from_canonical_u64(3 * 120));
+    }
+
+    #[test]
+    fn test_mapreduce_resumes_from_persisted_proofs() {
+        env_logger::try_init().unwrap_or_default();
+
+        let proof_dir = tempfile::tempdir().unwrap();
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_mapreduce_proof_dir(proof_dir.path());
+        let ctx = builder.constant::<Variable>(F::from_canonical_u64(5));
+        let inputs = (0..8).map(F::from_canonical_u64).collect::<Vec<_>>();
+        let output = builder.mapreduce::<Variable, Variable, Variable, DefaultSerializer, 1, _, _>(
+            ctx,
+            inputs,
+            |ctx, inputs, builder| builder.mul(inputs[0], ctx),
+            |_, left, right, builder| builder.add(left, right),
+        );
+        builder.write(output);
+        let circuit = builder.build();
+        let input = circuit.input();
+
+        // Run the job once so that every proof of the tree is persisted.
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<Variable>(), F::from_canonical_u64(5 * 28));
+
+        // The directory only holds this job.
+        let job_dirs = fs::read_dir(proof_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(job_dirs.len(), 1);
+        let job_dir = &job_dirs[0];
+
+        // Simulate an interruption after half of the map tasks by only keeping their proofs, and
+        // swap in the proof of another position at the first one.
+        fs::copy(job_dir.join("0_1.json"), job_dir.join("0_0.json")).unwrap();
+        let swapped = fs::read(job_dir.join("0_0.json")).unwrap();
+        let mut kept = Vec::new();
+        for entry in fs::read_dir(job_dir).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_str().unwrap().to_string();
+            if ["0_1.json", "0_2.json", "0_3.json"].contains(&name.as_str()) {
+                let modified = fs::metadata(&path).unwrap().modified().unwrap();
+                kept.push((path, modified));
+            } else if name != "0_0.json" {
+                fs::remove_file(path).unwrap();
+            }
+        }
+        assert_eq!(kept.len(), 3);
+
+        // Restarting reuses the persisted map proofs, regenerates the swapped proof, whose input
+        // does not match its position, and regenerates the rest of the tree.
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<Variable>(), F::from_canonical_u64(5 * 28));
+        for (path, modified) in kept {
+            assert_eq!(fs::metadata(path).unwrap().modified().unwrap(), modified);
+        }
+        assert_ne!(fs::read(job_dir.join("0_0.json")).unwrap(), swapped);
+        for position in ["0_4.json", "0_7.json", "1_3.json", "2_1.json", "3_0.json"] {
+            assert!(job_dir.join(position).exists());
+        }
+
+        // Locally generated proofs are persisted with their metrics, whose size is that of the
+        // proof as it is serialized in the file.
+        let persisted: serde_json::Value =
+            serde_json::from_slice(&fs::read(job_dir.join("3_0.json")).unwrap()).unwrap();
+        let proof = hex::decode(
+            persisted["proof"]
+                .as_str()
+                .unwrap()
+                .trim_start_matches("0x"),
+        )
+        .unwrap();
+        assert_eq!(
+            persisted["metrics"]["proof_bytes"].as_u64().unwrap() as usize,
+            proof.len()
+        );
+    }
 }
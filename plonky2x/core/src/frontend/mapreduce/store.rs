@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::debug;
+use plonky2::field::types::PrimeField64;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::circuit::{PlonkParameters, PublicInput};
+use crate::backend::prover::ProveMetrics;
+use crate::utils::hash::sha256;
+use crate::utils::serde::{deserialize_proof_with_pis, serialize_proof_with_pis};
+
+/// A proof persisted to disk, with the proof stored in the same hex format as function results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+struct PersistedProof<L: PlonkParameters<D>, const D: usize> {
+    /// The digest of the input that the proof was generated for.
+    input_digest: String,
+    #[serde(serialize_with = "serialize_proof_with_pis")]
+    #[serde(deserialize_with = "deserialize_proof_with_pis")]
+    proof: ProofWithPublicInputs<L::Field, L::Config, D>,
+    /// The timing and size metrics of the proof, if it was generated locally. The size is that of
+    /// the proof as serialized in this file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metrics: Option<ProveMetrics>,
+}
+
+/// Stores the intermediate proofs of a map/reduce job keyed by their position in the proof tree,
+/// so that an interrupted job can be resumed without regenerating them.
+///
+/// Layer `0` holds the map proofs and layer `i + 1` holds the proofs of the `i`-th reduce layer.
+/// Proofs are stored under a key derived from the map circuit and the map inputs, so different
+/// jobs can share the same directory. Each proof is stored with the digest of its input, and a
+/// proof whose input differs from the one at its position is not reused.
+#[derive(Debug, Clone)]
+pub struct MapReduceProofStore {
+    dir: PathBuf,
+}
+
+/// The digest of the input of a map or reduce proof. The input of a reduce proof is digested
+/// through the public inputs of its child proofs.
+fn input_digest<L: PlonkParameters<D>, const D: usize>(input: &PublicInput<L, D>) -> String {
+    let elements = match input {
+        PublicInput::Elements(elements) => elements.clone(),
+        PublicInput::RecursiveProofs(proofs, elements) => proofs
+            .iter()
+            .flat_map(|proof| proof.public_inputs.iter().copied())
+            .chain(elements.iter().copied())
+            .collect(),
+        _ => panic!("map/reduce inputs must be field elements or recursive proofs"),
+    };
+    let preimage = elements
+        .iter()
+        .flat_map(|e| e.to_canonical_u64().to_le_bytes())
+        .collect::<Vec<_>>();
+    hex::encode(sha256(&preimage))
+}
+
+impl MapReduceProofStore {
+    /// Creates a store in `dir` for the job with the given map circuit and map inputs.
+    pub fn new<L: PlonkParameters<D>, const D: usize>(
+        dir: impl AsRef<Path>,
+        map_circuit_id: &str,
+        map_inputs: &[PublicInput<L, D>],
+    ) -> Self {
+        let mut preimage = map_circuit_id.as_bytes().to_vec();
+        for input in map_inputs.iter() {
+            match input {
+                PublicInput::Elements(elements) => elements
+                    .iter()
+                    .for_each(|e| preimage.extend(e.to_canonical_u64().to_le_bytes())),
+                _ => panic!("map inputs must be field elements"),
+            }
+        }
+        let job_id = hex::encode(sha256(&preimage));
+        Self {
+            dir: dir.as_ref().join(job_id),
+        }
+    }
+
+    /// The path of the proof at the given position in the proof tree.
+    pub fn path(&self, layer: usize, index: usize) -> PathBuf {
+        self.dir.join(format!("{}_{}.json", layer, index))
+    }
+
+    /// Loads the proof at the given position, if it has been persisted for `input`.
+    pub fn load<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        layer: usize,
+        index: usize,
+        input: &PublicInput<L, D>,
+    ) -> Option<ProofWithPublicInputs<L::Field, L::Config, D>> {
+        let bytes = fs::read(self.path(layer, index)).ok()?;
+        let persisted: PersistedProof<L, D> = serde_json::from_slice(&bytes).ok()?;
+        if persisted.input_digest != input_digest(input) {
+            debug!(
+                "proof {}_{} was persisted for a different input",
+                layer, index
+            );
+            return None;
+        }
+        Some(persisted.proof)
+    }
+
+    /// Persists the proof of `input` at the given position, along with its metrics if given.
+    pub fn save<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        layer: usize,
+        index: usize,
+        input: &PublicInput<L, D>,
+        proof: &ProofWithPublicInputs<L::Field, L::Config, D>,
+        metrics: Option<ProveMetrics>,
+    ) {
+        fs::create_dir_all(&self.dir).unwrap();
+        let metrics = metrics.map(|metrics| ProveMetrics {
+            proof_bytes: bincode::serialize(proof).unwrap().len(),
+            ..metrics
+        });
+        let persisted = PersistedProof::<L, D> {
+            input_digest: input_digest(input),
+            proof: proof.clone(),
+            metrics,
+        };
+        // Write to a temporary file first so that an interruption never leaves a partial proof.
+        let path = self.path(layer, index);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&persisted).unwrap()).unwrap();
+        fs::rename(tmp_path, path).unwrap();
+    }
+}
@@ -1,4 +1,6 @@
 pub mod simple;
+#[cfg(feature = "ssz")]
+pub mod ssz;
 pub mod tendermint;
 pub mod tree;
 pub mod utils;
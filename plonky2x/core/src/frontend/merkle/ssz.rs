@@ -0,0 +1,386 @@
+//! Gadgets for verifying simple serialize (SSZ) merkle proofs and computing SSZ roots.
+
+use ethers::types::H256;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{
+    ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable, EvmVariable, SSZVariable, Ssz,
+};
+use crate::utils::hash::sha256;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verify a simple serialize (ssz) merkle proof with a dynamic index.
+    #[allow(unused_variables)]
+    pub fn ssz_verify_proof(
+        &mut self,
+        root: Bytes32Variable,
+        leaf: Bytes32Variable,
+        branch: &[Bytes32Variable],
+        gindex: U64Variable,
+    ) {
+        let expected_root = self.ssz_restore_merkle_root(leaf, branch, gindex);
+        self.assert_is_equal(root, expected_root);
+    }
+
+    /// Verify a simple serialize (ssz) merkle proof with a constant index.
+    #[allow(unused_variables)]
+    pub fn ssz_verify_proof_const(
+        &mut self,
+        root: Bytes32Variable,
+        leaf: Bytes32Variable,
+        branch: &[Bytes32Variable],
+        gindex: u64,
+    ) {
+        let expected_root = self.ssz_restore_merkle_root_const(leaf, branch, gindex);
+        self.assert_is_equal(root, expected_root);
+    }
+
+    /// Verify that `field_leaf` is the root of the field at `field_index` of a container of type
+    /// `C` with the root `container_root`. The branch holds the siblings from the field chunk up
+    /// to the container root.
+    pub fn ssz_verify_field<C: Ssz>(
+        &mut self,
+        container_root: Bytes32Variable,
+        field_index: usize,
+        field_leaf: Bytes32Variable,
+        branch: &[Bytes32Variable],
+    ) {
+        let gindex = C::field_gindex(field_index);
+        let depth = C::chunk_count().next_power_of_two().trailing_zeros() as usize;
+        assert_eq!(
+            branch.len(),
+            depth,
+            "a field proof of a container of {} fields has {} siblings",
+            C::chunk_count(),
+            depth
+        );
+        self.ssz_verify_proof_const(container_root, field_leaf, branch, gindex);
+    }
+
+    /// Computes the expected merkle root given a leaf, branch, and dynamic index.
+    pub fn ssz_restore_merkle_root(
+        &mut self,
+        leaf: Bytes32Variable,
+        branch: &[Bytes32Variable],
+        gindex: U64Variable,
+    ) -> Bytes32Variable {
+        let bits = self.to_le_bits(gindex);
+        let mut hash = leaf;
+        for i in 0..branch.len() {
+            let left = branch[i].as_bytes();
+            let right = hash.as_bytes();
+
+            let mut data = [self.init_unsafe::<ByteVariable>(); 64];
+            data[..32].copy_from_slice(&left);
+            data[32..].copy_from_slice(&right);
+            let case1 = self.curta_sha256(&data);
+
+            data[..32].copy_from_slice(&right);
+            data[32..].copy_from_slice(&left);
+            let case2 = self.curta_sha256(&data);
+
+            hash = self.select(bits[i], case1, case2);
+        }
+        hash
+    }
+
+    /// Computes the expected merkle root given a leaf, branch, and deterministic index.
+    pub fn ssz_restore_merkle_root_const(
+        &mut self,
+        leaf: Bytes32Variable,
+        branch: &[Bytes32Variable],
+        gindex: u64,
+    ) -> Bytes32Variable {
+        let mut hash = leaf;
+        for i in 0..branch.len() {
+            let (first, second) = if (gindex >> i) & 1 == 1 {
+                (branch[i].as_bytes(), hash.as_bytes())
+            } else {
+                (hash.as_bytes(), branch[i].as_bytes())
+            };
+            let mut data = [ByteVariable::init_unsafe(self); 64];
+            data[..32].copy_from_slice(&first);
+            data[32..].copy_from_slice(&second);
+            hash = self.curta_sha256(&data);
+        }
+        hash
+    }
+
+    /// Computes the leaf that is mixed into the root of an SSZ list, i.e. the length of the list
+    /// encoded as a little-endian uint256.
+    ///
+    /// This is the canonical [`ValueSerializable`](crate::frontend::vars::ValueSerializable)
+    /// encoding of the length reversed and zero-padded on the right to 32 bytes.
+    pub fn ssz_length_leaf(&mut self, length: U64Variable) -> Bytes32Variable {
+        let zero = self.constant::<ByteVariable>(0);
+        let mut length_bytes = length.encode(self);
+        length_bytes.reverse();
+        length_bytes.resize(32, zero);
+        Bytes32Variable(BytesVariable(length_bytes.try_into().unwrap()))
+    }
+
+    /// Computes the SSZ root of a `List[uint64, limit]`, such as the balances or slashings of a
+    /// beacon state.
+    ///
+    /// The uints are packed little-endian four to a chunk, the chunks are merkleized in a tree
+    /// with room for `limit` uints, and the length of the list is mixed into the root.
+    pub fn ssz_hash_uint_list(&mut self, values: &[U64Variable], limit: u64) -> Bytes32Variable {
+        assert!(
+            values.len() as u64 <= limit,
+            "list of {} uints exceeds its limit of {}",
+            values.len(),
+            limit
+        );
+
+        // Pack the uints into chunks, zero padding the last one.
+        let zero = self.constant::<ByteVariable>(0);
+        let mut bytes = Vec::with_capacity(values.len() * 8);
+        for value in values.iter() {
+            let mut value_bytes = value.encode(self);
+            value_bytes.reverse();
+            bytes.extend(value_bytes);
+        }
+        bytes.resize(bytes.len().div_ceil(32).max(1) * 32, zero);
+        let chunks = bytes
+            .chunks(32)
+            .map(|chunk| Bytes32Variable(BytesVariable(chunk.try_into().unwrap())))
+            .collect::<Vec<_>>();
+
+        // Merkleize the chunks and extend the tree with zero subtrees up to the chunk limit.
+        let chunk_limit = limit.div_ceil(4).max(1).next_power_of_two();
+        let mut depth = chunks.len().next_power_of_two().trailing_zeros();
+        let mut zero_hash = [0u8; 32];
+        for _ in 0..depth {
+            zero_hash = sha256(&[zero_hash, zero_hash].concat());
+        }
+        let mut root = self.ssz_merkleize(&chunks);
+        while depth < chunk_limit.trailing_zeros() {
+            let zero_subtree = self.constant::<Bytes32Variable>(H256::from(zero_hash));
+            root = self.curta_sha256_pair(root, zero_subtree);
+            zero_hash = sha256(&[zero_hash, zero_hash].concat());
+            depth += 1;
+        }
+
+        let length = self.constant::<U64Variable>(values.len() as u64);
+        let length_leaf = self.ssz_length_leaf(length);
+        self.curta_sha256_pair(root, length_leaf)
+    }
+
+    pub fn ssz_hash_tree_root<V: SSZVariable>(&mut self, variable: V) -> Bytes32Variable {
+        variable.hash_tree_root(self)
+    }
+
+    /// Merkleizes chunks as the leaves of a tree padded with zero chunks to a power of two.
+    ///
+    /// The roots of the subtrees that only hold padding are constants, so a container of `n`
+    /// fields costs `n - 1` hashes at most.
+    pub fn ssz_merkleize(&mut self, chunks: &[Bytes32Variable]) -> Bytes32Variable {
+        assert!(!chunks.is_empty(), "cannot merkleize zero chunks");
+        let mut zero_hash = [0u8; 32];
+        let mut nodes = chunks.to_vec();
+        while nodes.len() > 1 {
+            if nodes.len() % 2 == 1 {
+                nodes.push(self.constant::<Bytes32Variable>(H256::from(zero_hash)));
+            }
+            let mut parents = Vec::with_capacity(nodes.len() / 2);
+            for pair in nodes.chunks(2) {
+                parents.push(self.curta_sha256_pair(pair[0], pair[1]));
+            }
+            nodes = parents;
+            zero_hash = sha256(&[zero_hash, zero_hash].concat());
+        }
+        nodes[0]
+    }
+
+    pub fn ssz_hash_leafs(&mut self, leafs: &[Bytes32Variable]) -> Bytes32Variable {
+        let mut leafs = leafs.to_vec();
+        while leafs.len() != 1 {
+            let mut tmp = Vec::new();
+            for i in 0..leafs.len() / 2 {
+                tmp.push(self.curta_sha256_pair(leafs[i * 2], leafs[i * 2 + 1]));
+            }
+            leafs = tmp;
+        }
+        leafs[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::builder::CircuitBuilder;
+    use crate::frontend::uint::uint64::U64Variable;
+    use crate::frontend::vars::Bytes32Variable;
+    use crate::utils::bytes32;
+    use crate::utils::test_utils::assert_constraint_failure;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_restore_merkle_root_equal() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let leaf = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
+        ));
+        let index = builder.constant::<U64Variable>(2);
+        let branch = vec![
+            builder.constant::<Bytes32Variable>(bytes32!(
+                "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
+            )),
+            builder.constant::<Bytes32Variable>(bytes32!(
+                "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
+            )),
+        ];
+        let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xac0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
+        ));
+
+        let computed_root = builder.ssz_restore_merkle_root(leaf, &branch, index);
+        builder.assert_is_equal(expected_root, computed_root);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_restore_merkle_root_unequal() {
+        env_logger::try_init().unwrap_or_default();
+
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let leaf = builder.constant::<Bytes32Variable>(bytes32!(
+                    "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
+                ));
+                let index = builder.constant::<U64Variable>(2);
+                let branch = vec![
+                    builder.constant::<Bytes32Variable>(bytes32!(
+                        "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
+                    )),
+                    builder.constant::<Bytes32Variable>(bytes32!(
+                        "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
+                    )),
+                ];
+                let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
+                    "0xbd0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
+                ));
+                let computed_root = builder.ssz_restore_merkle_root(leaf, &branch, index);
+                builder.assert_is_equal(expected_root, computed_root);
+            },
+            |_| {},
+            None,
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_restore_merkle_root_const_equal() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let leaf = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
+        ));
+        let index = 2;
+        let branch = vec![
+            builder.constant::<Bytes32Variable>(bytes32!(
+                "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
+            )),
+            builder.constant::<Bytes32Variable>(bytes32!(
+                "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
+            )),
+        ];
+        let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xac0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
+        ));
+        let computed_root = builder.ssz_restore_merkle_root_const(leaf, &branch, index);
+        builder.assert_is_equal(expected_root, computed_root);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_restore_merkle_root_const_unequal() {
+        env_logger::try_init().unwrap_or_default();
+
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let leaf = builder.constant::<Bytes32Variable>(bytes32!(
+                    "0xa1b2c3d4e5f60718291a2b3c4d5e6f708192a2b3c4d5e6f7a1b2c3d4e5f60718"
+                ));
+                let index = 2;
+                let branch = vec![
+                    builder.constant::<Bytes32Variable>(bytes32!(
+                        "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
+                    )),
+                    builder.constant::<Bytes32Variable>(bytes32!(
+                        "0xfedcba0987654321fedcba0987654321fedcba0987654321fedcba0987654321"
+                    )),
+                ];
+                let expected_root = builder.constant::<Bytes32Variable>(bytes32!(
+                    "0xbd0757982d17231f28ac33c08f1dd7f420a60cec25bf517ac9e9b35d8543082f"
+                ));
+                let computed_root = builder.ssz_restore_merkle_root_const(leaf, &branch, index);
+                builder.assert_is_equal(expected_root, computed_root);
+            },
+            |_| {},
+            None,
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_hash_uint_list() {
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        // A list shorter than a single chunk, with the limit of the balances list.
+        let short = [1, 2, 3].map(|v| builder.constant::<U64Variable>(v));
+        let root = builder.ssz_hash_uint_list(&short, 1 << 40);
+        let expected = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xf9112cc27170de4726eb26d4a4e8680b16a26e52540e5c831703eaddd5a7b23f"
+        ));
+        builder.assert_is_equal(root, expected);
+
+        // A list spanning three chunks, the last of them partially filled.
+        let long = (0..10)
+            .map(|i| builder.constant::<U64Variable>(32_000_000_000 + i * 1_000_003))
+            .collect::<Vec<_>>();
+        let root = builder.ssz_hash_uint_list(&long, 16);
+        let expected = builder.constant::<Bytes32Variable>(bytes32!(
+            "0x62894fad5b347daf2b0a7d5b8824a8c4d573b63c4b9586801378cdc2a1a109dc"
+        ));
+        builder.assert_is_equal(root, expected);
+
+        // The empty list.
+        let root = builder.ssz_hash_uint_list(&[], 16);
+        let expected = builder.constant::<Bytes32Variable>(bytes32!(
+            "0x28ba1834a3a7b657460ce79fa3a1d909ab8828fd557659d4d0554a9bdbc0ec30"
+        ));
+        builder.assert_is_equal(root, expected);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
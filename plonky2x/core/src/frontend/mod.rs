@@ -1,5 +1,6 @@
 pub mod builder;
 pub mod curta;
+#[cfg(feature = "ecc")]
 pub mod ecc;
 pub mod eth;
 pub mod extension;
@@ -5,7 +5,7 @@ use plonky2::hash::hash_types::RichField;
 use super::Uint;
 use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
 use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
-use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable};
+use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable, ValueSerializable};
 use crate::prelude::{
     Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
     CircuitVariable, Div, LessThanOrEqual, Mul, One, PlonkParameters, Rem, Sub, Variable, Zero,
@@ -44,3 +44,226 @@ impl Uint<8> for U256 {
 
 make_uint32_n!(U256Variable, U256, 8);
 make_uint32_n_tests!(U256Variable, U256, 8);
+
+/// Converts the limbs of a `U256Variable` to a `BigUintTarget`.
+fn u256_to_biguint(value: U256Variable) -> BigUintTarget {
+    BigUintTarget {
+        limbs: value.limbs.iter().map(|x| U32Target::from(*x)).collect(),
+    }
+}
+
+/// Combines `values` pairwise, layer by layer, so that each value goes through at most
+/// `ceil(log2(n))` applications of `op` instead of the `n - 1` of a left fold.
+fn balanced_reduce<T>(values: Vec<T>, mut op: impl FnMut(T, T) -> T) -> Option<T> {
+    let mut layer = values;
+    while layer.len() > 1 {
+        let mut next = Vec::with_capacity(layer.len().div_ceil(2));
+        let mut values = layer.into_iter();
+        while let Some(lhs) = values.next() {
+            match values.next() {
+                Some(rhs) => next.push(op(lhs, rhs)),
+                None => next.push(lhs),
+            }
+        }
+        layer = next;
+    }
+    layer.pop()
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the sum of `xs` modulo 2^256, or zero if `xs` is empty.
+    ///
+    /// The additions form a balanced tree rather than a chain, which keeps the carry chains of
+    /// the circuit at a logarithmic depth in the number of values.
+    pub fn sum_u256(&mut self, xs: &[U256Variable]) -> U256Variable {
+        match balanced_reduce(xs.to_vec(), |a, b| self.add(a, b)) {
+            Some(sum) => sum,
+            None => self.zero::<U256Variable>(),
+        }
+    }
+
+    /// Computes `(a * b) mod modulus` without truncating the product to 256 bits. The modulus
+    /// must not be zero.
+    pub fn u256_mulmod(
+        &mut self,
+        a: U256Variable,
+        b: U256Variable,
+        modulus: U256Variable,
+    ) -> U256Variable {
+        let product = self
+            .api
+            .mul_biguint(&u256_to_biguint(a), &u256_to_biguint(b));
+        let rem = self.api.rem_biguint(&product, &u256_to_biguint(modulus));
+        U256Variable {
+            limbs: array![i => rem.limbs[i].into(); 8],
+        }
+    }
+
+    /// Computes `base^exp mod modulus` by square-and-multiply over the 256 bits of `exp`. The
+    /// modulus must not be zero, and `0^0` is 1 as in the EVM modexp precompile.
+    pub fn u256_powmod(
+        &mut self,
+        base: U256Variable,
+        exp: U256Variable,
+        modulus: U256Variable,
+    ) -> U256Variable {
+        self.u256_powmod_bounded(base, exp, modulus, 256)
+    }
+
+    /// Computes `base^exp mod modulus` like [`CircuitBuilder::u256_powmod`], but only over the low
+    /// `exp_bits` bits of `exp`, which bounds the circuit to `exp_bits` squarings and
+    /// multiplications. The bits of `exp` above `exp_bits` are constrained to be zero.
+    pub fn u256_powmod_bounded(
+        &mut self,
+        base: U256Variable,
+        exp: U256Variable,
+        modulus: U256Variable,
+        exp_bits: usize,
+    ) -> U256Variable {
+        assert!(
+            exp_bits <= 256,
+            "the exponent of a U256 has at most 256 bits"
+        );
+        let bits = self.to_le_bits(exp);
+        let false_bit = self._false();
+        for bit in bits[exp_bits..].iter() {
+            self.assert_is_equal(*bit, false_bit);
+        }
+
+        let one = self.one::<U256Variable>();
+        let base = self.rem(base, modulus);
+        let mut result = self.rem(one, modulus);
+        for bit in bits[..exp_bits].iter().rev() {
+            result = self.u256_mulmod(result, result, modulus);
+            let product = self.u256_mulmod(result, base, modulus);
+            result = self.select(*bit, product, result);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod powmod_tests {
+    use super::*;
+    use crate::backend::circuit::DefaultParameters;
+    use crate::utils::test_utils::assert_constraint_failure;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_u256_powmod_bounded() {
+        let secp256k1_p = U256::from_str_radix(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap();
+        let generator_x = U256::from_str_radix(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            16,
+        )
+        .unwrap();
+        let cases = [
+            (
+                U256::from(4),
+                U256::from(13),
+                U256::from(497),
+                U256::from(445),
+            ),
+            (U256::from(3), U256::zero(), U256::from(7), U256::one()),
+            (U256::zero(), U256::from(5), U256::from(13), U256::zero()),
+            (U256::zero(), U256::zero(), U256::from(13), U256::one()),
+            (U256::from(5), U256::from(3), U256::one(), U256::zero()),
+            (
+                generator_x,
+                U256::from(0xdeadbeefcafebabeu64),
+                secp256k1_p,
+                U256::from_str_radix(
+                    "70474bfea6f62f05c3dba2bbf1b90c10ea29173b3ef3511de256c9979ca91866",
+                    16,
+                )
+                .unwrap(),
+            ),
+        ];
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let base = builder.read::<U256Variable>();
+        let exp = builder.read::<U256Variable>();
+        let modulus = builder.read::<U256Variable>();
+        let result = builder.u256_powmod_bounded(base, exp, modulus, 64);
+        builder.write(result);
+        let circuit = builder.build();
+
+        for (base, exp, modulus, expected) in cases {
+            let mut input = circuit.input();
+            input.write::<U256Variable>(base);
+            input.write::<U256Variable>(exp);
+            input.write::<U256Variable>(modulus);
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<U256Variable>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_u256_powmod_exponent_out_of_bounds() {
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let base = builder.read::<U256Variable>();
+                let exp = builder.read::<U256Variable>();
+                let modulus = builder.read::<U256Variable>();
+                builder.u256_powmod_bounded(base, exp, modulus, 8);
+            },
+            |input| {
+                input.write::<U256Variable>(U256::from(2));
+                input.write::<U256Variable>(U256::from(256));
+                input.write::<U256Variable>(U256::from(1000));
+            },
+            None,
+        );
+    }
+}
+
+#[cfg(test)]
+mod sum_tests {
+    use super::*;
+    use crate::backend::circuit::DefaultParameters;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_sum_u256() {
+        let values = (0..100u64)
+            .map(|i| (U256::MAX / 200) - U256::from(i * 0xdeadbeef))
+            .collect::<Vec<_>>();
+        let expected = values.iter().fold(U256::zero(), |sum, x| sum + *x);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let xs = values
+            .iter()
+            .map(|x| builder.constant::<U256Variable>(*x))
+            .collect::<Vec<_>>();
+        let sum = builder.sum_u256(&xs);
+        builder.write(sum);
+        let empty_sum = builder.sum_u256(&[]);
+        builder.write(empty_sum);
+        let circuit = builder.build();
+
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<U256Variable>(), expected);
+        assert_eq!(output.read::<U256Variable>(), U256::zero());
+    }
+
+    #[test]
+    fn test_balanced_reduce_depth() {
+        // The depth of a sum is one more than the deepest of its operands.
+        let depth = |a: usize, b: usize| a.max(b) + 1;
+        assert_eq!(balanced_reduce(vec![0; 100], depth), Some(7));
+        assert_eq!(vec![0; 100].into_iter().reduce(depth), Some(99));
+        assert_eq!(balanced_reduce(vec![0; 1], depth), Some(0));
+        assert_eq!(balanced_reduce(Vec::<usize>::new(), depth), None);
+    }
+}
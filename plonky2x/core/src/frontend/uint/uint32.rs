@@ -1,12 +1,15 @@
 use std::fmt::Debug;
 
+use anyhow::Result;
 use itertools::Itertools;
 use plonky2::iop::target::BoolTarget;
+use serde::{Deserialize, Serialize};
 
+use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
-use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
 use crate::frontend::uint::num::u32::gadgets::multiple_comparison::list_lte_circuit;
-use crate::frontend::vars::EvmVariable;
+use crate::frontend::vars::{ensure_nb_value_bytes, EvmVariable, ValueSerializable};
 use crate::prelude::*;
 
 /// A variable in the circuit representing a u32 value.
@@ -47,8 +50,24 @@ impl CircuitVariable for U32Variable {
         &self,
         builder: &mut CircuitBuilder<L, D>,
     ) {
-        let bits = builder.api.u32_to_bits_le(U32Target::from(*self));
-        let reconstructed_val = builder.api.le_sum(bits.iter());
+        // Decompose the value into little-endian bytes and range check each byte with a lookup.
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&self.variable);
+        let output_stream = builder.hint(input_stream, U32ByteDecompositionHint);
+        let bytes = output_stream.read_vec::<Variable>(builder, 4);
+
+        for byte in bytes.iter() {
+            builder.range_check_u8(*byte);
+        }
+
+        let mut reconstructed_val = bytes[3].0;
+        for byte in bytes[..3].iter().rev() {
+            reconstructed_val = builder.api.mul_const_add(
+                L::Field::from_canonical_u32(1 << 8),
+                reconstructed_val,
+                byte.0,
+            );
+        }
         builder.assert_is_equal(self.variable, Variable(reconstructed_val))
     }
 
@@ -66,6 +85,20 @@ impl CircuitVariable for U32Variable {
     }
 }
 
+/// Decomposes a `u32` value into its four little-endian bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct U32ByteDecompositionHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for U32ByteDecompositionHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let value = input_stream.read_value::<Variable>().to_canonical_u64();
+        for i in 0..4 {
+            output_stream
+                .write_value::<Variable>(L::Field::from_canonical_u64((value >> (8 * i)) & 0xff));
+        }
+    }
+}
+
 impl EvmVariable for U32Variable {
     fn encode<L: PlonkParameters<D>, const D: usize>(
         &self,
@@ -100,22 +133,21 @@ impl EvmVariable for U32Variable {
         // Target is composed of 32 bool targets, so it will be within U32Variable's range.
         Self::from_variables_unsafe(&[Variable(target)])
     }
+}
 
-    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
-        let mut bytes = vec![0_u8; 4];
-        for i in 0..4 {
-            bytes[i] = ((value >> ((4 - i - 1) * 8)) & 0xff) as u8;
-        }
-        bytes
+/// Encoded as 4 big-endian bytes.
+impl ValueSerializable for U32Variable {
+    fn nb_value_bytes() -> usize {
+        4
     }
 
-    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
-        assert_eq!(bytes.len(), 4);
-        let mut value = 0_u32;
-        for i in 0..4 {
-            value |= (bytes[i] as u32) << ((4 - i - 1) * 8);
-        }
-        value
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(value.to_be_bytes().to_vec())
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
     }
 }
 
@@ -323,8 +355,8 @@ mod tests {
     #[test]
     fn test_u32_evm_value() {
         let val = 0x12345678_u32;
-        let encoded = U32Variable::encode_value::<GoldilocksField>(val);
-        let decoded = U32Variable::decode_value::<GoldilocksField>(&encoded);
+        let encoded = U32Variable::to_bytes::<GoldilocksField>(val).unwrap();
+        let decoded = U32Variable::from_bytes::<GoldilocksField>(&encoded).unwrap();
         assert_eq!(encoded[0], 0x12);
         assert_eq!(encoded[1], 0x34);
         assert_eq!(encoded[2], 0x56);
@@ -84,18 +84,28 @@ macro_rules! make_uint32_n {
                     limbs
                 }
             }
+        }
+
+        /// Encoded as big-endian bytes.
+        impl ValueSerializable for $a {
+            fn nb_value_bytes() -> usize {
+                $c * 4
+            }
 
-            fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+            fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> anyhow::Result<Vec<u8>> {
                 let mut bytes = vec![0u8; $c * 4];
                 <$b as Uint<$c>>::to_big_endian(&value, &mut bytes);
-                bytes
+                Ok(bytes)
             }
 
-            fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
-                <$b as Uint<$c>>::from_big_endian(bytes)
+            fn from_bytes<F: RichField>(bytes: &[u8]) -> anyhow::Result<Self::ValueType<F>> {
+                $crate::frontend::vars::ensure_nb_value_bytes::<Self>(bytes)?;
+                Ok(<$b as Uint<$c>>::from_big_endian(bytes))
             }
         }
 
+        /// The SSZ leaf is the little-endian encoding padded to 32 bytes, i.e. the reverse of
+        /// the big-endian [`ValueSerializable`] encoding.
         impl SSZVariable for $a {
             fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
                 &self,
@@ -330,7 +340,7 @@ macro_rules! make_uint32_n_tests {
             use rand::Rng;
             use $crate::backend::circuit::DefaultParameters;
             use $crate::frontend::uint::Uint;
-            use $crate::frontend::vars::EvmVariable;
+            use $crate::frontend::vars::{EvmVariable, ValueSerializable};
             use $crate::prelude::*;
 
             #[allow(unused_imports)]
@@ -370,10 +380,25 @@ macro_rules! make_uint32_n_tests {
 
                 let limbs = [OsRng.gen::<u32>(); $c];
                 let num = <$b as Uint<$c>>::from_u32_limbs(limbs);
-                let encoded = $a::encode_value::<F>(num);
-                let decoded: $b = $a::decode_value::<F>(&encoded);
+                let encoded = $a::to_bytes::<F>(num).unwrap();
+                let decoded: $b = $a::from_bytes::<F>(&encoded).unwrap();
 
                 assert_eq!(decoded.to_u32_limbs(), num.to_u32_limbs());
+                assert_eq!(encoded.len(), $a::nb_value_bytes());
+                assert!($a::from_bytes::<F>(&encoded[1..]).is_err());
+            }
+
+            #[test]
+            fn test_u32n_value_bytes_big_endian() {
+                type F = GoldilocksField;
+
+                let mut limbs = [0u32; $c];
+                limbs[0] = 0x01020304;
+                let num = <$b as Uint<$c>>::from_u32_limbs(limbs);
+                let encoded = $a::to_bytes::<F>(num).unwrap();
+
+                assert_eq!(encoded[$c * 4 - 4..], [1, 2, 3, 4]);
+                assert!(encoded[..$c * 4 - 4].iter().all(|b| *b == 0));
             }
 
             #[test]
@@ -5,7 +5,7 @@ use plonky2::hash::hash_types::RichField;
 use super::Uint;
 use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
 use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
-use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable};
+use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable, ValueSerializable};
 use crate::prelude::{
     Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
     CircuitVariable, Div, LessThanOrEqual, Mul, One, PlonkParameters, Rem, Sub, Variable, Zero,
@@ -1,6 +1,7 @@
 use core::fmt::Debug;
 use std::ops::{Index, Range};
 
+use anyhow::{ensure, Result};
 use itertools::Itertools;
 use plonky2::field::types::{Field, PrimeField64};
 use plonky2::hash::hash_types::RichField;
@@ -9,7 +10,10 @@ use plonky2::iop::challenger::RecursiveChallenger;
 use plonky2::iop::target::Target;
 use serde::{Deserialize, Serialize};
 
-use super::{ByteVariable, CircuitVariable, ValueStream, Variable, VariableStream};
+use super::{
+    ensure_nb_value_bytes, ByteVariable, CircuitVariable, ValueSerializable, ValueStream, Variable,
+    VariableStream,
+};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::hint::simple::hint::Hint;
@@ -125,6 +129,35 @@ impl<V: CircuitVariable, const N: usize> CircuitVariable for ArrayVariable<V, N>
     }
 }
 
+/// Encoded as the concatenation of the encodings of its elements.
+impl<V: ValueSerializable, const N: usize> ValueSerializable for ArrayVariable<V, N> {
+    fn nb_value_bytes() -> usize {
+        N * V::nb_value_bytes()
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        ensure!(
+            value.len() == N,
+            "expected {} values, got {}",
+            N,
+            value.len()
+        );
+        let bytes = value
+            .into_iter()
+            .map(V::to_bytes)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(bytes.concat())
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        bytes
+            .chunks_exact(V::nb_value_bytes())
+            .map(V::from_bytes)
+            .collect()
+    }
+}
+
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// Given `array` of variables and dynamic `selector`, returns `array[selector]` as a variable.
     pub fn select_array<V: CircuitVariable>(&mut self, array: &[V], selector: Variable) -> V {
@@ -0,0 +1,333 @@
+use anyhow::{ensure, Result};
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+
+use super::{
+    ensure_nb_value_bytes, ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable,
+    BytesVariable, CircuitVariable, SSZVariable, ValueSerializable, Variable,
+};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+
+/// The number of bits in an SSZ chunk.
+const BITS_PER_CHUNK: usize = 256;
+
+/// A variable in the circuit representing an SSZ `Bitvector[N]`.
+#[derive(Debug, Clone)]
+pub struct BitvectorVariable<const N: usize>(pub ArrayVariable<BoolVariable, N>);
+
+/// A variable in the circuit representing an SSZ `Bitlist[MAX]`.
+///
+/// The bits at positions past `len` are constrained to be zero.
+#[derive(Debug, Clone)]
+pub struct BitlistVariable<const MAX: usize> {
+    pub data: ArrayVariable<BoolVariable, MAX>,
+    pub len: U64Variable,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Packs bits into SSZ chunks, where bit `i` is stored in byte `i / 8` at position `i % 8`
+    /// counting from the least significant bit. The chunks are zero padded to `num_chunks`.
+    fn ssz_pack_bits(&mut self, bits: &[BoolVariable], num_chunks: usize) -> Vec<Bytes32Variable> {
+        let false_v = self._false();
+        let mut bits = bits.to_vec();
+        bits.resize(num_chunks * BITS_PER_CHUNK, false_v);
+        let bytes = bits
+            .chunks(8)
+            .map(|byte_bits| {
+                let mut be_bits: [BoolVariable; 8] = byte_bits.try_into().unwrap();
+                be_bits.reverse();
+                ByteVariable(be_bits)
+            })
+            .collect::<Vec<_>>();
+        bytes
+            .chunks(32)
+            .map(|chunk| Bytes32Variable(BytesVariable(chunk.try_into().unwrap())))
+            .collect()
+    }
+
+    /// Merkleizes SSZ chunks, padding them with zero chunks to the next power of two.
+    fn ssz_merkleize_chunks(&mut self, mut chunks: Vec<Bytes32Variable>) -> Bytes32Variable {
+        let zero = self.constant::<Bytes32Variable>(Default::default());
+        chunks.resize(chunks.len().next_power_of_two(), zero);
+        self.ssz_hash_leafs(&chunks)
+    }
+}
+
+impl<const N: usize> CircuitVariable for BitvectorVariable<N> {
+    type ValueType<F: RichField> = Vec<bool>;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self(ArrayVariable::init_unsafe(builder))
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.0.variables()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self(ArrayVariable::from_variables_unsafe(variables))
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.0.assert_is_valid(builder)
+    }
+
+    fn nb_elements() -> usize {
+        ArrayVariable::<BoolVariable, N>::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        assert_eq!(value.len(), N);
+        ArrayVariable::<BoolVariable, N>::elements(value)
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        ArrayVariable::<BoolVariable, N>::from_elements(elements)
+    }
+}
+
+impl<const N: usize> SSZVariable for BitvectorVariable<N> {
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let chunks = builder.ssz_pack_bits(self.0.as_slice(), N.div_ceil(BITS_PER_CHUNK));
+        builder.ssz_merkleize_chunks(chunks)
+    }
+}
+
+/// Encoded as `N` bytes that are each `0` or `1`.
+impl<const N: usize> ValueSerializable for BitvectorVariable<N> {
+    fn nb_value_bytes() -> usize {
+        ArrayVariable::<BoolVariable, N>::nb_value_bytes()
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        ArrayVariable::<BoolVariable, N>::to_bytes::<F>(value)
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ArrayVariable::<BoolVariable, N>::from_bytes::<F>(bytes)
+    }
+}
+
+impl<const MAX: usize> CircuitVariable for BitlistVariable<MAX> {
+    type ValueType<F: RichField> = Vec<bool>;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            data: ArrayVariable::init_unsafe(builder),
+            len: U64Variable::init_unsafe(builder),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        let mut variables = self.data.variables();
+        variables.extend(self.len.variables());
+        variables
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        let (data, len) = variables.split_at(MAX);
+        Self {
+            data: ArrayVariable::from_variables_unsafe(data),
+            len: U64Variable::from_variables_unsafe(len),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.data.assert_is_valid(builder);
+        self.len.assert_is_valid(builder);
+
+        let max = builder.constant::<U64Variable>(MAX as u64);
+        let true_v = builder._true();
+        let false_v = builder._false();
+        let len_in_bounds = builder.lte(self.len, max);
+        builder.assert_is_equal(len_in_bounds, true_v);
+
+        // Since len <= MAX, it fits in a single field element and the bits past it can be found by
+        // comparing it against every index.
+        let len = self.len.to_variable_with_overflow(builder);
+        let mut in_range = true_v;
+        for i in 0..MAX {
+            let index = builder.constant::<Variable>(L::Field::from_canonical_usize(i));
+            let is_len = builder.is_equal(len, index);
+            let is_not_len = builder.not(is_len);
+            in_range = builder.and(in_range, is_not_len);
+
+            let is_past_len = builder.not(in_range);
+            let is_set_past_len = builder.and(self.data[i], is_past_len);
+            builder.assert_is_equal(is_set_past_len, false_v);
+        }
+    }
+
+    fn nb_elements() -> usize {
+        MAX + U64Variable::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        assert!(value.len() <= MAX);
+        let len = value.len() as u64;
+        let mut bits = value;
+        bits.resize(MAX, false);
+        let mut elements = ArrayVariable::<BoolVariable, MAX>::elements(bits);
+        elements.extend(U64Variable::elements(len));
+        elements
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let (data, len) = elements.split_at(MAX);
+        let mut bits = ArrayVariable::<BoolVariable, MAX>::from_elements(data);
+        bits.truncate(U64Variable::from_elements(len) as usize);
+        bits
+    }
+}
+
+/// Encoded as `MAX` bytes that are each `0` or `1`, zero padded past the length of the list,
+/// followed by the length as a `U64Variable`.
+impl<const MAX: usize> ValueSerializable for BitlistVariable<MAX> {
+    fn nb_value_bytes() -> usize {
+        MAX + U64Variable::nb_value_bytes()
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        ensure!(
+            value.len() <= MAX,
+            "expected at most {} bits, got {}",
+            MAX,
+            value.len()
+        );
+        let len = value.len() as u64;
+        let mut bits = value;
+        bits.resize(MAX, false);
+        let mut bytes = ArrayVariable::<BoolVariable, MAX>::to_bytes::<F>(bits)?;
+        bytes.extend(U64Variable::to_bytes::<F>(len)?);
+        Ok(bytes)
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        let (data, len) = bytes.split_at(MAX);
+        let mut bits = ArrayVariable::<BoolVariable, MAX>::from_bytes::<F>(data)?;
+        let len = U64Variable::from_bytes::<F>(len)?;
+        ensure!(len <= MAX as u64, "length {} exceeds {}", len, MAX);
+        ensure!(
+            bits[len as usize..].iter().all(|bit| !bit),
+            "bits past the length are set"
+        );
+        bits.truncate(len as usize);
+        Ok(bits)
+    }
+}
+
+impl<const MAX: usize> SSZVariable for BitlistVariable<MAX> {
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let chunks = builder.ssz_pack_bits(self.data.as_slice(), MAX.div_ceil(BITS_PER_CHUNK));
+        let root = builder.ssz_merkleize_chunks(chunks);
+        let len = builder.ssz_length_leaf(self.len);
+        builder.curta_sha256_pair(root, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+
+    use super::*;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_bitvector_hash_tree_root() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let full = builder.read::<BitvectorVariable<512>>();
+        let sparse = builder.read::<BitvectorVariable<512>>();
+        let full_root = full.hash_tree_root(&mut builder);
+        let sparse_root = sparse.hash_tree_root(&mut builder);
+        builder.write(full_root);
+        builder.write(sparse_root);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BitvectorVariable<512>>(vec![true; 512]);
+        input.write::<BitvectorVariable<512>>((0..512).map(|i| i % 3 == 0).collect());
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x8667e718294e9e0df1d30600ba3eeb201f764aad2dad72748643e4a285e1d1f7")
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x146a85e85e44a166b9d324a4f3d871f9342f78c5555649db3f949952a7530cdc")
+        );
+    }
+
+    #[test]
+    fn test_bitlist_hash_tree_root() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let bits = builder.read::<BitlistVariable<2048>>();
+        let root = bits.hash_tree_root(&mut builder);
+        builder.write(root);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BitlistVariable<2048>>((0..100).map(|i| i % 5 == 0 || i % 7 == 0).collect());
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let root: H256 = output.read::<Bytes32Variable>();
+        assert_eq!(
+            root,
+            bytes32!("0xf062c65ecaef6127c9cb03dd6b3b651e5ba94a3347f0cafd9dff364ec86bf60e")
+        );
+    }
+
+    #[test]
+    fn test_bits_value_bytes() {
+        type F = GoldilocksField;
+
+        let bits = vec![true, false, false, true];
+        let bytes = BitvectorVariable::<4>::to_bytes::<F>(bits.clone()).unwrap();
+        assert_eq!(bytes, [1, 0, 0, 1]);
+        assert_eq!(
+            BitvectorVariable::<4>::from_bytes::<F>(&bytes).unwrap(),
+            bits
+        );
+
+        let bits = vec![false, true, true];
+        let bytes = BitlistVariable::<8>::to_bytes::<F>(bits.clone()).unwrap();
+        assert_eq!(bytes.len(), BitlistVariable::<8>::nb_value_bytes());
+        assert_eq!(BitlistVariable::<8>::from_bytes::<F>(&bytes).unwrap(), bits);
+        let empty = BitlistVariable::<8>::to_bytes::<F>(vec![]).unwrap();
+        assert!(BitlistVariable::<8>::from_bytes::<F>(&empty)
+            .unwrap()
+            .is_empty());
+
+        // A bitlist whose length exceeds its capacity, or with bits set past its length.
+        let mut bytes = vec![0u8; 4];
+        bytes.extend(5u64.to_be_bytes());
+        assert!(BitlistVariable::<4>::from_bytes::<F>(&bytes).is_err());
+        let mut bytes = vec![0, 0, 1, 0];
+        bytes.extend(2u64.to_be_bytes());
+        assert!(BitlistVariable::<4>::from_bytes::<F>(&bytes).is_err());
+    }
+}
@@ -1,10 +1,14 @@
 use std::fmt::Debug;
 
+use anyhow::{bail, Result};
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::BoolTarget;
 use serde::{Deserialize, Serialize};
 
-use super::{CircuitVariable, Variable};
+use super::{
+    ensure_nb_value_bytes, ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable,
+    SSZVariable, ValueSerializable, Variable,
+};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::ops::{BitAnd, BitOr, BitXor, Not};
@@ -65,6 +69,40 @@ impl CircuitVariable for BoolVariable {
     }
 }
 
+/// Encoded as a single `0` or `1` byte.
+impl ValueSerializable for BoolVariable {
+    fn nb_value_bytes() -> usize {
+        1
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(vec![value as u8])
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        match bytes[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => bail!("expected a 0 or 1 byte, got {}", byte),
+        }
+    }
+}
+
+/// The SSZ leaf is the byte `0x01` or `0x00` padded with zeros to 32 bytes.
+impl SSZVariable for BoolVariable {
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let zero = builder.constant::<ByteVariable>(0);
+        let one = builder.constant::<ByteVariable>(1);
+        let mut bytes = [zero; 32];
+        bytes[0] = builder.select(*self, one, zero);
+        Bytes32Variable(BytesVariable(bytes))
+    }
+}
+
 impl From<BoolTarget> for BoolVariable {
     fn from(v: BoolTarget) -> Self {
         // BoolTarget's range is the same as BoolVariable's.
@@ -1,11 +1,14 @@
 use std::fmt::Debug;
 
+use anyhow::Result;
 use array_macro::array;
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::{BoolTarget, Target};
 use serde::{Deserialize, Serialize};
 
-use super::{BoolVariable, CircuitVariable, EvmVariable, Variable};
+use super::{
+    ensure_nb_value_bytes, BoolVariable, CircuitVariable, EvmVariable, ValueSerializable, Variable,
+};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::ops::{BitAnd, BitOr, BitXor, Not, RotateLeft, RotateRight, Shl, Shr, Zero};
@@ -81,14 +84,21 @@ impl EvmVariable for ByteVariable {
         assert_eq!(bytes.len(), 1);
         bytes[0]
     }
+}
 
-    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
-        vec![value]
+/// Encoded as the raw byte.
+impl ValueSerializable for ByteVariable {
+    fn nb_value_bytes() -> usize {
+        1
     }
 
-    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
-        assert_eq!(bytes.len(), 1);
-        bytes[0]
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(vec![value])
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        Ok(bytes[0])
     }
 }
 
@@ -112,16 +122,21 @@ impl ByteVariable {
             .unwrap()
     }
 
+    /// Splits the byte into its high and low nibbles.
+    ///
+    /// The bits of a byte are already constrained to be boolean, so the nibbles are rewired from
+    /// them and need neither a decomposition nor a range check.
     pub fn to_nibbles<L: PlonkParameters<D>, const D: usize>(
         self,
         builder: &mut CircuitBuilder<L, D>,
     ) -> [ByteVariable; 2] {
         let bits = self.as_be_bits();
+        let zero = builder.constant(false);
 
-        let mut left_nibble = array![_ => builder.constant(false); 8];
+        let mut left_nibble = [zero; 8];
         left_nibble[4..].copy_from_slice(&bits[0..4]);
 
-        let mut right_nibble = array![_ => builder.constant(false); 8];
+        let mut right_nibble = [zero; 8];
         right_nibble[4..].copy_from_slice(&bits[4..8]);
 
         [ByteVariable(left_nibble), ByteVariable(right_nibble)]
@@ -132,12 +147,13 @@ impl ByteVariable {
     }
 
     /// Creates a ByteVariable from a Target.
+    ///
+    /// The decomposition into eight bits already bounds the target to `[0, 2^8)`, so no separate
+    /// range check is needed.
     pub fn from_target<L: PlonkParameters<D>, const D: usize>(
         builder: &mut CircuitBuilder<L, D>,
         byte_target: Target,
     ) -> Self {
-        builder.api.range_check(byte_target, 8);
-
         let le_bool_targets: [BoolTarget; 8] = builder
             .api
             .low_bits(byte_target, 8, 8)
@@ -379,6 +395,42 @@ mod tests {
         circuit.data.verify(proof).unwrap();
     }
 
+    #[test]
+    fn test_byte_decomposition_gates() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let byte = builder.init::<ByteVariable>();
+        let value = builder.init_unsafe::<Variable>();
+
+        let num_gates = builder.api.num_gates();
+        byte.to_nibbles(&mut builder);
+        assert_eq!(builder.api.num_gates(), num_gates);
+
+        let num_gates = builder.api.num_gates();
+        builder.api.range_check(value.0, 8);
+        builder.api.low_bits(value.0, 8, 8);
+        let checked_decomposition_gates = builder.api.num_gates() - num_gates;
+
+        let num_gates = builder.api.num_gates();
+        ByteVariable::from_variable(&mut builder, value);
+        let decomposition_gates = builder.api.num_gates() - num_gates;
+        assert!(decomposition_gates < checked_decomposition_gates);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_byte_from_variable_out_of_range() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let value = builder.init_unsafe::<Variable>();
+        let byte = ByteVariable::from_variable(&mut builder, value);
+        builder.write(byte);
+        let circuit = builder.build();
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(value.0, GoldilocksField::from_canonical_u64(256));
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
     #[test]
     fn test_to_nibbles() {
         let mut builder = CircuitBuilder::<L, D>::new();
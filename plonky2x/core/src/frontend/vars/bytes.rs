@@ -1,10 +1,11 @@
 use std::fmt::Debug;
 use std::ops::{Index, Range};
 
+use anyhow::Result;
 use array_macro::array;
 use plonky2::hash::hash_types::RichField;
 
-use super::{CircuitVariable, EvmVariable, Variable};
+use super::{ensure_nb_value_bytes, CircuitVariable, EvmVariable, ValueSerializable, Variable};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::ops::{BitAnd, BitOr, BitXor, Not, RotateLeft, RotateRight, Shl, Shr, Zero};
@@ -118,13 +119,21 @@ impl<const N: usize> EvmVariable for BytesVariable<N> {
         assert_eq!(bytes.len(), N);
         Self(array![i => ByteVariable::decode(builder, &bytes[i..i+1]); N])
     }
+}
+
+/// Encoded as the raw bytes.
+impl<const N: usize> ValueSerializable for BytesVariable<N> {
+    fn nb_value_bytes() -> usize {
+        N
+    }
 
-    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
-        value.to_vec()
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(value.to_vec())
     }
 
-    fn decode_value<F: RichField>(value: &[u8]) -> Self::ValueType<F> {
-        value.try_into().unwrap()
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        Ok(bytes.try_into().unwrap())
     }
 }
 
@@ -287,6 +296,21 @@ impl<L: PlonkParameters<D>, const D: usize, const N: usize> RotateRight<L, D, us
     }
 }
 
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Rotates a byte buffer left by `n` bytes, so that byte `i` of the result is byte
+    /// `(i + n) % buf.len()` of the buffer.
+    ///
+    /// This only rewires the bytes and adds no constraints. To rotate by a number of bits, use
+    /// `rotate_left` on a [`BytesVariable`] instead.
+    pub fn rotate_left_bytes(&mut self, buf: &[ByteVariable], n: usize) -> Vec<ByteVariable> {
+        if buf.is_empty() {
+            return Vec::new();
+        }
+        let n = n % buf.len();
+        buf[n..].iter().chain(buf[..n].iter()).copied().collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{thread_rng, Rng};
@@ -399,4 +423,22 @@ mod tests {
         let proof = circuit.data.prove(pw).unwrap();
         circuit.data.verify(proof).unwrap();
     }
+
+    #[test]
+    fn test_rotate_left_bytes() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let buf = builder.read::<BytesVariable<8>>();
+        let rotated = builder.rotate_left_bytes(&buf.0, 3);
+        builder.write(BytesVariable::<8>(rotated.try_into().unwrap()));
+        let unrotated = builder.rotate_left_bytes(&buf.0, 8);
+        builder.write(BytesVariable::<8>(unrotated.try_into().unwrap()));
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BytesVariable<8>>([1, 2, 3, 4, 5, 6, 7, 8]);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<BytesVariable<8>>(), [4, 5, 6, 7, 8, 1, 2, 3]);
+        assert_eq!(output.read::<BytesVariable<8>>(), [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
 }
@@ -1,10 +1,12 @@
 use std::fmt::Debug;
 
+use anyhow::Result;
 use ethers::types::H256;
 use plonky2::hash::hash_types::RichField;
 
 use super::{
-    ByteVariable, BytesVariable, CircuitVariable, EvmVariable, SSZVariable, U256Variable, Variable,
+    ensure_nb_value_bytes, ByteVariable, BytesVariable, CircuitVariable, EvmVariable, SSZVariable,
+    U256Variable, ValueSerializable, Variable,
 };
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
@@ -90,13 +92,21 @@ impl EvmVariable for Bytes32Variable {
     ) -> Self {
         Self(BytesVariable::decode(builder, bytes))
     }
+}
+
+/// Encoded as the raw 32 bytes.
+impl ValueSerializable for Bytes32Variable {
+    fn nb_value_bytes() -> usize {
+        32
+    }
 
-    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
-        value.as_bytes().to_vec()
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(value.as_bytes().to_vec())
     }
 
-    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
-        H256::from_slice(bytes)
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        Ok(H256::from_slice(bytes))
     }
 }
 
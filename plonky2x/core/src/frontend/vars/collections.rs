@@ -1,8 +1,9 @@
+use anyhow::Result;
 use array_macro::array;
 use log::debug;
 use plonky2::hash::hash_types::RichField;
 
-use super::{CircuitVariable, Variable};
+use super::{ensure_nb_value_bytes, CircuitVariable, ValueSerializable, Variable};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 
@@ -58,6 +59,30 @@ impl<const N: usize, V: CircuitVariable> CircuitVariable for [V; N] {
     }
 }
 
+/// Encoded as the concatenation of the encodings of its elements.
+impl<const N: usize, V: ValueSerializable> ValueSerializable for [V; N] {
+    fn nb_value_bytes() -> usize {
+        N * V::nb_value_bytes()
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        let bytes = value
+            .into_iter()
+            .map(V::to_bytes)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(bytes.concat())
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        let values = bytes
+            .chunks_exact(V::nb_value_bytes())
+            .map(V::from_bytes)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(values.try_into().unwrap())
+    }
+}
+
 impl CircuitVariable for () {
     type ValueType<F: RichField> = ();
 
@@ -644,3 +669,54 @@ impl<
         )
     }
 }
+
+/// Implements `ValueSerializable` for a tuple, encoded as the concatenation of the encodings of
+/// its elements.
+macro_rules! impl_value_serializable_tuple {
+    ($($v:ident $x:ident),+) => {
+        impl<$($v: ValueSerializable),+> ValueSerializable for ($($v,)+) {
+            fn nb_value_bytes() -> usize {
+                0 $(+ $v::nb_value_bytes())+
+            }
+
+            fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+                let ($($x,)+) = value;
+                let mut bytes = Vec::with_capacity(Self::nb_value_bytes());
+                $(bytes.extend($v::to_bytes($x)?);)+
+                Ok(bytes)
+            }
+
+            #[allow(unused_assignments)]
+            fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+                ensure_nb_value_bytes::<Self>(bytes)?;
+                let mut offset = 0;
+                $(
+                    let $x = $v::from_bytes(&bytes[offset..offset + $v::nb_value_bytes()])?;
+                    offset += $v::nb_value_bytes();
+                )+
+                Ok(($($x,)+))
+            }
+        }
+    };
+}
+
+/// Encoded as zero bytes.
+impl ValueSerializable for () {
+    fn nb_value_bytes() -> usize {
+        0
+    }
+
+    fn to_bytes<F: RichField>(_: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(vec![])
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)
+    }
+}
+
+impl_value_serializable_tuple!(V1 v1, V2 v2);
+impl_value_serializable_tuple!(V1 v1, V2 v2, V3 v3);
+impl_value_serializable_tuple!(V1 v1, V2 v2, V3 v3, V4 v4);
+impl_value_serializable_tuple!(V1 v1, V2 v2, V3 v3, V4 v4, V5 v5);
+impl_value_serializable_tuple!(V1 v1, V2 v2, V3 v3, V4 v4, V5 v5, V6 v6, V7 v7, V8 v8);
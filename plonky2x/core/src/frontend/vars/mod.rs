@@ -1,4 +1,6 @@
 mod array;
+#[cfg(feature = "ssz")]
+mod bits;
 mod boolean;
 mod byte;
 mod bytes;
@@ -9,7 +11,10 @@ mod stream;
 mod variable;
 use std::fmt::Debug;
 
+use anyhow::{ensure, Result};
 pub use array::*;
+#[cfg(feature = "ssz")]
+pub use bits::*;
 pub use boolean::*;
 pub use byte::*;
 pub use bytes::*;
@@ -122,7 +127,42 @@ pub trait CircuitVariable: Debug + Clone + Sized + Sync + Send + 'static {
     fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F>;
 }
 
-pub trait EvmVariable: CircuitVariable {
+/// A canonical byte encoding for the values of a circuit variable.
+///
+/// Every layer that turns values into bytes (evm io, JSON io, ...) goes through this trait so that
+/// they agree on the encoding of a value. Unsigned integers are encoded in big-endian, byte types
+/// as their raw bytes, booleans as a single `0` or `1` byte and composite types (arrays, tuples and
+/// derived structs) as the concatenation of the encodings of their fields.
+///
+/// The encoding has a fixed length of `nb_value_bytes()` for every value of a type.
+pub trait ValueSerializable: CircuitVariable {
+    /// The number of bytes in the encoding of a value.
+    fn nb_value_bytes() -> usize;
+
+    /// Serializes a value to its canonical bytes, failing if it does not hold a valid value of the
+    /// type.
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>>;
+
+    /// Deserializes a value from its canonical bytes, failing if they are not a valid encoding.
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>>;
+}
+
+/// Checks that `bytes` has the length of an encoded value of `V`.
+pub(crate) fn ensure_nb_value_bytes<V: ValueSerializable>(bytes: &[u8]) -> Result<()> {
+    ensure!(
+        bytes.len() == V::nb_value_bytes(),
+        "expected {} bytes, got {}",
+        V::nb_value_bytes(),
+        bytes.len()
+    );
+    Ok(())
+}
+
+/// A variable with an in-circuit byte encoding.
+///
+/// The encoding should match `abi.encodePacked(...)` and the canonical value encoding given by
+/// `ValueSerializable`.
+pub trait EvmVariable: ValueSerializable {
     /// The number of bytes it takes to represent this variable.
     fn nb_bytes<L: PlonkParameters<D>, const D: usize>() -> usize {
         let mut builder = CircuitBuilder::<L, D>::new();
@@ -149,14 +189,6 @@ pub trait EvmVariable: CircuitVariable {
         bytes: &[ByteVariable],
     ) -> Self;
 
-    /// Serializes a value to bytes. This implementation should match the implementation of
-    /// `abi.encodePacked(...)`.
-    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8>;
-
-    /// Deserializes a value from bytes. This implementation should match the implementation of
-    /// `abi.decodePacked(...)`.
-    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F>;
-
     /// Serializes the variable to little endian bits.
     fn to_le_bits<L: PlonkParameters<D>, const D: usize>(
         &self,
@@ -186,6 +218,33 @@ pub trait SSZVariable: CircuitVariable {
     ) -> Bytes32Variable;
 }
 
+/// An SSZ container, whose root merkleizes the roots of its fields as one chunk each.
+///
+/// The `hash_tree_root` of a container is [`ssz_merkleize`](CircuitBuilder::ssz_merkleize) over
+/// its `field_roots`. `#[derive(Ssz)]` implements both this trait and [`SSZVariable`] that way,
+/// taking the fields in declaration order.
+pub trait Ssz: SSZVariable {
+    /// The number of chunks the container is merkleized from, i.e. its number of fields.
+    fn chunk_count() -> usize;
+
+    /// The gindex of a field relative to the container root.
+    fn field_gindex(field: usize) -> u64 {
+        assert!(
+            field < Self::chunk_count(),
+            "field {} is out of range for a container of {} fields",
+            field,
+            Self::chunk_count()
+        );
+        (Self::chunk_count().next_power_of_two() + field) as u64
+    }
+
+    /// The roots of the fields of the container, in order.
+    fn field_roots<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<Bytes32Variable>;
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -265,4 +324,172 @@ mod tests {
             y: GoldilocksField::ZERO,
         });
     }
+
+    fn assert_roundtrip<V: ValueSerializable>(value: V::ValueType<GoldilocksField>)
+    where
+        V::ValueType<GoldilocksField>: PartialEq + core::fmt::Debug,
+    {
+        let bytes = V::to_bytes::<GoldilocksField>(value.clone()).unwrap();
+        assert_eq!(bytes.len(), V::nb_value_bytes());
+        assert_eq!(V::from_bytes::<GoldilocksField>(&bytes).unwrap(), value);
+        assert!(V::from_bytes::<GoldilocksField>(&[bytes, vec![0]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_value_serializable_roundtrip() {
+        use ethers::types::{H160, H256, U256};
+
+        use crate::frontend::eth::vars::{AddressVariable, BLSPubkeyVariable};
+
+        type F = GoldilocksField;
+
+        assert_roundtrip::<Variable>(F::from_canonical_u64(0x0123456789abcdef));
+        assert_roundtrip::<Variable>(F::NEG_ONE);
+        assert_roundtrip::<BoolVariable>(true);
+        assert_roundtrip::<BoolVariable>(false);
+        assert_roundtrip::<ByteVariable>(0xa5);
+        assert_roundtrip::<BytesVariable<5>>([1, 2, 3, 4, 5]);
+        assert_roundtrip::<Bytes32Variable>(H256::repeat_byte(0x42));
+        assert_roundtrip::<U32Variable>(0xdeadbeef);
+        assert_roundtrip::<U64Variable>(u64::MAX);
+        assert_roundtrip::<U256Variable>(U256::MAX - 1);
+        assert_roundtrip::<ArrayVariable<U32Variable, 3>>(vec![1, 2, 3]);
+        assert_roundtrip::<[ByteVariable; 2]>([7, 8]);
+        assert_roundtrip::<()>(());
+        assert_roundtrip::<(BoolVariable, U64Variable)>((true, 12));
+        assert_roundtrip::<AddressVariable>(H160::repeat_byte(0x11));
+        assert_roundtrip::<BLSPubkeyVariable>([0x22; 48]);
+    }
+
+    #[test]
+    fn test_value_serializable_big_endian() {
+        type F = GoldilocksField;
+
+        assert_eq!(
+            Variable::to_bytes::<F>(F::from_canonical_u64(0x0102)).unwrap(),
+            [0, 0, 0, 0, 0, 0, 1, 2]
+        );
+        assert_eq!(
+            U32Variable::to_bytes::<F>(0x01020304).unwrap(),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(
+            U64Variable::to_bytes::<F>(0x0102).unwrap(),
+            [0, 0, 0, 0, 0, 0, 1, 2]
+        );
+    }
+
+    /// The canonical bytes of a value are the bytes the circuit encodes it to.
+    #[test]
+    fn test_value_serializable_matches_encode() {
+        use ethers::types::H256;
+
+        use crate::frontend::vars::EvmVariable;
+
+        type F = GoldilocksField;
+
+        let mut builder = CircuitBuilder::<DefaultParameters, 2>::new();
+        let a = builder.read::<Bytes32Variable>();
+        let b = builder.read::<U64Variable>();
+        let a_bytes = a.encode(&mut builder);
+        let b_bytes = b.encode(&mut builder);
+        builder.write(BytesVariable::<32>(a_bytes.try_into().unwrap()));
+        builder.write(BytesVariable::<8>(b_bytes.try_into().unwrap()));
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        let a_value = H256::from_slice(&(0..32).collect::<Vec<u8>>());
+        let b_value = 0x0102030405060708u64;
+        input.write::<Bytes32Variable>(a_value);
+        input.write::<U64Variable>(b_value);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let a_expected = Bytes32Variable::to_bytes::<F>(a_value).unwrap();
+        assert_eq!(a_expected, (0..32).collect::<Vec<u8>>());
+        assert_eq!(output.read::<BytesVariable<32>>().to_vec(), a_expected);
+        assert_eq!(
+            output.read::<BytesVariable<8>>().to_vec(),
+            U64Variable::to_bytes::<F>(b_value).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_value_serializable_rejects_non_canonical() {
+        type F = GoldilocksField;
+
+        // A u64 at or above the field order is not a canonical field element.
+        assert!(Variable::from_bytes::<F>(&[0xff; 8]).is_err());
+        assert!(BoolVariable::from_bytes::<F>(&[2]).is_err());
+        assert!(U64Variable::from_bytes::<F>(&[0; 7]).is_err());
+    }
+
+    #[test]
+    fn test_value_serializable_derive_struct() {
+        #[derive(Debug, Clone, CircuitVariable)]
+        #[value_name(MyPoint)]
+        #[value_derive(PartialEq, Eq)]
+        struct Point {
+            x: ArrayVariable<U32Variable, 2>,
+            y: BoolVariable,
+            z: ByteVariable,
+        }
+
+        type F = GoldilocksField;
+
+        let value = MyPoint {
+            x: vec![0x01020304, 5],
+            y: true,
+            z: 9,
+        };
+        let bytes = Point::to_bytes::<F>(value.clone()).unwrap();
+        assert_eq!(bytes, [1, 2, 3, 4, 0, 0, 0, 5, 1, 9]);
+        assert_eq!(Point::nb_value_bytes(), bytes.len());
+        assert_eq!(Point::from_bytes::<F>(&bytes).unwrap(), value);
+        assert!(Point::from_bytes::<F>(&bytes[1..]).is_err());
+    }
+
+    /// The in-circuit SSZ leaf encodings of integers are little-endian, which is the canonical
+    /// value encoding reversed and zero-padded on the right.
+    #[test]
+    #[cfg(feature = "ssz")]
+    fn test_ssz_leaf_diverges_from_value_bytes() {
+        use ethers::types::{H256, U256};
+
+        use crate::frontend::vars::SSZVariable;
+
+        type F = GoldilocksField;
+
+        let mut builder = CircuitBuilder::<DefaultParameters, 2>::new();
+        let a = builder.read::<U64Variable>();
+        let b = builder.read::<U256Variable>();
+        let a_leaf = a.hash_tree_root(&mut builder);
+        let b_leaf = b.hash_tree_root(&mut builder);
+        let length_leaf = builder.ssz_length_leaf(a);
+        builder.write(a_leaf);
+        builder.write(b_leaf);
+        builder.write(length_leaf);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        let a_value = 0x0102030405060708u64;
+        let b_value = U256::from_big_endian(&(1..=32).collect::<Vec<u8>>());
+        input.write::<U64Variable>(a_value);
+        input.write::<U256Variable>(b_value);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let leaf = |mut bytes: Vec<u8>| {
+            bytes.reverse();
+            bytes.resize(32, 0);
+            H256::from_slice(&bytes)
+        };
+        let a_expected = leaf(U64Variable::to_bytes::<F>(a_value).unwrap());
+        assert_eq!(output.read::<Bytes32Variable>(), a_expected);
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            leaf(U256Variable::to_bytes::<F>(b_value).unwrap())
+        );
+        assert_eq!(output.read::<Bytes32Variable>(), a_expected);
+    }
 }
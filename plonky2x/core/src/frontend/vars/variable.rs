@@ -1,10 +1,11 @@
 use std::fmt::Debug;
 
+use anyhow::{ensure, Result};
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::Target;
 use serde::{Deserialize, Serialize};
 
-use super::CircuitVariable;
+use super::{ensure_nb_value_bytes, CircuitVariable, ValueSerializable};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::ops::{Add, Div, Mul, Neg, One, Sub, Zero};
@@ -64,6 +65,28 @@ impl CircuitVariable for Variable {
     }
 }
 
+/// Encoded as the big-endian bytes of the canonical `u64` representation of the field element.
+impl ValueSerializable for Variable {
+    fn nb_value_bytes() -> usize {
+        8
+    }
+
+    fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> Result<Vec<u8>> {
+        Ok(value.to_canonical_u64().to_be_bytes().to_vec())
+    }
+
+    fn from_bytes<F: RichField>(bytes: &[u8]) -> Result<Self::ValueType<F>> {
+        ensure_nb_value_bytes::<Self>(bytes)?;
+        let value = u64::from_be_bytes(bytes.try_into().unwrap());
+        ensure!(
+            value < F::ORDER,
+            "{} is not a canonical field element",
+            value
+        );
+        Ok(F::from_canonical_u64(value))
+    }
+}
+
 impl From<Target> for Variable {
     fn from(target: Target) -> Self {
         Self(target)
@@ -7,6 +7,7 @@
 
 extern crate alloc;
 extern crate clap;
+extern crate self as plonky2x;
 
 pub mod backend;
 pub mod frontend;
@@ -21,7 +22,7 @@ pub mod prelude {
     pub use plonky2::iop::target::Target;
     pub use plonky2::iop::witness::{PartialWitness, Witness, WitnessWrite};
     pub use plonky2::plonk::config::PoseidonGoldilocksConfig;
-    pub use plonky2x_derive::CircuitVariable;
+    pub use plonky2x_derive::{CircuitVariable, Ssz};
     pub use starkyx::math::prelude::cubic::element::CubicElement;
 
     pub use crate::backend::circuit::config::{DefaultParameters, PlonkParameters};
@@ -33,7 +34,8 @@ pub mod prelude {
     pub use crate::frontend::uint::uint64::U64Variable;
     pub use crate::frontend::vars::{
         ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable,
-        OutputVariableStream, U32Variable, ValueStream, Variable, VariableStream,
+        OutputVariableStream, SSZVariable, Ssz, U32Variable, ValueSerializable, ValueStream,
+        Variable, VariableStream,
     };
     pub use crate::utils::{address, bytes, bytes32, hex};
 }
@@ -1,15 +1,21 @@
+#[cfg(test)]
+pub(crate) mod mock;
+pub mod reference;
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
 use core::time::Duration;
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use ethers::types::{H256, U256};
 use itertools::Itertools;
 use log::{debug, info};
 use num::BigInt;
 use reqwest::blocking::Client;
-use serde::Deserialize;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use serde_with::serde_as;
 
+use crate::utils::bytes32;
 use crate::utils::hash::sha256;
 use crate::utils::reqwest::ReqwestClient;
 use crate::utils::serde::deserialize_bigint;
@@ -21,6 +27,125 @@ pub struct BeaconClient {
     client: ReqwestClient,
 }
 
+/// Configures the HTTP client of a [`BeaconClient`].
+///
+/// Requests that fail with a server or connection error are retried with exponential backoff.
+/// Unless a timeout or a number of retries is set, the proof server routes keep their defaults:
+/// the validators root, validator by pubkey, balance witness, execution payload and slot routes
+/// time out after 60 to 300 seconds and are sent once, and every other route times out after 300
+/// seconds, or 900 when asynchronous, and is retried twice.
+#[derive(Debug, Clone)]
+pub struct BeaconClientBuilder {
+    rpc_url: String,
+    timeout: Option<Duration>,
+    max_retries: Option<u32>,
+    initial_retry_delay: Duration,
+    auth_header: Option<String>,
+}
+
+/// Identifies a beacon block by one of the state ids accepted by the consensus API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BeaconStateId {
+    Head,
+    Finalized,
+    Justified,
+    Slot(u64),
+}
+
+impl Display for BeaconStateId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            BeaconStateId::Head => write!(f, "head"),
+            BeaconStateId::Finalized => write!(f, "finalized"),
+            BeaconStateId::Justified => write!(f, "justified"),
+            BeaconStateId::Slot(slot) => write!(f, "{}", slot),
+        }
+    }
+}
+
+/// The fork of the beacon states that the gadgets prove against, which determines the shape of the
+/// state tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BeaconFork {
+    /// Up to Deneb, the state has at most 32 fields and its tree has depth 5.
+    #[default]
+    Deneb,
+    /// From Electra on, the state has more than 32 fields and its tree has depth 6.
+    Electra,
+}
+
+impl BeaconFork {
+    /// The depth of the tree of the state fields.
+    pub const fn state_depth(&self) -> usize {
+        match self {
+            BeaconFork::Deneb => 5,
+            BeaconFork::Electra => 6,
+        }
+    }
+}
+
+/// The SLOTS_PER_HISTORICAL_ROOT of the mainnet preset.
+pub const SLOTS_PER_HISTORICAL_ROOT: usize = 8192;
+
+/// The EPOCHS_PER_HISTORICAL_VECTOR of the mainnet preset.
+pub const EPOCHS_PER_HISTORICAL_VECTOR: usize = 65536;
+
+/// The chain parameters that the beacon gadgets read their slot, epoch and historical vector math
+/// from. Defaults to mainnet.
+///
+/// The historical vector lengths also size the `block_roots` and `randao_mixes` proofs, so a
+/// preset such as minimal, where both are 64, gets proofs of the matching depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconConfig {
+    pub slots_per_epoch: u64,
+    pub slots_per_historical_root: u64,
+    pub epochs_per_historical_vector: u64,
+    pub seconds_per_slot: u64,
+    pub genesis_time: u64,
+    pub capella_fork_epoch: u64,
+    #[serde(default)]
+    pub fork: BeaconFork,
+}
+
+impl BeaconConfig {
+    pub const fn mainnet() -> Self {
+        Self {
+            slots_per_epoch: 32,
+            slots_per_historical_root: SLOTS_PER_HISTORICAL_ROOT as u64,
+            epochs_per_historical_vector: EPOCHS_PER_HISTORICAL_VECTOR as u64,
+            seconds_per_slot: 12,
+            genesis_time: 1606824023,
+            capella_fork_epoch: 194048,
+            fork: BeaconFork::Deneb,
+        }
+    }
+
+    /// The depth of the tree of a `block_roots` vector, which is padded to a power of two.
+    pub fn block_roots_depth(&self) -> usize {
+        self.slots_per_historical_root
+            .next_power_of_two()
+            .trailing_zeros() as usize
+    }
+
+    /// The depth of the tree of the `randao_mixes` vector, which is padded to a power of two.
+    pub fn randao_mixes_depth(&self) -> usize {
+        self.epochs_per_historical_vector
+            .next_power_of_two()
+            .trailing_zeros() as usize
+    }
+
+    /// The first slot from which on the state accumulates historical summaries.
+    pub fn capella_fork_slot(&self) -> u64 {
+        self.capella_fork_epoch * self.slots_per_epoch
+    }
+}
+
+impl Default for BeaconConfig {
+    fn default() -> Self {
+        Self::mainnet()
+    }
+}
+
 /// The data format returned by official Eth Beacon Node APIs.
 #[derive(Debug, Deserialize)]
 struct BeaconData<T> {
@@ -31,6 +156,13 @@ struct BeaconData<T> {
     pub data: T,
 }
 
+/// The data format of the official endpoints that return no finality information, such as the
+/// genesis and light client ones.
+#[derive(Debug, Deserialize)]
+struct BeaconVersionedData<T> {
+    pub data: T,
+}
+
 /// All custom endpoints return a response with this format.
 #[derive(Debug, Deserialize)]
 struct CustomResponse<T> {
@@ -63,10 +195,25 @@ pub struct BeaconHeader {
 
 /// The beacon validator struct according to the consensus spec.
 /// Reference: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#validator
+///
+/// Validators parsed from RPC responses are checked with [`BeaconValidator::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "ValidatorApiResponse")]
+pub struct BeaconValidator {
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    pub effective_balance: u64,
+    pub slashed: bool,
+    pub activation_eligibility_epoch: String,
+    pub activation_epoch: String,
+    pub exit_epoch: String,
+    pub withdrawable_epoch: String,
+}
+
+/// The validator struct as returned by the custom endpoints, before it is validated.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
-pub struct BeaconValidator {
+pub struct ValidatorApiResponse {
     pub pubkey: String,
     pub withdrawal_credentials: String,
     pub effective_balance: u64,
@@ -77,7 +224,167 @@ pub struct BeaconValidator {
     pub withdrawable_epoch: String,
 }
 
+/// A field of a [`BeaconValidator`] that does not hold a valid value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The field is not a `0x`-prefixed hex string.
+    InvalidHex { field: &'static str, value: String },
+    /// The field does not decode to the expected number of bytes.
+    InvalidLength {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// The field is not a decimal integer that fits in a `u64`.
+    InvalidNumber { field: &'static str, value: String },
+}
+
+impl ValidationError {
+    /// The name of the invalid field.
+    pub fn field(&self) -> &'static str {
+        match self {
+            ValidationError::InvalidHex { field, .. }
+            | ValidationError::InvalidLength { field, .. }
+            | ValidationError::InvalidNumber { field, .. } => field,
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ValidationError::InvalidHex { field, value } => {
+                write!(
+                    f,
+                    "validator {} is not a 0x-prefixed hex string: {}",
+                    field, value
+                )
+            }
+            ValidationError::InvalidLength {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "validator {} has {} bytes, expected {}",
+                field, actual, expected
+            ),
+            ValidationError::InvalidNumber { field, value } => {
+                write!(f, "validator {} is not a u64: {}", field, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Decodes a `0x`-prefixed hex string of exactly `N` bytes.
+fn decode_hex_field<const N: usize>(
+    field: &'static str,
+    value: &str,
+) -> core::result::Result<[u8; N], ValidationError> {
+    let invalid_hex = || ValidationError::InvalidHex {
+        field,
+        value: value.to_string(),
+    };
+    let bytes = value
+        .strip_prefix("0x")
+        .and_then(|stripped| hex::decode(stripped).ok())
+        .ok_or_else(invalid_hex)?;
+    let actual = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| ValidationError::InvalidLength {
+            field,
+            expected: N,
+            actual,
+        })
+}
+
+/// Parses a decimal string into a `u64`, failing if it is out of range.
+fn parse_u64_field(field: &'static str, value: &str) -> core::result::Result<u64, ValidationError> {
+    value
+        .parse::<u64>()
+        .map_err(|_| ValidationError::InvalidNumber {
+            field,
+            value: value.to_string(),
+        })
+}
+
+impl TryFrom<ValidatorApiResponse> for BeaconValidator {
+    type Error = ValidationError;
+
+    fn try_from(response: ValidatorApiResponse) -> core::result::Result<Self, Self::Error> {
+        BeaconValidator::new(
+            response.pubkey,
+            response.withdrawal_credentials,
+            response.effective_balance,
+            response.slashed,
+            response.activation_eligibility_epoch,
+            response.activation_epoch,
+            response.exit_epoch,
+            response.withdrawable_epoch,
+        )
+    }
+}
+
 impl BeaconValidator {
+    /// Creates a validator, checking that the pubkey and withdrawal credentials are hex strings of
+    /// 48 and 32 bytes and that the epochs fit in a `u64`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pubkey: String,
+        withdrawal_credentials: String,
+        effective_balance: u64,
+        slashed: bool,
+        activation_eligibility_epoch: String,
+        activation_epoch: String,
+        exit_epoch: String,
+        withdrawable_epoch: String,
+    ) -> core::result::Result<Self, ValidationError> {
+        let validator = Self {
+            pubkey,
+            withdrawal_credentials,
+            effective_balance,
+            slashed,
+            activation_eligibility_epoch,
+            activation_epoch,
+            exit_epoch,
+            withdrawable_epoch,
+        };
+        validator.validate()?;
+        Ok(validator)
+    }
+
+    /// Checks the fields of a validator that was constructed directly.
+    pub fn validate(&self) -> core::result::Result<(), ValidationError> {
+        self.pubkey_bytes()?;
+        self.withdrawal_credentials_bytes()?;
+        self.epochs()?;
+        Ok(())
+    }
+
+    pub fn pubkey_bytes(&self) -> core::result::Result<[u8; 48], ValidationError> {
+        decode_hex_field("pubkey", &self.pubkey)
+    }
+
+    pub fn withdrawal_credentials_bytes(&self) -> core::result::Result<[u8; 32], ValidationError> {
+        decode_hex_field("withdrawal_credentials", &self.withdrawal_credentials)
+    }
+
+    /// The activation eligibility, activation, exit and withdrawable epochs.
+    pub fn epochs(&self) -> core::result::Result<[u64; 4], ValidationError> {
+        Ok([
+            parse_u64_field(
+                "activation_eligibility_epoch",
+                &self.activation_eligibility_epoch,
+            )?,
+            parse_u64_field("activation_epoch", &self.activation_epoch)?,
+            parse_u64_field("exit_epoch", &self.exit_epoch)?,
+            parse_u64_field("withdrawable_epoch", &self.withdrawable_epoch)?,
+        ])
+    }
+
     pub fn pubkey_hash(&self) -> H256 {
         let pubkey_bytes = hex::decode(&self.pubkey.as_str()[2..]).unwrap();
         let mut pubkey_p1 = [0u8; 32];
@@ -186,6 +493,14 @@ pub struct GetBeaconValidatorsRoot {
     pub proof: Vec<String>,
 }
 
+/// The two children of the validators root: the merkle root of the validators and the length
+/// of the list that is mixed into it.
+#[derive(Debug, Clone)]
+pub struct BeaconValidatorsListNode {
+    pub merkle_root: H256,
+    pub length: u64,
+}
+
 /// The result returned from `/api/beacon/proof/validator/[beacon_id]/[validator_idx]`.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -307,6 +622,249 @@ pub struct GetBeaconExecutionPayload {
     pub proof: Vec<String>,
 }
 
+/// An execution payload header, in the format of the consensus api.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BeaconExecutionPayloadHeader {
+    pub parent_hash: String,
+    pub fee_recipient: String,
+    pub state_root: String,
+    pub receipts_root: String,
+    pub logs_bloom: String,
+    pub prev_randao: String,
+    pub block_number: String,
+    pub gas_limit: String,
+    pub gas_used: String,
+    pub timestamp: String,
+    pub extra_data: String,
+    pub base_fee_per_gas: String,
+    pub block_hash: String,
+    pub transactions_root: String,
+    pub withdrawals_root: String,
+    pub blob_gas_used: String,
+    pub excess_blob_gas: String,
+}
+
+impl BeaconExecutionPayloadHeader {
+    /// The hash tree root of `logs_bloom`, a `ByteVector[256]`.
+    pub fn logs_bloom_root(&self) -> Result<H256> {
+        let logs_bloom = hex::decode(self.logs_bloom.trim_start_matches("0x"))?;
+        ensure!(
+            logs_bloom.len() == 256,
+            "logs_bloom has {} bytes instead of 256",
+            logs_bloom.len()
+        );
+        Ok(H256::from(ssz_merkleize_chunks(
+            ssz_pack_bytes(&logs_bloom),
+            3,
+        )))
+    }
+
+    /// The hash tree root of `extra_data`, a `ByteList[32]`.
+    pub fn extra_data_root(&self) -> Result<H256> {
+        let extra_data = hex::decode(self.extra_data.trim_start_matches("0x"))?;
+        ensure!(
+            extra_data.len() <= 32,
+            "extra_data has {} bytes, more than 32",
+            extra_data.len()
+        );
+        let root = ssz_merkleize_chunks(ssz_pack_bytes(&extra_data), 0);
+        Ok(H256::from(ssz_mix_in_length(root, extra_data.len())))
+    }
+
+    /// The SSZ leaves of the fields of the header, in the order of the Deneb spec.
+    fn ssz_field_roots(&self) -> Result<Vec<[u8; 32]>> {
+        let mut base_fee_per_gas = [0u8; 32];
+        U256::from_dec_str(&self.base_fee_per_gas)?.to_little_endian(&mut base_fee_per_gas);
+        Ok(vec![
+            bytes32!(self.parent_hash).0,
+            ssz_address_chunk(&self.fee_recipient)?,
+            bytes32!(self.state_root).0,
+            bytes32!(self.receipts_root).0,
+            self.logs_bloom_root()?.0,
+            bytes32!(self.prev_randao).0,
+            ssz_uint64_chunk(self.block_number.parse()?),
+            ssz_uint64_chunk(self.gas_limit.parse()?),
+            ssz_uint64_chunk(self.gas_used.parse()?),
+            ssz_uint64_chunk(self.timestamp.parse()?),
+            self.extra_data_root()?.0,
+            base_fee_per_gas,
+            bytes32!(self.block_hash).0,
+            bytes32!(self.transactions_root).0,
+            bytes32!(self.withdrawals_root).0,
+            ssz_uint64_chunk(self.blob_gas_used.parse()?),
+            ssz_uint64_chunk(self.excess_blob_gas.parse()?),
+        ])
+    }
+
+    pub fn ssz_hash_tree_root(&self) -> Result<H256> {
+        Ok(H256::from(ssz_merkleize_chunks(
+            self.ssz_field_roots()?,
+            EXECUTION_PAYLOAD_HEADER_DEPTH,
+        )))
+    }
+}
+
+/// A withdrawal of an execution payload, in the format of the consensus api.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BeaconPayloadWithdrawal {
+    pub index: String,
+    pub validator_index: String,
+    pub address: String,
+    pub amount: String,
+}
+
+/// The list fields of an execution payload, in the format of the consensus api, which the header
+/// replaces with their hash tree roots.
+#[derive(Debug, Deserialize)]
+struct BeaconExecutionPayloadLists {
+    transactions: Vec<String>,
+    withdrawals: Vec<BeaconPayloadWithdrawal>,
+}
+
+/// The execution payload header of a block with the proof from `blockRoot -> body.executionPayload`.
+#[derive(Debug, Clone)]
+pub struct GetBeaconExecutionPayloadHeader {
+    pub header: BeaconExecutionPayloadHeader,
+    pub proof: Vec<H256>,
+}
+
+/// The depth of the execution payload header, whose 17 fields are padded to 32 leaves.
+const EXECUTION_PAYLOAD_HEADER_DEPTH: usize = 5;
+
+/// The index of `block_number` among the fields of the execution payload header.
+const EXECUTION_PAYLOAD_BLOCK_NUMBER_INDEX: u64 = 6;
+
+/// The depth of the `transactions` of an execution payload, MAX_TRANSACTIONS_PER_PAYLOAD = 2^20.
+const TRANSACTIONS_DEPTH: usize = 20;
+
+/// The depth of the chunks of a transaction, MAX_BYTES_PER_TRANSACTION / 32 = 2^25.
+const TRANSACTION_DEPTH: usize = 25;
+
+/// The depth of the `withdrawals` of an execution payload, MAX_WITHDRAWALS_PER_PAYLOAD = 16.
+const WITHDRAWALS_DEPTH: usize = 4;
+
+/// The SSZ leaf of a `uint64`.
+fn ssz_uint64_chunk(value: u64) -> [u8; 32] {
+    let mut chunk = [0u8; 32];
+    chunk[..8].copy_from_slice(&value.to_le_bytes());
+    chunk
+}
+
+/// The SSZ leaf of an `ExecutionAddress`, the address bytes right-padded to 32 bytes.
+fn ssz_address_chunk(address: &str) -> Result<[u8; 32]> {
+    let address = hex::decode(address.trim_start_matches("0x"))?;
+    ensure!(
+        address.len() == 20,
+        "address has {} bytes instead of 20",
+        address.len()
+    );
+    let mut chunk = [0u8; 32];
+    chunk[..20].copy_from_slice(&address);
+    Ok(chunk)
+}
+
+/// Packs `bytes` into 32 byte chunks, right-padding the last one.
+fn ssz_pack_bytes(bytes: &[u8]) -> Vec<[u8; 32]> {
+    bytes
+        .chunks(32)
+        .map(|bytes| {
+            let mut chunk = [0u8; 32];
+            chunk[..bytes.len()].copy_from_slice(bytes);
+            chunk
+        })
+        .collect()
+}
+
+/// Merkleizes `chunks` into a tree of the given depth, padding them with zero chunks.
+fn ssz_merkleize_chunks(mut chunks: Vec<[u8; 32]>, depth: usize) -> [u8; 32] {
+    let mut zero = [0u8; 32];
+    for _ in 0..depth {
+        if chunks.len() % 2 == 1 {
+            chunks.push(zero);
+        }
+        chunks = chunks
+            .chunks(2)
+            .map(|pair| sha256(&[pair[0], pair[1]].concat()))
+            .collect();
+        zero = sha256(&[zero, zero].concat());
+    }
+    chunks.first().copied().unwrap_or(zero)
+}
+
+/// Mixes the length of a list into the root of its elements.
+fn ssz_mix_in_length(root: [u8; 32], length: usize) -> [u8; 32] {
+    sha256(&[root, ssz_uint64_chunk(length as u64)].concat())
+}
+
+/// Restores the root of the subtree that `branch` opens at `index` from `leaf`.
+fn ssz_restore_root(leaf: [u8; 32], branch: &[H256], index: u64) -> H256 {
+    let root = branch
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (i, sibling)| match (index >> i) & 1 {
+            1 => sha256(&[sibling.0, node].concat()),
+            _ => sha256(&[node, sibling.0].concat()),
+        });
+    H256::from(root)
+}
+
+/// The hash tree root of the `transactions` of an execution payload, a
+/// `List[ByteList[MAX_BYTES_PER_TRANSACTION], MAX_TRANSACTIONS_PER_PAYLOAD]`.
+fn ssz_transactions_root(transactions: &[String]) -> Result<H256> {
+    let roots = transactions
+        .iter()
+        .map(|transaction| {
+            let bytes = hex::decode(transaction.trim_start_matches("0x"))?;
+            let root = ssz_merkleize_chunks(ssz_pack_bytes(&bytes), TRANSACTION_DEPTH);
+            Ok(ssz_mix_in_length(root, bytes.len()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let root = ssz_merkleize_chunks(roots, TRANSACTIONS_DEPTH);
+    Ok(H256::from(ssz_mix_in_length(root, transactions.len())))
+}
+
+/// The hash tree root of the `withdrawals` of an execution payload, a
+/// `List[Withdrawal, MAX_WITHDRAWALS_PER_PAYLOAD]`.
+fn ssz_withdrawals_root(withdrawals: &[BeaconPayloadWithdrawal]) -> Result<H256> {
+    let roots = withdrawals
+        .iter()
+        .map(|withdrawal| {
+            let fields = vec![
+                ssz_uint64_chunk(withdrawal.index.parse()?),
+                ssz_uint64_chunk(withdrawal.validator_index.parse()?),
+                ssz_address_chunk(&withdrawal.address)?,
+                ssz_uint64_chunk(withdrawal.amount.parse()?),
+            ];
+            Ok(ssz_merkleize_chunks(fields, 2))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let root = ssz_merkleize_chunks(roots, WITHDRAWALS_DEPTH);
+    Ok(H256::from(ssz_mix_in_length(root, withdrawals.len())))
+}
+
+/// An entry of `state.historical_summaries`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconHistoricalSummary {
+    pub block_summary_root: H256,
+    pub state_summary_root: H256,
+}
+
+impl BeaconHistoricalSummary {
+    pub fn ssz_hash_tree_root(&self) -> H256 {
+        H256::from(sha256(
+            &[self.block_summary_root.0, self.state_summary_root.0].concat(),
+        ))
+    }
+}
+
+/// An entry of `state.historical_summaries` with the proof from
+/// `blockRoot -> state.historical_summaries[index]`.
+#[derive(Debug, Clone)]
+pub struct GetBeaconHistoricalSummary {
+    pub historical_summary: BeaconHistoricalSummary,
+    pub proof: Vec<H256>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBeaconSlotNumber {
@@ -335,12 +893,120 @@ pub struct GetBeaconGraffiti {
     pub proof: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBeaconRandaoMix {
+    pub randao_mix: String,
+    #[serde(deserialize_with = "deserialize_bigint")]
+    pub gindex: BigInt,
+    pub depth: u64,
+    pub proof: Vec<String>,
+}
+
+/// The genesis of the chain returned by the official Beacon Node API.
+/// https://ethereum.github.io/beacon-APIs/#/Beacon/getGenesis
+#[derive(Debug, Deserialize)]
+pub struct BeaconGenesis {
+    pub genesis_time: String,
+    pub genesis_validators_root: String,
+    pub genesis_fork_version: String,
+}
+
+/// The `fork` field of a state returned by the official Beacon Node API.
+/// https://ethereum.github.io/beacon-APIs/#/Beacon/getStateFork
+#[derive(Debug, Deserialize)]
+pub struct BeaconStateFork {
+    pub previous_version: String,
+    pub current_version: String,
+    pub epoch: String,
+}
+
+/// The sync committee struct according to the consensus spec.
+/// Reference: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/beacon-chain.md#synccommittee
+#[derive(Debug, Clone, Deserialize)]
+pub struct BeaconSyncCommittee {
+    pub pubkeys: Vec<String>,
+    pub aggregate_pubkey: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LightClientHeader {
+    pub beacon: BeaconHeader,
+}
+
+/// The light client bootstrap of a block returned by the official Beacon Node API, whose branch
+/// proves the current sync committee against the state root of the block.
+/// https://ethereum.github.io/beacon-APIs/#/Beacon/getLightClientBootstrap
+#[derive(Debug, Deserialize)]
+pub struct LightClientBootstrap {
+    pub header: LightClientHeader,
+    pub current_sync_committee: BeaconSyncCommittee,
+    pub current_sync_committee_branch: Vec<String>,
+}
+
+/// The genesis validators root of a state along with the SSZ proof from
+/// `stateRoot -> genesisValidatorsRoot`.
+#[derive(Debug)]
+pub struct GetBeaconGenesisValidatorsRoot {
+    pub genesis_validators_root: H256,
+    pub proof: Vec<H256>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBeaconHeadersFromOffsetRange {
     pub headers: Vec<String>,
 }
 
+impl BeaconClientBuilder {
+    /// Sets the timeout of every request, overriding the default of each route.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of times every request is retried after a server or connection error,
+    /// overriding the default of each route.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Sets the delay before the first retry, which doubles on every following retry.
+    pub fn initial_retry_delay(mut self, delay: Duration) -> Self {
+        self.initial_retry_delay = delay;
+        self
+    }
+
+    /// Sets the value of the `Authorization` header sent with every request.
+    pub fn auth_header(mut self, value: String) -> Self {
+        self.auth_header = Some(value);
+        self
+    }
+
+    pub fn build(self) -> Result<BeaconClient> {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = self.auth_header {
+            let mut value = HeaderValue::from_str(&value)?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+        let mut client = ReqwestClient::from_clients(
+            Client::builder().default_headers(headers.clone()).build()?,
+            reqwest::Client::builder()
+                .default_headers(headers)
+                .build()?,
+        );
+        client.timeout = self.timeout;
+        client.max_retries = self.max_retries;
+        client.initial_retry_delay = self.initial_retry_delay;
+        Ok(BeaconClient {
+            rpc_url: self.rpc_url,
+            client,
+        })
+    }
+}
+
 impl BeaconClient {
     /// Creates a new BeaconClient based on a rpc url.
     pub fn new(rpc_url: String) -> Self {
@@ -350,6 +1016,48 @@ impl BeaconClient {
         }
     }
 
+    /// Creates a builder for a BeaconClient with a configurable timeout, retries and
+    /// authorization.
+    pub fn builder(rpc_url: String) -> BeaconClientBuilder {
+        let client = ReqwestClient::new();
+        BeaconClientBuilder {
+            rpc_url,
+            timeout: client.timeout,
+            max_retries: client.max_retries,
+            initial_retry_delay: client.initial_retry_delay,
+            auth_header: None,
+        }
+    }
+
+    /// The url of the beacon node queried by this client.
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Checks that this client and `other` returned the same value for a leaf, so that a single
+    /// faulty or malicious node cannot feed a witness on its own.
+    fn check_agreement(
+        &self,
+        other: &BeaconClient,
+        leaf: &str,
+        beacon_id: &str,
+        value: &str,
+        other_value: &str,
+    ) -> Result<()> {
+        if !value.eq_ignore_ascii_case(other_value) {
+            return Err(anyhow::anyhow!(
+                "consensus mismatch: the {} of {} is {} according to {} but {} according to {}",
+                leaf,
+                beacon_id,
+                value,
+                self.rpc_url,
+                other_value,
+                other.rpc_url
+            ));
+        }
+        Ok(())
+    }
+
     /// Gets the block root at `head`.
     pub fn get_finalized_block_root_sync(&self) -> Result<String> {
         self.get_finalized_block_root()
@@ -357,7 +1065,12 @@ impl BeaconClient {
 
     /// Gets the latest finalized block root asynchronously.
     pub fn get_finalized_block_root(&self) -> Result<String> {
-        let endpoint = format!("{}/eth/v1/beacon/headers/finalized", self.rpc_url);
+        self.get_block_root(BeaconStateId::Finalized)
+    }
+
+    /// Resolves a state id into the root of the corresponding block.
+    pub fn get_block_root(&self, state_id: BeaconStateId) -> Result<String> {
+        let endpoint = format!("{}/eth/v1/beacon/headers/{}", self.rpc_url, state_id);
         let response = self.client.fetch(&endpoint)?;
         let parsed: Value = response.json()?;
 
@@ -444,17 +1157,66 @@ impl BeaconClient {
     pub fn get_validators_root(&self, beacon_id: String) -> Result<GetBeaconValidatorsRoot> {
         let endpoint = format!("{}/api/beacon/proof/validator/{}", self.rpc_url, beacon_id);
         debug!("{}", endpoint);
-        let client = Client::new();
-        let response = client
-            .get(&endpoint)
-            .timeout(Duration::new(120, 0))
-            .send()?;
+        let response = self
+            .client
+            .fetch_with_timeout(&endpoint, Duration::from_secs(120))?;
         let response: CustomResponse<GetBeaconValidatorsRoot> = response.json()?;
         assert!(response.success);
         debug!("done {}", endpoint);
         Ok(response.result)
     }
 
+    /// Gets the validators root like [`BeaconClient::get_validators_root`], failing if `other`
+    /// returns a different root.
+    pub fn get_validators_root_agreed(
+        &self,
+        other: &BeaconClient,
+        beacon_id: String,
+    ) -> Result<GetBeaconValidatorsRoot> {
+        let result = self.get_validators_root(beacon_id.clone())?;
+        let other_result = other.get_validators_root(beacon_id.clone())?;
+        self.check_agreement(
+            other,
+            "validators root",
+            &beacon_id,
+            &result.validators_root,
+            &other_result.validators_root,
+        )?;
+        Ok(result)
+    }
+
+    /// Gets the merkle root and the length of the validators list, such that
+    /// `validators_root = sha256(merkle_root, length)`.
+    ///
+    /// These are recovered from the proof of the first validator, whose last sibling is the
+    /// length leaf.
+    pub fn get_validators_list_node(&self, beacon_id: String) -> Result<BeaconValidatorsListNode> {
+        let response = self.get_validator(beacon_id, 0)?;
+        let proof = response
+            .proof
+            .iter()
+            .map(|p| bytes32!(p))
+            .collect::<Vec<H256>>();
+        let (length_leaf, branch) = proof
+            .split_last()
+            .ok_or_else(|| anyhow::anyhow!("empty validator proof"))?;
+
+        // The first validator is always the left child, so the root is restored by hashing with
+        // each sibling on the right.
+        let mut merkle_root = bytes32!(response.validator_root);
+        for sibling in branch {
+            merkle_root = H256(sha256(
+                &[merkle_root.as_bytes(), sibling.as_bytes()].concat(),
+            ));
+        }
+        let length = u64::from_le_bytes(length_leaf.as_bytes()[..8].try_into().unwrap());
+
+        Ok(BeaconValidatorsListNode {
+            merkle_root,
+            length,
+        })
+    }
+
     /// Gets the state of a validator based on a beacon_id and index, omitting any SSZ proofs.
     /// With repeated calls on the same beacon_id, this should be faster than `get_validator`.
     pub fn get_validator_witness(
@@ -520,8 +1282,9 @@ impl BeaconClient {
             self.rpc_url, beacon_id, pubkey
         );
         info!("{}", endpoint);
-        let client = Client::new();
-        let response = client.get(endpoint).timeout(Duration::new(120, 0)).send()?;
+        let response = self
+            .client
+            .fetch_with_timeout(&endpoint, Duration::from_secs(120))?;
         let response: CustomResponse<GetBeaconValidator> = response.json()?;
         assert!(response.success);
         Ok(response.result)
@@ -556,11 +1319,9 @@ impl BeaconClient {
 
     pub fn get_balance_witness(&self, beacon_id: String, idx: u64) -> Result<u64> {
         let endpoint = format!("{}/api/beacon/balance/{}/{}", self.rpc_url, beacon_id, idx);
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(endpoint)
-            .timeout(Duration::from_secs(300))
-            .send()?;
+        let response = self
+            .client
+            .fetch_with_timeout(&endpoint, Duration::from_secs(300))?;
         let response: GetBeaconBalanceWitness = response.json()?;
         Ok(response.balance)
     }
@@ -576,11 +1337,9 @@ impl BeaconClient {
             self.rpc_url, beacon_id, start_idx, end_idx
         );
         debug!("{}", endpoint);
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(endpoint)
-            .timeout(Duration::from_secs(300))
-            .send()?;
+        let response = self
+            .client
+            .fetch_with_timeout(&endpoint, Duration::from_secs(300))?;
         let response: GetBeaconBalanceBatchWitness = response.json()?;
         Ok(response.balances)
     }
@@ -706,19 +1465,137 @@ impl BeaconClient {
             self.rpc_url, beacon_id
         );
         info!("{}", endpoint);
-        let client = Client::new();
-        let response = client.get(endpoint).timeout(Duration::new(60, 0)).send()?;
+        let response = self
+            .client
+            .fetch_with_timeout(&endpoint, Duration::from_secs(60))?;
         let response: CustomResponse<GetBeaconExecutionPayload> = response.json()?;
         assert!(response.success);
         Ok(response.result)
     }
 
+    /// Gets the execution payload header of the block at the given `beacon_id`, along with the SSZ
+    /// proof from `blockRoot -> body.executionPayload`.
+    ///
+    /// The header is built from the execution payload of the block on the standard consensus api.
+    /// The proof is the upper part of the `executionPayload` proof, which runs from
+    /// `body.executionPayload.blockNumber` to the block root, and whose lower part has to open the
+    /// built header at its block number.
+    pub fn get_execution_payload_header(
+        &self,
+        beacon_id: String,
+    ) -> Result<GetBeaconExecutionPayloadHeader> {
+        let endpoint = format!("{}/eth/v2/beacon/blocks/{}", self.rpc_url, beacon_id);
+        info!("{}", endpoint);
+        let response = self.client.fetch(&endpoint)?;
+        let parsed: Value = response.json()?;
+        let mut payload = parsed["data"]["message"]["body"]["execution_payload"].clone();
+        let lists: BeaconExecutionPayloadLists = serde_json::from_value(payload.clone())?;
+        payload["transactions_root"] =
+            Value::String(format!("{:?}", ssz_transactions_root(&lists.transactions)?));
+        payload["withdrawals_root"] =
+            Value::String(format!("{:?}", ssz_withdrawals_root(&lists.withdrawals)?));
+        let header: BeaconExecutionPayloadHeader = serde_json::from_value(payload)?;
+
+        let execution_payload = self.get_execution_payload(beacon_id.clone())?;
+        let proof = execution_payload
+            .proof
+            .iter()
+            .map(|p| bytes32!(p))
+            .collect::<Vec<H256>>();
+        ensure!(
+            proof.len() > EXECUTION_PAYLOAD_HEADER_DEPTH,
+            "execution payload proof of {} is too short: {} siblings",
+            beacon_id,
+            proof.len()
+        );
+        let (header_proof, proof) = proof.split_at(EXECUTION_PAYLOAD_HEADER_DEPTH);
+        let block_number = ssz_uint64_chunk(execution_payload.block_number.parse()?);
+        ensure!(
+            ssz_restore_root(
+                block_number,
+                header_proof,
+                EXECUTION_PAYLOAD_BLOCK_NUMBER_INDEX
+            ) == header.ssz_hash_tree_root()?,
+            "the execution payload of {} does not match its proof",
+            beacon_id
+        );
+
+        Ok(GetBeaconExecutionPayloadHeader {
+            header,
+            proof: proof.to_vec(),
+        })
+    }
+
+    /// Gets the entry at `index` of `state.historical_summaries` for the given `beacon_id`, along
+    /// with the SSZ proof from `blockRoot -> state.historical_summaries[index]`.
+    ///
+    /// The entry is read off the far slot proofs of the `historical` route for the first slot of
+    /// the period that it summarizes, `capella_fork_slot + index * SLOTS_PER_HISTORICAL_ROOT`
+    /// under `config`. The block summary root is restored from the block root of that slot, and
+    /// the state summary root is the last sibling of its proof.
+    pub fn get_historical_summary(
+        &self,
+        beacon_id: String,
+        index: u64,
+        config: &BeaconConfig,
+    ) -> Result<GetBeaconHistoricalSummary> {
+        let target_slot = config.capella_fork_slot() + index * config.slots_per_historical_root;
+        let endpoint = format!(
+            "{}/api/beacon/proof/historical/{}/{}",
+            self.rpc_url, beacon_id, target_slot
+        );
+        info!("{}", endpoint);
+        let response = self.client.fetch(&endpoint)?;
+        let response: CustomResponse<GetBeaconHistoricalBlock> = response.json()?;
+        assert!(response.success);
+        let historical = response.result;
+
+        let block_root_proof = historical
+            .far_slot_block_root_proof
+            .iter()
+            .map(|p| bytes32!(p))
+            .collect::<Vec<H256>>();
+        let block_roots_depth = config.block_roots_depth();
+        ensure!(
+            block_root_proof.len() == block_roots_depth + 1,
+            "far slot block root proof of {} has {} siblings instead of {}",
+            beacon_id,
+            block_root_proof.len(),
+            block_roots_depth + 1
+        );
+        let historical_summary = BeaconHistoricalSummary {
+            block_summary_root: ssz_restore_root(
+                bytes32!(historical.target_block_root).0,
+                &block_root_proof[..block_roots_depth],
+                target_slot % config.slots_per_historical_root,
+            ),
+            state_summary_root: block_root_proof[block_roots_depth],
+        };
+        ensure!(
+            historical_summary.ssz_hash_tree_root()
+                == bytes32!(historical.far_slot_historical_summary_root),
+            "the historical summary {} of {} does not match its proof",
+            index,
+            beacon_id
+        );
+
+        Ok(GetBeaconHistoricalSummary {
+            historical_summary,
+            proof: historical
+                .far_slot_historical_summary_proof
+                .iter()
+                .map(|p| bytes32!(p))
+                .collect(),
+        })
+    }
+
     /// Gets the slot from header + SSZ proof at the given `beacon_id`.
     pub fn get_slot_number(&self, beacon_id: String) -> Result<GetBeaconSlotNumber> {
         let endpoint = format!("{}/api/beacon/proof/slot/{}", self.rpc_url, beacon_id);
         info!("{}", endpoint);
-        let client = Client::new();
-        let response = client.get(endpoint).timeout(Duration::new(60, 0)).send()?;
+        let response = self
+            .client
+            .fetch_with_timeout(&endpoint, Duration::from_secs(60))?;
         let response: CustomResponse<GetBeaconSlotNumber> = response.json()?;
         assert!(response.success);
         Ok(response.result)
@@ -734,6 +1611,25 @@ impl BeaconClient {
         Ok(parsed.data.header.message)
     }
 
+    /// Gets the header like [`BeaconClient::get_header`], failing if `other` returns a different
+    /// state root.
+    pub async fn get_header_agreed(
+        &self,
+        other: &BeaconClient,
+        beacon_id: String,
+    ) -> Result<BeaconHeader> {
+        let header = self.get_header(beacon_id.clone()).await?;
+        let other_header = other.get_header(beacon_id.clone()).await?;
+        self.check_agreement(
+            other,
+            "state root",
+            &beacon_id,
+            &header.state_root,
+            &other_header.state_root,
+        )?;
+        Ok(header)
+    }
+
     pub fn get_block_roots(&self, beacon_id: String) -> Result<GetBeaconBlockRoots> {
         let endpoint = format!("{}/api/beacon/proof/blockRoots/{}", self.rpc_url, beacon_id);
         info!("{}", endpoint);
@@ -743,6 +1639,124 @@ impl BeaconClient {
         Ok(response.result)
     }
 
+    /// Gets the genesis of the chain.
+    pub fn get_genesis(&self) -> Result<BeaconGenesis> {
+        let endpoint = format!("{}/eth/v1/beacon/genesis", self.rpc_url);
+        info!("{}", endpoint);
+        let response = self.client.fetch(&endpoint)?;
+        let parsed: BeaconVersionedData<BeaconGenesis> = response.json()?;
+        Ok(parsed.data)
+    }
+
+    /// Gets the `fork` field of the state with the given `state_id`.
+    pub fn get_state_fork(&self, state_id: String) -> Result<BeaconStateFork> {
+        let endpoint = format!("{}/eth/v1/beacon/states/{}/fork", self.rpc_url, state_id);
+        info!("{}", endpoint);
+        let response = self.client.fetch(&endpoint)?;
+        let parsed: BeaconData<BeaconStateFork> = response.json()?;
+        Ok(parsed.data)
+    }
+
+    /// Gets the light client bootstrap of the block at `block_root`. Nodes usually only serve
+    /// bootstraps for finalized blocks at the start of an epoch.
+    pub fn get_light_client_bootstrap(&self, block_root: String) -> Result<LightClientBootstrap> {
+        let endpoint = format!(
+            "{}/eth/v1/beacon/light_client/bootstrap/{}",
+            self.rpc_url, block_root
+        );
+        info!("{}", endpoint);
+        let response = self.client.fetch(&endpoint)?;
+        let parsed: BeaconVersionedData<LightClientBootstrap> = response.json()?;
+        Ok(parsed.data)
+    }
+
+    /// Gets the genesis validators root along with the SSZ proof from
+    /// `stateRoot -> genesisValidatorsRoot` for the state of the block at `beacon_id`.
+    ///
+    /// The proof is assembled from data that is already served: the branch of the neighbouring
+    /// `block_roots` field holds every sibling above the first four fields of the state, and the
+    /// genesis time, slot and fork fill in the first four fields. The depth of the state tree, 5
+    /// up to Deneb and 6 from Electra on, is read from the length of that branch.
+    pub fn get_genesis_validators_root(
+        &self,
+        beacon_id: String,
+    ) -> Result<GetBeaconGenesisValidatorsRoot> {
+        let endpoint = format!("{}/eth/v1/beacon/headers/{}", self.rpc_url, beacon_id);
+        let response = self.client.fetch(&endpoint)?;
+        let header = response
+            .json::<BeaconData<BeaconHeaderContainer>>()?
+            .data
+            .header
+            .message;
+        let genesis = self.get_genesis()?;
+        let fork = self.get_state_fork(header.state_root.clone())?;
+        let block_roots = self.get_block_roots(beacon_id.clone())?;
+
+        // The branch of blockRoots, the field at index 5, starts with latestBlockHeader, the node
+        // of fields 6..8, the node of fields 0..4 and the nodes of fields 8..16, 16..32 and, from
+        // Electra on, 32..64. The last 3 siblings are those of the block header.
+        let proof = block_roots
+            .proof
+            .iter()
+            .map(|p| bytes32!(p))
+            .collect::<Vec<H256>>();
+        let state_depth = proof.len().saturating_sub(3);
+        if state_depth < 4 {
+            return Err(anyhow::anyhow!(
+                "block roots proof of {} is too short: {} siblings",
+                beacon_id,
+                proof.len()
+            ));
+        }
+
+        let uint64_leaf = |value: u64| {
+            let mut leaf = [0u8; 32];
+            leaf[..8].copy_from_slice(&value.to_le_bytes());
+            leaf
+        };
+        let version_leaf = |version: &str| -> Result<[u8; 32]> {
+            let bytes = hex::decode(version.trim_start_matches("0x"))?;
+            let mut leaf = [0u8; 32];
+            leaf[..bytes.len().min(32)].copy_from_slice(&bytes[..bytes.len().min(32)]);
+            Ok(leaf)
+        };
+        let pair = |left: [u8; 32], right: [u8; 32]| sha256(&[left, right].concat());
+
+        let genesis_time = uint64_leaf(genesis.genesis_time.parse::<u64>()?);
+        let genesis_validators_root = bytes32!(genesis.genesis_validators_root);
+        let slot = uint64_leaf(header.slot.parse::<u64>()?);
+        let fork_root = pair(
+            pair(
+                version_leaf(&fork.previous_version)?,
+                version_leaf(&fork.current_version)?,
+            ),
+            pair(uint64_leaf(fork.epoch.parse::<u64>()?), [0u8; 32]),
+        );
+        let slot_and_fork = pair(slot, fork_root);
+
+        let first_fields = pair(pair(genesis_time, genesis_validators_root.0), slot_and_fork);
+        if H256::from(first_fields) != proof[2] {
+            return Err(anyhow::anyhow!(
+                "the genesis and fork of {} do not match its state",
+                beacon_id
+            ));
+        }
+
+        let block_roots_root = bytes32!(block_roots.block_roots_root);
+        let next_fields = pair(pair(proof[0].0, block_roots_root.0), proof[1].0);
+        let mut branch = vec![
+            H256::from(genesis_time),
+            H256::from(slot_and_fork),
+            H256::from(next_fields),
+        ];
+        branch.extend_from_slice(&proof[3..state_depth]);
+
+        Ok(GetBeaconGenesisValidatorsRoot {
+            genesis_validators_root,
+            proof: branch,
+        })
+    }
+
     pub fn get_graffiti(&self, beacon_id: String) -> Result<GetBeaconGraffiti> {
         let endpoint = format!("{}/api/beacon/proof/graffiti/{}", self.rpc_url, beacon_id);
         info!("{}", endpoint);
@@ -752,6 +1766,61 @@ impl BeaconClient {
         Ok(response.result)
     }
 
+    /// Gets the randao mix stored at `randao_mixes[epoch % EPOCHS_PER_HISTORICAL_VECTOR]` in the
+    /// state at the given `beacon_id`, along with the SSZ proof from `blockRoot -> randaoMix`.
+    ///
+    /// The proof comes from the `randaoMix` route of the proof server, since the standard
+    /// consensus api serves no state proofs. The mix it returns has to match the one served by
+    /// `/eth/v1/beacon/states/{state_id}/randao`.
+    pub fn get_randao_mix(&self, beacon_id: String, epoch: u64) -> Result<GetBeaconRandaoMix> {
+        let endpoint = format!(
+            "{}/api/beacon/proof/randaoMix/{}/{}",
+            self.rpc_url, beacon_id, epoch
+        );
+        info!("{}", endpoint);
+        let response = self.client.fetch(&endpoint)?;
+        ensure!(
+            response.status().is_success(),
+            "the proof server does not serve {}: {}",
+            endpoint,
+            response.status()
+        );
+        let response: CustomResponse<GetBeaconRandaoMix> = response.json()?;
+        assert!(response.success);
+
+        let state_randao = self.get_state_randao(beacon_id.clone(), epoch)?;
+        ensure!(
+            bytes32!(response.result.randao_mix) == bytes32!(state_randao),
+            "the randao mix of epoch {} at {} does not match its state",
+            epoch,
+            beacon_id
+        );
+        Ok(response.result)
+    }
+
+    /// Gets the randao mix of `epoch` from the state of the block at `beacon_id` using the
+    /// standard consensus api.
+    pub fn get_state_randao(&self, beacon_id: String, epoch: u64) -> Result<String> {
+        let endpoint = format!("{}/eth/v1/beacon/headers/{}", self.rpc_url, beacon_id);
+        let response = self.client.fetch(&endpoint)?;
+        let parsed: Value = response.json()?;
+        let state_root = parsed["data"]["header"]["message"]["state_root"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("failed to parse response"))?
+            .to_string();
+
+        let endpoint = format!(
+            "{}/eth/v1/beacon/states/{}/randao?epoch={}",
+            self.rpc_url, state_root, epoch
+        );
+        let response = self.client.fetch(&endpoint)?;
+        let parsed: Value = response.json()?;
+        parsed["data"]["randao"]
+            .as_str()
+            .map(|randao| randao.to_string())
+            .ok_or_else(|| anyhow::anyhow!("failed to parse response"))
+    }
+
     pub fn get_headers_from_offset_range(
         &self,
         beacon_id: String,
@@ -774,13 +1843,189 @@ impl BeaconClient {
 mod tests {
     extern crate dotenv;
 
-    use std::env;
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::{env, thread};
 
     use log::debug;
 
     use super::*;
     use crate::utils;
 
+    /// Serves one response per connection from `statuses`, answering successful requests with a
+    /// block header whose root is `root`. Returns the url of the server.
+    fn serve_block_headers(statuses: Vec<u16>, root: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let body = match status {
+                    200 => format!("{{\"data\":{{\"root\":\"{}\"}}}}", root),
+                    _ => String::new(),
+                };
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_beacon_client_retries_server_errors() {
+        let root = "0x3b4dd3ae2d7d4ae89ec1ab6daf6bb0b1f9d1e1d5c6e5c8a4dbd5c3b7a1a0f2e9";
+
+        let url = serve_block_headers(vec![503, 503, 200], root);
+        let client = BeaconClient::builder(url)
+            .timeout(Duration::from_secs(10))
+            .max_retries(2)
+            .initial_retry_delay(Duration::from_millis(10))
+            .auth_header("Bearer token".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.get_block_root(BeaconStateId::Finalized).unwrap(),
+            root
+        );
+
+        let url = serve_block_headers(vec![503, 503], root);
+        let client = BeaconClient::builder(url)
+            .max_retries(1)
+            .initial_retry_delay(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        assert!(client.get_block_root(BeaconStateId::Finalized).is_err());
+    }
+
+    #[test]
+    fn test_beacon_client_keeps_route_defaults() {
+        let root = "0x3b4dd3ae2d7d4ae89ec1ab6daf6bb0b1f9d1e1d5c6e5c8a4dbd5c3b7a1a0f2e9";
+
+        // The slot route is sent once, so the successful response is left for the next request.
+        let url = serve_block_headers(vec![503, 200], root);
+        let client = BeaconClient::builder(url)
+            .initial_retry_delay(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        assert!(client.get_slot_number("head".to_string()).is_err());
+        assert_eq!(
+            client.get_block_root(BeaconStateId::Finalized).unwrap(),
+            root
+        );
+    }
+
+    /// Answers `connections` requests with `body` as a successful response. Returns the url of
+    /// the server.
+    fn serve_json(body: String, connections: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for _ in 0..connections {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_beacon_clients_agreement() {
+        let validators_root = |root: &str| {
+            serde_json::json!({
+                "success": true,
+                "result": {
+                    "validatorsRoot": root,
+                    "gindex": "363",
+                    "depth": 8,
+                    "proof": []
+                }
+            })
+            .to_string()
+        };
+        let header = |state_root: &str| {
+            serde_json::json!({
+                "execution_optimistic": false,
+                "finalized": true,
+                "data": {
+                    "root": "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670",
+                    "canonical": true,
+                    "header": {
+                        "message": {
+                            "slot": "7000000",
+                            "proposer_index": "1",
+                            "parent_root": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                            "state_root": state_root,
+                            "body_root": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                        }
+                    }
+                }
+            })
+            .to_string()
+        };
+        let root = "0x4bba6e5bf53bd1ef1d9b6c9d8a6b6f0e1f2d1c8a58d4f4c9e0b2a7b3c6d5e4f3";
+        let other_root = "0x5c0b2e29c4f2d8a1e7b6c3d9f0a8e4b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7";
+        let beacon_id = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+
+        let client = BeaconClient::new(serve_json(validators_root(root), 2));
+        let agreeing = BeaconClient::new(serve_json(validators_root(&root.to_uppercase()), 1));
+        let disagreeing = BeaconClient::new(serve_json(validators_root(other_root), 1));
+        let result = client
+            .get_validators_root_agreed(&agreeing, beacon_id.to_string())
+            .unwrap();
+        assert_eq!(result.validators_root, root);
+        let err = client
+            .get_validators_root_agreed(&disagreeing, beacon_id.to_string())
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("consensus mismatch: the validators root of"),
+            "{}",
+            err
+        );
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let client = BeaconClient::new(serve_json(header(root), 1));
+        let disagreeing = BeaconClient::new(serve_json(header(other_root), 1));
+        let err = rt
+            .block_on(client.get_header_agreed(&disagreeing, beacon_id.to_string()))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("consensus mismatch: the state root of"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_validator_hash_tree_root() {
         let validator = BeaconValidator {
@@ -797,6 +2042,89 @@ mod tests {
         println!("{}", root);
     }
 
+    /// A `/api/beacon/proof/validator/[beacon_id]/[validator_idx]` response with the given
+    /// validator fields, as parsed by `BeaconClient::get_validator`.
+    fn parse_validator_response(
+        pubkey: &str,
+        withdrawal_credentials: &str,
+    ) -> Result<GetBeaconValidator> {
+        let body = serde_json::json!({
+            "success": true,
+            "result": {
+                "validatorRoot": "0x2baf4065b5d6246410518c7981e5507ce82d46d87f8099df52c396c3b62b0fd5",
+                "validatorsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "validatorIdx": 0,
+                "validator": {
+                    "pubkey": pubkey,
+                    "withdrawalCredentials": withdrawal_credentials,
+                    "effectiveBalance": 32000000000u64,
+                    "slashed": false,
+                    "activationEligibilityEpoch": "0",
+                    "activationEpoch": "0",
+                    "exitEpoch": "18446744073709551615",
+                    "withdrawableEpoch": "18446744073709551615"
+                },
+                "gindex": "0",
+                "depth": 0,
+                "proof": []
+            }
+        });
+        let response: CustomResponse<GetBeaconValidator> = serde_json::from_value(body)?;
+        Ok(response.result)
+    }
+
+    #[test]
+    fn test_validator_response_validation() {
+        let pubkey = "0x933ad9491b62059dd065b560d256d8957a8c402cc6e8d8ee7290ae11e8f7329267a8811c397529dac52ae1342ba58c95";
+        let withdrawal_credentials =
+            "0x0100000000000000000000000d369bb49efa5100fd3b86a9f828c55da04d2d50";
+        let response = parse_validator_response(pubkey, withdrawal_credentials).unwrap();
+        assert_eq!(
+            response.validator.ssz_hash_tree_root(),
+            bytes32!("0x2baf4065b5d6246410518c7981e5507ce82d46d87f8099df52c396c3b62b0fd5")
+        );
+
+        let truncated_pubkey = &pubkey[..pubkey.len() - 2];
+        let err = parse_validator_response(truncated_pubkey, withdrawal_credentials).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("validator pubkey has 47 bytes, expected 48"),
+            "{}",
+            err
+        );
+
+        let non_hex_credentials =
+            "0x01000000000000000000000z0d369bb49efa5100fd3b86a9f828c55da04d2d50";
+        let err = parse_validator_response(pubkey, non_hex_credentials).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("validator withdrawal_credentials is not a 0x-prefixed hex string"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_beacon_validator_new() {
+        let validator = |exit_epoch: &str| {
+            BeaconValidator::new(
+                "0x933ad9491b62059dd065b560d256d8957a8c402cc6e8d8ee7290ae11e8f7329267a8811c397529dac52ae1342ba58c95".to_string(),
+                "0x0100000000000000000000000d369bb49efa5100fd3b86a9f828c55da04d2d50".to_string(),
+                32000000000,
+                false,
+                "0".to_string(),
+                "0".to_string(),
+                exit_epoch.to_string(),
+                "18446744073709551615".to_string(),
+            )
+        };
+        assert!(validator("18446744073709551615").is_ok());
+        assert_eq!(
+            validator("18446744073709551616").unwrap_err().field(),
+            "exit_epoch"
+        );
+    }
+
     #[cfg_attr(feature = "ci", ignore)]
     #[test]
     fn test_get_validators_root_by_slot() -> Result<()> {
@@ -823,6 +2151,470 @@ mod tests {
         Ok(())
     }
 
+    #[cfg_attr(feature = "ci", ignore)]
+    #[test]
+    fn test_get_validators_list_node() -> Result<()> {
+        utils::setup_logger();
+        dotenv::dotenv()?;
+        let rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(rpc.to_string());
+        let block_root = "0x6b6964f45d0aeff741260ec4faaf76bb79a009fc18ae17979784d92aec374946";
+        let validators_root = client.get_validators_root(block_root.to_string())?;
+        let list_node = client.get_validators_list_node(block_root.to_string())?;
+        debug!("{:?}", list_node);
+
+        let mut length_leaf = [0u8; 32];
+        length_leaf[..8].copy_from_slice(&list_node.length.to_le_bytes());
+        let root = sha256(&[list_node.merkle_root.as_bytes(), &length_leaf].concat());
+        assert_eq!(H256(root), bytes32!(validators_root.validators_root));
+        Ok(())
+    }
+
+    /// Builds the routes of a node whose Deneb state has the given genesis, slot and fork in its
+    /// first four fields and arbitrary roots in the others. Returns the routes and the state root.
+    fn genesis_routes(
+        beacon_id: &str,
+        genesis_time: u64,
+        genesis_validators_root: H256,
+    ) -> (HashMap<String, String>, H256) {
+        let uint64_leaf = |value: u64| {
+            let mut leaf = [0u8; 32];
+            leaf[..8].copy_from_slice(&value.to_le_bytes());
+            leaf
+        };
+        let pair = |left: [u8; 32], right: [u8; 32]| sha256(&[left, right].concat());
+        let (slot, previous_version, current_version, fork_epoch) = (8_000_000, 3u8, 4u8, 194048);
+        let mut version_leaves = [[0u8; 32]; 2];
+        version_leaves[0][3] = previous_version;
+        version_leaves[1][3] = current_version;
+
+        let mut nodes = (0..32u8).map(|i| [i + 100; 32]).collect::<Vec<_>>();
+        nodes[0] = uint64_leaf(genesis_time);
+        nodes[1] = genesis_validators_root.0;
+        nodes[2] = uint64_leaf(slot);
+        nodes[3] = pair(
+            pair(version_leaves[0], version_leaves[1]),
+            pair(uint64_leaf(fork_epoch), [0u8; 32]),
+        );
+        let mut tree = vec![nodes.clone()];
+        while nodes.len() > 1 {
+            nodes = nodes.chunks(2).map(|c| pair(c[0], c[1])).collect();
+            tree.push(nodes.clone());
+        }
+        let state_root = H256::from(tree[5][0]);
+
+        // The branch of blockRoots, at gindex 32 + 5, followed by three siblings in the header.
+        let mut branch = Vec::new();
+        let mut index = 5;
+        for level in tree.iter().take(5) {
+            branch.push(format!("0x{}", hex::encode(level[index ^ 1])));
+            index /= 2;
+        }
+        branch.extend((0..3).map(|i| format!("0x{}", hex::encode([i; 32]))));
+
+        let routes = HashMap::from([
+            (
+                format!("/eth/v1/beacon/headers/{}", beacon_id),
+                serde_json::json!({
+                    "execution_optimistic": false,
+                    "finalized": true,
+                    "data": {
+                        "root": beacon_id,
+                        "canonical": true,
+                        "header": {
+                            "message": {
+                                "slot": slot.to_string(),
+                                "proposer_index": "1",
+                                "parent_root": format!("0x{}", hex::encode([1u8; 32])),
+                                "state_root": format!("{:?}", state_root),
+                                "body_root": format!("0x{}", hex::encode([2u8; 32])),
+                            }
+                        }
+                    }
+                })
+                .to_string(),
+            ),
+            (
+                "/eth/v1/beacon/genesis".to_string(),
+                serde_json::json!({
+                    "data": {
+                        "genesis_time": "1606824023",
+                        "genesis_validators_root": format!("{:?}", genesis_validators_root),
+                        "genesis_fork_version": "0x00000000",
+                    }
+                })
+                .to_string(),
+            ),
+            (
+                format!("/eth/v1/beacon/states/{:?}/fork", state_root),
+                serde_json::json!({
+                    "execution_optimistic": false,
+                    "finalized": true,
+                    "data": {
+                        "previous_version": format!("0x000000{:02x}", previous_version),
+                        "current_version": format!("0x000000{:02x}", current_version),
+                        "epoch": fork_epoch.to_string(),
+                    }
+                })
+                .to_string(),
+            ),
+            (
+                format!("/api/beacon/proof/blockRoots/{}", beacon_id),
+                serde_json::json!({
+                    "success": true,
+                    "result": {
+                        "blockRootsRoot": format!("0x{}", hex::encode(tree[0][5])),
+                        "blockRoots": [],
+                        "gindex": "357",
+                        "depth": 8,
+                        "proof": branch,
+                    }
+                })
+                .to_string(),
+            ),
+        ]);
+        (routes, state_root)
+    }
+
+    #[test]
+    fn test_get_genesis_validators_root() {
+        let beacon_id = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+        let genesis_validators_root =
+            bytes32!("0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95");
+
+        let (routes, state_root) = genesis_routes(beacon_id, 1606824023, genesis_validators_root);
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let result = client
+            .get_genesis_validators_root(beacon_id.to_string())
+            .unwrap();
+        assert_eq!(result.genesis_validators_root, genesis_validators_root);
+        assert_eq!(result.proof.len(), 5);
+        let mut root = result.genesis_validators_root.0;
+        for (i, sibling) in result.proof.iter().enumerate() {
+            root = match (33 >> i) & 1 {
+                1 => sha256(&[sibling.0, root].concat()),
+                _ => sha256(&[root, sibling.0].concat()),
+            };
+        }
+        assert_eq!(H256::from(root), state_root);
+
+        // The node serves a different genesis time than the one in the state.
+        let (routes, _) = genesis_routes(beacon_id, 1606824000, genesis_validators_root);
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let err = client
+            .get_genesis_validators_root(beacon_id.to_string())
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("do not match its state"),
+            "{}",
+            err
+        );
+    }
+
+    /// Builds the routes of a node that serves `payload` as the execution payload of the block and
+    /// an `executionPayload` proof whose lower part opens the header with the given list roots and
+    /// `block_number`. Returns the routes and the upper part of the proof.
+    fn execution_payload_routes(
+        beacon_id: &str,
+        payload: &Value,
+        transactions_root: H256,
+        withdrawals_root: H256,
+        block_number: u64,
+    ) -> (HashMap<String, String>, Vec<H256>) {
+        let mut header = payload.clone();
+        header["transactions_root"] = Value::String(format!("{:?}", transactions_root));
+        header["withdrawals_root"] = Value::String(format!("{:?}", withdrawals_root));
+        header["block_number"] = Value::String(block_number.to_string());
+        let header: BeaconExecutionPayloadHeader = serde_json::from_value(header).unwrap();
+
+        let mut nodes = header.ssz_field_roots().unwrap();
+        nodes.resize(32, [0u8; 32]);
+        let mut branch = Vec::new();
+        let mut index = EXECUTION_PAYLOAD_BLOCK_NUMBER_INDEX as usize;
+        while nodes.len() > 1 {
+            branch.push(H256::from(nodes[index ^ 1]));
+            nodes = nodes
+                .chunks(2)
+                .map(|c| sha256(&[c[0], c[1]].concat()))
+                .collect();
+            index /= 2;
+        }
+        let upper = (0..7u8).map(|i| H256::from([i + 100; 32])).collect_vec();
+        branch.extend(upper.iter().copied());
+
+        let routes = HashMap::from([
+            (
+                format!("/eth/v2/beacon/blocks/{}", beacon_id),
+                serde_json::json!({
+                    "version": "deneb",
+                    "execution_optimistic": false,
+                    "finalized": true,
+                    "data": { "message": { "body": { "execution_payload": payload } } }
+                })
+                .to_string(),
+            ),
+            (
+                format!("/api/beacon/proof/executionPayload/{}", beacon_id),
+                serde_json::json!({
+                    "success": true,
+                    "result": {
+                        "blockNumber": block_number.to_string(),
+                        "proof": branch.iter().map(|p| format!("{:?}", p)).collect_vec(),
+                    }
+                })
+                .to_string(),
+            ),
+        ]);
+        (routes, upper)
+    }
+
+    #[test]
+    fn test_get_execution_payload_header() {
+        let beacon_id = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+        let mut payload = serde_json::json!({
+            "parent_hash": format!("0x{}", hex::encode([1u8; 32])),
+            "fee_recipient": format!("0x{}", hex::encode([2u8; 20])),
+            "state_root": format!("0x{}", hex::encode([3u8; 32])),
+            "receipts_root": format!("0x{}", hex::encode([4u8; 32])),
+            "logs_bloom": format!("0x{}", hex::encode([5u8; 256])),
+            "prev_randao": format!("0x{}", hex::encode([6u8; 32])),
+            "block_number": "19000000",
+            "gas_limit": "30000000",
+            "gas_used": "12345678",
+            "timestamp": "1705000000",
+            "extra_data": "0x6265617665726275696c642e6f7267",
+            "base_fee_per_gas": "25000000000",
+            "block_hash": format!("0x{}", hex::encode([7u8; 32])),
+            "transactions": [],
+            "withdrawals": [],
+            "blob_gas_used": "131072",
+            "excess_blob_gas": "0",
+        });
+
+        // The hash tree roots of empty transactions and withdrawals lists.
+        let empty_transactions_root =
+            bytes32!("0x7ffe241ea60187fdb0187bfa22de35d1f9bed7ab061d9401fd47e34a54fbede1");
+        let empty_withdrawals_root =
+            bytes32!("0x792930bbd5baac43bcc798ee49aa8185ef76bb3b44ba62b91d86ae569e4bb535");
+        let (routes, upper) = execution_payload_routes(
+            beacon_id,
+            &payload,
+            empty_transactions_root,
+            empty_withdrawals_root,
+            19000000,
+        );
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let result = client
+            .get_execution_payload_header(beacon_id.to_string())
+            .unwrap();
+        assert_eq!(
+            bytes32!(result.header.transactions_root),
+            empty_transactions_root
+        );
+        assert_eq!(
+            bytes32!(result.header.withdrawals_root),
+            empty_withdrawals_root
+        );
+        assert_eq!(result.proof, upper);
+
+        payload["transactions"] =
+            serde_json::json!([format!("0x{}", hex::encode([0xabu8; 40])), "0x01",]);
+        payload["withdrawals"] = serde_json::json!([{
+            "index": "7",
+            "validator_index": "42",
+            "address": format!("0x{}", hex::encode([0x11u8; 20])),
+            "amount": "1000",
+        }]);
+        let transactions_root =
+            bytes32!("0x2497ee525e7ca95a7e8c9e8c8286f5a495be89331b7d97c3946bcd7857a174bf");
+        let withdrawals_root =
+            bytes32!("0xfb3240620a5f100574ea18a592aab4d57bd5091ce2ef967027d6453c42bed398");
+        let (routes, _) = execution_payload_routes(
+            beacon_id,
+            &payload,
+            transactions_root,
+            withdrawals_root,
+            19000000,
+        );
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let result = client
+            .get_execution_payload_header(beacon_id.to_string())
+            .unwrap();
+        assert_eq!(bytes32!(result.header.transactions_root), transactions_root);
+        assert_eq!(bytes32!(result.header.withdrawals_root), withdrawals_root);
+
+        // The proof opens a header with a different block number than the served payload.
+        let (routes, _) = execution_payload_routes(
+            beacon_id,
+            &payload,
+            transactions_root,
+            withdrawals_root,
+            19000001,
+        );
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let err = client
+            .get_execution_payload_header(beacon_id.to_string())
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("does not match its proof"),
+            "{}",
+            err
+        );
+    }
+
+    /// Builds the routes of a node that serves the far slot proofs of the historical block at
+    /// `target_slot`, whose historical summary has the given root.
+    fn historical_routes(
+        beacon_id: &str,
+        target_slot: u64,
+        historical_summary_root: H256,
+    ) -> HashMap<String, String> {
+        let hex_node = |byte: u8| format!("0x{}", hex::encode([byte; 32]));
+        let mut far_slot_block_root_proof = (1..14u8).map(hex_node).collect_vec();
+        far_slot_block_root_proof.push(hex_node(0x77));
+        HashMap::from([(
+            format!("/api/beacon/proof/historical/{}/{}", beacon_id, target_slot),
+            serde_json::json!({
+                "success": true,
+                "result": {
+                    "targetBlockRoot": hex_node(0x42),
+                    "farSlotHistoricalSummaryRoot": format!("{:?}", historical_summary_root),
+                    "farSlotHistoricalSummaryProof": (100..133u8).map(hex_node).collect_vec(),
+                    "farSlotBlockRootProof": far_slot_block_root_proof,
+                    "closeSlotBlockRootProof": (0..21u8).map(hex_node).collect_vec(),
+                }
+            })
+            .to_string(),
+        )])
+    }
+
+    #[test]
+    fn test_get_historical_summary() {
+        let beacon_id = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+        let config = BeaconConfig::mainnet();
+        let index = 10;
+        let target_slot = config.capella_fork_slot() + index * config.slots_per_historical_root;
+        let historical_summary_root =
+            bytes32!("0x34fd19ca765d61095a2fa3eee6f413ca5d1111f24034722a941e0782f23ac9c8");
+
+        let routes = historical_routes(beacon_id, target_slot, historical_summary_root);
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let result = client
+            .get_historical_summary(beacon_id.to_string(), index, &config)
+            .unwrap();
+        assert_eq!(
+            result.historical_summary,
+            BeaconHistoricalSummary {
+                block_summary_root: bytes32!(
+                    "0xc807480787a3b6521a751fa92713ce51855ec07a0f4920e0c0ca62fb8e0c60d8"
+                ),
+                state_summary_root: H256::from([0x77; 32]),
+            }
+        );
+        assert_eq!(
+            result.historical_summary.ssz_hash_tree_root(),
+            historical_summary_root
+        );
+        assert_eq!(
+            result.proof,
+            (100..133u8).map(|i| H256::from([i; 32])).collect_vec()
+        );
+
+        // The node serves proofs for a different summary than the one in the state.
+        let routes = historical_routes(beacon_id, target_slot, H256::from([0x88; 32]));
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let err = client
+            .get_historical_summary(beacon_id.to_string(), index, &config)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("does not match its proof"),
+            "{}",
+            err
+        );
+    }
+
+    /// Builds the routes of a node whose state at `beacon_id` has `state_randao` as the mix of
+    /// `epoch`, and whose proof server serves `randao_mix` for it if given.
+    fn randao_routes(
+        beacon_id: &str,
+        epoch: u64,
+        state_randao: H256,
+        randao_mix: Option<H256>,
+    ) -> HashMap<String, String> {
+        let state_root = H256::from([3u8; 32]);
+        let mut routes = HashMap::from([
+            (
+                format!("/eth/v1/beacon/headers/{}", beacon_id),
+                serde_json::json!({
+                    "data": { "header": { "message": { "state_root": format!("{:?}", state_root) } } }
+                })
+                .to_string(),
+            ),
+            (
+                format!("/eth/v1/beacon/states/{:?}/randao?epoch={}", state_root, epoch),
+                serde_json::json!({ "data": { "randao": format!("{:?}", state_randao) } })
+                    .to_string(),
+            ),
+        ]);
+        if let Some(randao_mix) = randao_mix {
+            let proof = (0..24u8)
+                .map(|i| format!("0x{}", hex::encode([i; 32])))
+                .collect_vec();
+            routes.insert(
+                format!("/api/beacon/proof/randaoMix/{}/{}", beacon_id, epoch),
+                serde_json::json!({
+                    "success": true,
+                    "result": {
+                        "randaoMix": format!("{:?}", randao_mix),
+                        "gindex": (365u64 * 65536 + epoch % 65536).to_string(),
+                        "depth": 24,
+                        "proof": proof,
+                    }
+                })
+                .to_string(),
+            );
+        }
+        routes
+    }
+
+    #[test]
+    fn test_get_randao_mix() {
+        let beacon_id = "0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670";
+        let epoch = 250000;
+        let randao_mix =
+            bytes32!("0x9a4e1b4e7a3d0d3cf64e5fd8c5b34b53e6e5d1ac36a05e1e06fdfbd2b8c1d7a1");
+
+        let routes = randao_routes(beacon_id, epoch, randao_mix, Some(randao_mix));
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let result = client.get_randao_mix(beacon_id.to_string(), epoch).unwrap();
+        assert_eq!(bytes32!(result.randao_mix), randao_mix);
+        assert_eq!(result.proof.len(), 24);
+
+        // The proof server serves a different mix than the state.
+        let routes = randao_routes(beacon_id, epoch, randao_mix, Some(H256::from([9u8; 32])));
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let err = client
+            .get_randao_mix(beacon_id.to_string(), epoch)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("does not match its state"),
+            "{}",
+            err
+        );
+
+        // The node has no proof server.
+        let routes = randao_routes(beacon_id, epoch, randao_mix, None);
+        let client = BeaconClient::new(mock::serve_routes(routes));
+        let err = client
+            .get_randao_mix(beacon_id.to_string(), epoch)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("the proof server does not serve"),
+            "{}",
+            err
+        );
+    }
+
     #[cfg_attr(feature = "ci", ignore)]
     #[test]
     fn test_get_validator_by_slot() -> Result<()> {
@@ -0,0 +1,82 @@
+//! Off-circuit references for the beacon gadgets, which compute the same statistics from the
+//! consensus client directly. These serve as golden values when testing circuits end-to-end.
+
+use anyhow::{anyhow, Result};
+use ethers::types::H256;
+
+use super::BeaconClient;
+use crate::utils::hex;
+
+/// Sums the balances, in Gwei, of the validators with the given pubkeys at a block root.
+pub fn validator_balance_sum(
+    client: &BeaconClient,
+    block_root: H256,
+    pubkeys: &[String],
+) -> Result<u64> {
+    let beacon_id = hex!(block_root.as_bytes());
+    pubkeys.iter().try_fold(0u64, |sum, pubkey| {
+        let balance = client
+            .get_validator_balance_by_pubkey_v2(beacon_id.clone(), pubkey.clone())?
+            .balance;
+        sum.checked_add(balance)
+            .ok_or_else(|| anyhow!("validator balance sum overflows a u64"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::builder::CircuitBuilder;
+    use crate::frontend::eth::vars::BLSPubkeyVariable;
+    use crate::frontend::uint::uint64::U64Variable;
+    use crate::frontend::vars::Bytes32Variable;
+    use crate::utils::{bytes, bytes32};
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_validator_balance_sum_matches_circuit() {
+        env_logger::try_init().unwrap_or_default();
+        dotenv::dotenv().ok();
+
+        let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
+        let client = BeaconClient::new(consensus_rpc);
+        let block_root =
+            bytes32!("0xe6d6e23b8e07e15b98811579e5f6c36a916b749fd7146d009196beeddc4a6670");
+        let pubkeys = (0..4)
+            .map(|i| {
+                client
+                    .get_validator_witness(hex!(block_root.as_bytes()), i * 1000)
+                    .unwrap()
+                    .validator
+                    .pubkey
+            })
+            .collect::<Vec<_>>();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_beacon_client(client.clone());
+        let block_root_var = builder.constant::<Bytes32Variable>(block_root);
+        let state = builder.beacon_state(block_root_var);
+        let mut sum = builder.zero::<U64Variable>();
+        for pubkey in pubkeys.iter() {
+            let pubkey = builder.constant::<BLSPubkeyVariable>(bytes!(pubkey));
+            let (index, _) = builder.beacon_get_validator_by_pubkey(state.validators(), pubkey);
+            let balance = state.get_balance(&mut builder, index);
+            sum = builder.add(sum, balance);
+        }
+        builder.write(sum);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let expected = validator_balance_sum(&client, block_root, &pubkeys).unwrap();
+        assert_eq!(output.read::<U64Variable>(), expected);
+    }
+}
@@ -4,6 +4,7 @@ use std::env;
 
 use ethers::providers::{Http, Provider};
 
+#[cfg(feature = "beacon")]
 pub mod beacon;
 
 #[derive(Debug, Clone)]
@@ -1,12 +1,14 @@
 use std::sync::Once;
 pub mod eth;
 pub mod hash;
+#[cfg(feature = "mpt")]
 pub mod lido;
 pub mod poseidon;
 pub mod proof;
 pub mod reqwest;
 pub mod serde;
 pub mod stream;
+pub mod test_utils;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use log::{set_max_level, LevelFilter};
@@ -43,6 +45,22 @@ pub macro hex($bytes:expr) {{
     hex_string
 }}
 
+/// Builds the circuit defined by `builder` and asserts that it has at most `max_gates` gates.
+///
+/// Returns the compiled circuit, so that it can be used further in the test.
+pub macro assert_circuit_size($builder:expr, $max_gates:expr) {{
+    let circuit = $builder.build();
+    let num_gates = circuit.stats().num_gates;
+    let max_gates: usize = $max_gates;
+    assert!(
+        num_gates <= max_gates,
+        "circuit has {} gates, which exceeds the bound of {} gates",
+        num_gates,
+        max_gates
+    );
+    circuit
+}}
+
 pub fn byte_to_bits_be(input: u8) -> [bool; 8] {
     let mut bits = [false; 8];
     for i in 0..8 {
@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+use crate::backend::circuit::PlonkParameters;
+
+/// The encoding of a proof saved to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofFormat {
+    /// The proof bytes as a `0x` prefixed hex string.
+    Hex,
+    /// The proof bytes as a standard base64 string.
+    Base64,
+    /// The proof bytes as is.
+    RawFile,
+}
+
+impl ProofFormat {
+    /// Encodes the proof bytes in this format.
+    pub fn encode(&self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            ProofFormat::Hex => format!("0x{}", hex::encode(bytes)).into_bytes(),
+            ProofFormat::Base64 => base64::encode(bytes).into_bytes(),
+            ProofFormat::RawFile => bytes.to_vec(),
+        }
+    }
+
+    /// Decodes the proof bytes from this format.
+    pub fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            ProofFormat::Hex => {
+                let encoded = std::str::from_utf8(encoded)?.trim();
+                Ok(hex::decode(encoded.strip_prefix("0x").unwrap_or(encoded))?)
+            }
+            ProofFormat::Base64 => Ok(base64::decode(std::str::from_utf8(encoded)?.trim())?),
+            ProofFormat::RawFile => Ok(encoded.to_vec()),
+        }
+    }
+}
+
+/// Saves a proof to the file at `path` in the given format.
+pub fn save_proof<L: PlonkParameters<D>, const D: usize>(
+    proof: &ProofWithPublicInputs<L::Field, L::Config, D>,
+    format: ProofFormat,
+    path: impl AsRef<Path>,
+) -> Result<()> {
+    fs::write(path, format.encode(&proof.to_bytes()))?;
+    Ok(())
+}
+
+/// Loads a proof saved with [`save_proof`] in the given format.
+///
+/// The common data of the circuit is needed to split the bytes back into the parts of the proof.
+pub fn load_proof<L: PlonkParameters<D>, const D: usize>(
+    common_data: &CommonCircuitData<L::Field, D>,
+    format: ProofFormat,
+    path: impl AsRef<Path>,
+) -> Result<ProofWithPublicInputs<L::Field, L::Config, D>> {
+    let bytes = format.decode(&fs::read(path)?)?;
+    ProofWithPublicInputs::from_bytes(bytes, common_data)
+        .map_err(|e| anyhow!("failed to deserialize proof: {:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::circuit::DefaultParameters;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_proof_format_roundtrip() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<U64Variable>();
+        let b = builder.read::<U64Variable>();
+        let c = builder.add(a, b);
+        builder.write(c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<U64Variable>(3);
+        input.write::<U64Variable>(4);
+        let (proof, output) = circuit.prove(&input);
+
+        let dir = tempfile::tempdir().unwrap();
+        for format in [ProofFormat::Hex, ProofFormat::Base64, ProofFormat::RawFile] {
+            let path = dir.path().join(format!("{:?}.proof", format));
+            save_proof::<L, D>(&proof, format, &path).unwrap();
+            let loaded = load_proof::<L, D>(&circuit.data.common, format, &path).unwrap();
+            assert_eq!(loaded, proof);
+            circuit.verify(&loaded, &input, &output);
+        }
+
+        // Hex proofs are saved with a `0x` prefix and base64 proofs without one.
+        let hex_proof = fs::read_to_string(dir.path().join("Hex.proof")).unwrap();
+        assert_eq!(hex_proof, format!("0x{}", hex::encode(proof.to_bytes())));
+        let base64_proof = fs::read_to_string(dir.path().join("Base64.proof")).unwrap();
+        assert_eq!(base64_proof, base64::encode(proof.to_bytes()));
+    }
+}
@@ -1,3 +1,6 @@
+mod format;
+
+pub use format::*;
 use plonky2::plonk::proof::ProofWithPublicInputsTarget;
 
 use crate::prelude::CircuitVariable;
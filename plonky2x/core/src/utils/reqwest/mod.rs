@@ -4,40 +4,56 @@ use anyhow::{anyhow, Result};
 use log::debug;
 use reqwest::blocking::Response;
 
-#[derive(Debug, Clone, Default)]
+/// The default timeout of blocking requests.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The default timeout of asynchronous requests.
+const DEFAULT_ASYNC_TIMEOUT: Duration = Duration::from_secs(900);
+
+/// The default number of retries of a request sent with `fetch` or `fetch_async`.
+const DEFAULT_MAX_RETRIES: u32 = 2;
+
+#[derive(Debug, Clone)]
 pub struct ReqwestClient {
     pub client: reqwest::blocking::Client,
     pub client_async: reqwest::Client,
+    /// Overrides the timeout of each request, which otherwise defaults to 300 seconds for
+    /// blocking requests and 900 seconds for asynchronous ones, or to the timeout passed to
+    /// `fetch_with_timeout`.
+    pub timeout: Option<Duration>,
+    /// Overrides the number of times a request is retried after a server or connection error,
+    /// which otherwise defaults to 2, or to none for requests sent with `fetch_with_timeout`.
+    pub max_retries: Option<u32>,
+    /// The delay before the first retry, which doubles on every following retry.
+    pub initial_retry_delay: Duration,
 }
 
 impl ReqwestClient {
     pub fn new() -> Self {
-        ReqwestClient {
-            client: reqwest::blocking::Client::new(),
-            client_async: reqwest::Client::new(),
-        }
+        Self::from_clients(reqwest::blocking::Client::new(), reqwest::Client::new())
     }
 
     pub fn from_clients(client: reqwest::blocking::Client, client_async: reqwest::Client) -> Self {
         ReqwestClient {
             client,
             client_async,
+            timeout: None,
+            max_retries: None,
+            initial_retry_delay: Duration::from_secs(5),
         }
     }
 
     pub async fn fetch_async(&self, endpoint: &str) -> Result<reqwest::Response> {
-        const MAX_RETRIES: u32 = 2;
-        const INITIAL_RETRY_DELAY: u64 = 5;
-
+        let max_retries = self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
         let mut retries = 0;
-        let mut retry_delay = INITIAL_RETRY_DELAY;
+        let mut retry_delay = self.initial_retry_delay;
 
         loop {
             debug!("fetching {}: retries={}", endpoint, retries);
             let response = self
                 .client_async
                 .get(endpoint)
-                .timeout(core::time::Duration::from_secs(900))
+                .timeout(self.timeout.unwrap_or(DEFAULT_ASYNC_TIMEOUT))
                 .send()
                 .await;
             match response {
@@ -46,7 +62,7 @@ impl ReqwestClient {
                         return Ok(res);
                     } else if res.status().is_server_error() {
                         debug!("Server error: {:?}", res.status());
-                        if retries >= MAX_RETRIES {
+                        if retries >= max_retries {
                             return Err(anyhow!("Maximum retries exceeded"));
                         }
                     } else {
@@ -55,31 +71,38 @@ impl ReqwestClient {
                 }
                 Err(err) => {
                     debug!("Connection error {:?}", err);
-                    if retries >= MAX_RETRIES {
+                    if retries >= max_retries {
                         return Err(anyhow!("Maximum retries exceeded"));
                     }
                 }
             }
 
-            tokio::time::sleep(Duration::from_secs(retry_delay)).await;
+            tokio::time::sleep(retry_delay).await;
             retry_delay *= 2;
             retries += 1;
         }
     }
 
     pub fn fetch(&self, endpoint: &str) -> Result<Response> {
-        const MAX_RETRIES: u32 = 2;
-        const INITIAL_RETRY_DELAY: u64 = 5;
+        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let max_retries = self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+        self.fetch_with(endpoint, timeout, max_retries)
+    }
 
+    /// Sends a blocking request that times out after `timeout` and is not retried, unless the
+    /// client overrides either.
+    pub fn fetch_with_timeout(&self, endpoint: &str, timeout: Duration) -> Result<Response> {
+        let timeout = self.timeout.unwrap_or(timeout);
+        let max_retries = self.max_retries.unwrap_or(0);
+        self.fetch_with(endpoint, timeout, max_retries)
+    }
+
+    fn fetch_with(&self, endpoint: &str, timeout: Duration, max_retries: u32) -> Result<Response> {
         let mut retries = 0;
-        let mut retry_delay = INITIAL_RETRY_DELAY;
+        let mut retry_delay = self.initial_retry_delay;
 
         loop {
-            let response = self
-                .client
-                .get(endpoint)
-                .timeout(core::time::Duration::from_secs(300))
-                .send();
+            let response = self.client.get(endpoint).timeout(timeout).send();
 
             match response {
                 Ok(res) => {
@@ -87,7 +110,7 @@ impl ReqwestClient {
                         return Ok(res);
                     } else if res.status().is_server_error() {
                         debug!("Server error: {:?}", res.status());
-                        if retries >= MAX_RETRIES {
+                        if retries >= max_retries {
                             return Err(anyhow!("Maximum retries exceeded"));
                         }
                     } else {
@@ -96,15 +119,21 @@ impl ReqwestClient {
                 }
                 Err(err) => {
                     debug!("Connection error {:?}", err);
-                    if retries >= MAX_RETRIES {
+                    if retries >= max_retries {
                         return Err(anyhow!("Maximum retries exceeded"));
                     }
                 }
             }
 
-            std::thread::sleep(Duration::from_secs(retry_delay));
+            std::thread::sleep(retry_delay);
             retry_delay *= 2;
             retries += 1;
         }
     }
 }
+
+impl Default for ReqwestClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,242 @@
+//! Helpers for writing negative circuit tests and variable round-trip tests.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use plonky2::iop::witness::PartialWitness;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::plonk::prover::prove_with_partition_witness;
+use plonky2::util::timing::TimingTree;
+
+use crate::backend::circuit::{generate_witness, PlonkParameters, PublicInput};
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::CircuitVariable;
+
+/// The message plonky2 panics with when two targets that are connected by a copy constraint are
+/// assigned different values during witness generation.
+const COPY_CONSTRAINT_FAILURE: &str = "set twice with different values";
+
+/// Asserts that the circuit defined by `builder_fn`, given the inputs written by `inputs`, fails
+/// its constraint checks.
+///
+/// Unlike `#[should_panic]`, this only passes if the failure comes from the constraints. A panic
+/// while building the circuit or writing its inputs, or a witness generation error such as a
+/// failed hint, fails the test instead. The constraints fail if either:
+/// - a copy constraint (e.g. from `assert_is_equal`) is violated, which plonky2 detects while
+///   generating the witness, or
+/// - the witness is generated but does not satisfy the gate constraints, so either proving fails
+///   or the resulting proof does not verify.
+///
+/// If `expected` is given, the failure message must contain it.
+pub fn assert_constraint_failure<L: PlonkParameters<D>, const D: usize>(
+    builder_fn: impl FnOnce(&mut CircuitBuilder<L, D>),
+    inputs: impl FnOnce(&mut PublicInput<L, D>),
+    expected: Option<&str>,
+) where
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+        AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+{
+    let mut builder = CircuitBuilder::<L, D>::new();
+    builder_fn(&mut builder);
+    let circuit = builder.build();
+
+    let mut input = circuit.input();
+    inputs(&mut input);
+    let mut pw = PartialWitness::new();
+    circuit.io.set_witness(&mut pw, &input);
+
+    let witness = panic::catch_unwind(AssertUnwindSafe(|| {
+        generate_witness(
+            pw,
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            &circuit.async_hints,
+        )
+    }));
+    let failure = match witness {
+        Ok(Ok(witness)) => {
+            let proof = panic::catch_unwind(AssertUnwindSafe(|| {
+                prove_with_partition_witness::<L::Field, L::Config, D>(
+                    &circuit.data.prover_only,
+                    &circuit.data.common,
+                    witness,
+                    &mut TimingTree::default(),
+                )
+            }));
+            match proof {
+                Ok(Ok(proof)) => match circuit.data.verify(proof) {
+                    Ok(()) => panic!("expected the circuit constraints to fail, but they held"),
+                    Err(e) => e.to_string(),
+                },
+                Ok(Err(e)) => e.to_string(),
+                Err(payload) => panic_message(payload),
+            }
+        }
+        Ok(Err(e)) => panic!(
+            "witness generation failed before the constraints were checked: {}",
+            e
+        ),
+        Err(payload) => {
+            let message = panic_message(payload);
+            assert!(
+                message.contains(COPY_CONSTRAINT_FAILURE),
+                "witness generation panicked before the constraints were checked: {}",
+                message
+            );
+            message
+        }
+    };
+
+    if let Some(expected) = expected {
+        assert!(
+            failure.contains(expected),
+            "constraint failure `{}` does not contain `{}`",
+            failure,
+            expected
+        );
+    }
+}
+
+/// The seed of the random samples of the round-trip tests, so that failures are reproducible.
+pub const ROUNDTRIP_SEED: u64 = 0x5eed;
+
+/// Asserts that `value` round-trips through a variable of type `V`.
+///
+/// The value is set in the witness of a variable created with `V::init`, read back from the
+/// generated witness, and read back from the public output of a proof. This also checks that
+/// `V::from_targets` inverts `targets`, which catches offset bugs in hand-written impls. Failures
+/// report the value, so that a failing random sample can be reproduced.
+pub fn assert_variable_roundtrip<L: PlonkParameters<D>, const D: usize, V: CircuitVariable>(
+    value: V::ValueType<L::Field>,
+) where
+    V::ValueType<L::Field>: PartialEq,
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+        AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+{
+    let mut builder = CircuitBuilder::<L, D>::new();
+    let variable = builder.init::<V>();
+    builder.write(variable.clone());
+    let circuit = builder.build();
+
+    let targets = variable.targets();
+    assert_eq!(
+        V::from_targets(&targets).targets(),
+        targets,
+        "from_targets does not invert targets"
+    );
+
+    let mut pw = PartialWitness::new();
+    variable.set(&mut pw, value.clone());
+    let witness = generate_witness(
+        pw.clone(),
+        &circuit.data.prover_only,
+        &circuit.data.common,
+        &circuit.async_hints,
+    )
+    .unwrap();
+    assert_eq!(
+        variable.get(&witness),
+        value,
+        "witness value mismatch for {:?}",
+        value
+    );
+
+    let (proof, mut output) = circuit.prove_with_partial_witness(pw);
+    circuit.verify(&proof, &circuit.input(), &output);
+    assert_eq!(
+        output.read::<V>(),
+        value,
+        "output value mismatch for {:?}",
+        value
+    );
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{H256, U256};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_assert_constraint_failure() {
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let a = builder.read::<Variable>();
+                let b = builder.read::<Variable>();
+                builder.assert_is_equal(a, b);
+            },
+            |input| {
+                input.write::<Variable>(GoldilocksField::ONE);
+                input.write::<Variable>(GoldilocksField::TWO);
+            },
+            Some(COPY_CONSTRAINT_FAILURE),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected the circuit constraints to fail")]
+    fn test_assert_constraint_failure_satisfied() {
+        assert_constraint_failure::<L, D>(
+            |builder| {
+                let a = builder.read::<Variable>();
+                let b = builder.read::<Variable>();
+                builder.assert_is_equal(a, b);
+            },
+            |input| {
+                input.write::<Variable>(GoldilocksField::ONE);
+                input.write::<Variable>(GoldilocksField::ONE);
+            },
+            None,
+        );
+    }
+
+    /// The number of random samples each round-trip test runs.
+    const ROUNDTRIP_SAMPLES: usize = 8;
+
+    #[test]
+    fn test_byte_variable_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(ROUNDTRIP_SEED);
+        for _ in 0..ROUNDTRIP_SAMPLES {
+            assert_variable_roundtrip::<L, D, ByteVariable>(rng.gen());
+        }
+    }
+
+    #[test]
+    fn test_bytes32_variable_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(ROUNDTRIP_SEED);
+        for _ in 0..ROUNDTRIP_SAMPLES {
+            assert_variable_roundtrip::<L, D, Bytes32Variable>(H256(rng.gen()));
+        }
+    }
+
+    #[test]
+    fn test_u256_variable_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(ROUNDTRIP_SEED);
+        for _ in 0..ROUNDTRIP_SAMPLES {
+            let limbs: [u64; 4] = rng.gen();
+            assert_variable_roundtrip::<L, D, U256Variable>(U256(limbs));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to build")]
+    fn test_assert_constraint_failure_setup_panic() {
+        assert_constraint_failure::<L, D>(|_| panic!("failed to build"), |_| {}, None);
+    }
+}
@@ -4,6 +4,8 @@ mod assert_is_valid;
 mod constant;
 mod elements;
 mod init;
+mod serialize;
+mod ssz;
 mod value;
 mod variables;
 mod witness;
@@ -14,6 +16,8 @@ use elements::{elements, from_elements, nb_elements};
 use init::init_unsafe;
 use proc_macro2::Ident;
 use quote::quote;
+use serialize::{from_bytes, nb_value_bytes, to_bytes};
+use ssz::field_roots;
 use syn::{
     parse_macro_input, parse_quote, Data, DeriveInput, Generics, Meta, Type, Visibility,
     WherePredicate,
@@ -26,6 +30,11 @@ struct StructData {
     fields: Vec<(Option<Ident>, Type, Visibility)>,
 }
 
+/// Implements `CircuitVariable` and `ValueSerializable` for a struct of variables.
+///
+/// The value encoding is the concatenation of the encodings of the fields, so every field type
+/// must implement `ValueSerializable`. This is a breaking change for structs with fields that only
+/// implement `CircuitVariable`; implement `ValueSerializable` for those field types first.
 #[proc_macro_derive(CircuitVariable, attributes(value_name, value_derive))]
 pub fn derive_circuit_variable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -60,13 +69,16 @@ pub fn derive_circuit_variable(input: proc_macro::TokenStream) -> proc_macro::To
     }
 
     let mut generics = input.generics;
+    let mut serialize_generics = generics.clone();
     make_where_clause(&data, &mut generics);
+    make_serialize_where_clause(&mut serialize_generics);
 
     if value_derive.len() > 2 && !generics.params.is_empty() {
         panic!("Cannot use [value_derive] with generic parameters");
     }
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, _, serialize_where_clause) = serialize_generics.split_for_impl();
 
     let (value_generics, value_expanded) = value(&value_ident, &value_derive, &data, &generics);
     let (_, value_ty_generics, _) = value_generics.split_for_impl();
@@ -81,6 +93,9 @@ pub fn derive_circuit_variable(input: proc_macro::TokenStream) -> proc_macro::To
     let elements_expanded = elements(&data);
     let from_elements_expanded = from_elements(&data);
     let nb_elements_expanded = nb_elements(&data);
+    let nb_value_bytes_expanded = nb_value_bytes(&data);
+    let to_bytes_expanded = to_bytes(&data);
+    let from_bytes_expanded = from_bytes(&data);
 
     let expanded = quote! {
 
@@ -118,6 +133,58 @@ pub fn derive_circuit_variable(input: proc_macro::TokenStream) -> proc_macro::To
                 #from_elements_expanded
             }
         }
+
+        impl #impl_generics plonky2x::frontend::vars::ValueSerializable for #name #ty_generics #serialize_where_clause {
+            fn nb_value_bytes() -> usize {
+                #nb_value_bytes_expanded
+            }
+
+            fn to_bytes<F: RichField>(value: Self::ValueType<F>) -> anyhow::Result<Vec<u8>> {
+                #to_bytes_expanded
+            }
+
+            fn from_bytes<F: RichField>(cv_derive_impl_bytes: &[u8]) -> anyhow::Result<Self::ValueType<F>> {
+                #from_bytes_expanded
+            }
+        }
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Implements `Ssz` for a container whose fields are all `SSZVariable`s, in declaration order,
+/// and `SSZVariable` as the merkleization of the roots of those fields.
+#[proc_macro_derive(Ssz)]
+pub fn derive_ssz(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = input.ident;
+    let data = parse_struct_data(input.data);
+
+    let mut generics = input.generics;
+    make_ssz_where_clause(&data, &mut generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let chunk_count = data.fields.len();
+    let field_roots_expanded = field_roots(&data);
+
+    let expanded = quote! {
+        impl #impl_generics Ssz for #name #ty_generics #where_clause {
+            fn chunk_count() -> usize {
+                #chunk_count
+            }
+
+            fn field_roots<L: PlonkParameters<D>, const D: usize>(&self, builder: &mut CircuitBuilder<L, D>) -> Vec<Bytes32Variable> {
+                #field_roots_expanded
+            }
+        }
+
+        impl #impl_generics SSZVariable for #name #ty_generics #where_clause {
+            fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(&self, builder: &mut CircuitBuilder<L, D>) -> Bytes32Variable {
+                let field_roots = <Self as Ssz>::field_roots(self, builder);
+                builder.ssz_merkleize(&field_roots)
+            }
+        }
     };
 
     proc_macro::TokenStream::from(expanded)
@@ -155,3 +222,33 @@ fn make_where_clause(data: &StructData, generics: &mut Generics) {
         .get_or_insert_with(|| parse_quote!(where));
     where_clause.predicates.extend(circuit_var_recurse);
 }
+
+fn make_serialize_where_clause(generics: &mut Generics) {
+    let value_serializable_recurse = generics
+        .type_params()
+        .map(|param| -> WherePredicate {
+            let ident = &param.ident;
+            parse_quote! {
+                #ident: plonky2x::frontend::vars::ValueSerializable
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let where_clause = generics
+        .where_clause
+        .get_or_insert_with(|| parse_quote!(where));
+    where_clause.predicates.extend(value_serializable_recurse);
+}
+
+fn make_ssz_where_clause(data: &StructData, generics: &mut Generics) {
+    let ssz_var_recurse = data.fields.iter().map(|(_, ty, _)| -> WherePredicate {
+        parse_quote! {
+            #ty: SSZVariable
+        }
+    });
+
+    let where_clause = generics
+        .where_clause
+        .get_or_insert_with(|| parse_quote!(where));
+    where_clause.predicates.extend(ssz_var_recurse);
+}
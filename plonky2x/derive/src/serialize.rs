@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::StructData;
+
+pub(crate) fn nb_value_bytes(data: &StructData) -> TokenStream {
+    let recurse = data.fields.iter().map(|(_, ty, _)| {
+        quote! {
+            res += <#ty as plonky2x::frontend::vars::ValueSerializable>::nb_value_bytes();
+        }
+    });
+
+    quote! {
+        let mut res = 0;
+
+        #(#recurse)*
+
+        res
+    }
+}
+
+pub(crate) fn to_bytes(data: &StructData) -> TokenStream {
+    let recurse = data.fields.iter().map(|(name, ty, _)| {
+        quote! {
+            bytes_vec.extend(<#ty as plonky2x::frontend::vars::ValueSerializable>::to_bytes::<F>(value.#name)?);
+        }
+    });
+
+    quote! {
+        let mut bytes_vec = vec![];
+
+        #(#recurse)*
+
+        Ok(bytes_vec)
+    }
+}
+
+pub(crate) fn from_bytes(data: &StructData) -> TokenStream {
+    let value_recurse = data.fields.iter().map(|(name, ty, _)| {
+        quote! {
+            let cv_derive_impl_size = <#ty as plonky2x::frontend::vars::ValueSerializable>::nb_value_bytes();
+            let #name = <#ty as plonky2x::frontend::vars::ValueSerializable>::from_bytes::<F>(&cv_derive_impl_bytes[cv_derive_impl_index..cv_derive_impl_index+cv_derive_impl_size])?;
+            cv_derive_impl_index += cv_derive_impl_size;
+        }
+    });
+
+    let instant_recurse = data.fields.iter().map(|(name, _, _)| {
+        quote! {
+            #name,
+        }
+    });
+
+    quote! {
+        anyhow::ensure!(
+            cv_derive_impl_bytes.len() == <Self as plonky2x::frontend::vars::ValueSerializable>::nb_value_bytes(),
+            "expected {} bytes, got {}",
+            <Self as plonky2x::frontend::vars::ValueSerializable>::nb_value_bytes(),
+            cv_derive_impl_bytes.len()
+        );
+
+        let mut cv_derive_impl_index = 0;
+        #(#value_recurse)*
+
+        Ok(Self::ValueType::<F> {
+            #(#instant_recurse)*
+        })
+    }
+}
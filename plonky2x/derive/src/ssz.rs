@@ -0,0 +1,17 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::StructData;
+
+pub(crate) fn field_roots(data: &StructData) -> TokenStream {
+    let recurse = data.fields.iter().map(|(name, ty, _)| {
+        quote! {
+            <#ty as SSZVariable>::hash_tree_root(&self.#name, builder),
+        }
+    });
+    quote! {
+        vec![
+            #(#recurse)*
+        ]
+    }
+}